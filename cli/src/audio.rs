@@ -1,7 +1,11 @@
 use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
 use cpal::{BufferSize, Device, OutputCallbackInfo, Sample, SampleRate, Stream, StreamConfig};
 use std::error::Error;
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::Path;
 use std::sync::mpsc::{self, Sender};
+use system::WavWriter;
 use tracing::error;
 
 const MAX_SAMPLE_RATE: u32 = 48000;
@@ -12,36 +16,54 @@ struct OutputStream {
 }
 
 pub struct AudioReceiver {
-    device: Device,
+    // No device is opened when muted
+    device: Option<Device>,
     sample_rate: u32,
     stream: Option<OutputStream>,
+    wav: Option<WavWriter<BufWriter<File>>>,
 }
 
 impl AudioReceiver {
-    pub fn new(sample_rate: u32) -> Result<AudioReceiver, Box<dyn Error>> {
-        let host = cpal::default_host();
+    pub fn new(
+        sample_rate: u32,
+        mute: bool,
+        wav_path: Option<&Path>,
+    ) -> Result<AudioReceiver, Box<dyn Error>> {
+        let device = if mute {
+            None
+        } else {
+            let host = cpal::default_host();
+
+            Some(
+                host.default_output_device()
+                    .expect("No audio output device available"),
+            )
+        };
 
-        let device = host
-            .default_output_device()
-            .expect("No audio output device available");
+        let stream = device
+            .as_ref()
+            .map(|device| Self::stream_from(device, sample_rate))
+            .transpose()?
+            .flatten();
 
-        let stream = Self::stream_from(&device, sample_rate)?;
+        let wav = wav_path
+            .map(|path| WavWriter::new(BufWriter::new(File::create(path)?), sample_rate))
+            .transpose()?;
 
         Ok(AudioReceiver {
             device,
             sample_rate,
             stream,
+            wav,
         })
     }
 
-    pub fn set_sample_rate(&mut self, sample_rate: u32) -> Result<(), Box<dyn Error>> {
-        if sample_rate == self.sample_rate {
-            return Ok(());
+    // Completes the WAV file, if one is being written
+    pub fn finish(&mut self) -> Result<(), Box<dyn Error>> {
+        if let Some(wav) = self.wav.take() {
+            wav.finish()?;
         }
 
-        self.sample_rate = sample_rate;
-        self.stream = Self::stream_from(&self.device, sample_rate)?;
-
         Ok(())
     }
 
@@ -95,5 +117,31 @@ impl system::AudioReceiver for AudioReceiver {
         if let Some(stream) = &self.stream {
             stream.sender.send(sample).unwrap();
         }
+
+        if let Some(wav) = &mut self.wav {
+            wav.queue_sample(sample);
+        }
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: u32) {
+        if sample_rate == self.sample_rate {
+            return;
+        }
+
+        self.sample_rate = sample_rate;
+
+        if let Some(wav) = &mut self.wav {
+            wav.set_sample_rate(sample_rate);
+        }
+
+        if let Some(device) = &self.device {
+            self.stream = match Self::stream_from(device, sample_rate) {
+                Ok(stream) => stream,
+                Err(err) => {
+                    error!("Failed to reopen audio stream: {}", err);
+                    None
+                }
+            };
+        }
     }
 }
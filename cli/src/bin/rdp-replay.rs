@@ -0,0 +1,30 @@
+use clap::Parser;
+use std::error::Error;
+use std::fs;
+use std::path::PathBuf;
+
+/// Replays an RDP frame capture (taken with --rdp-capture) and writes the
+/// color image it draws as a PNG
+#[derive(Parser, Debug)]
+struct Args {
+    capture_path: PathBuf,
+
+    output_path: PathBuf,
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let args = Args::parse();
+
+    let data = fs::read(args.capture_path)?;
+    let mut image = system::replay_rdp_capture(&data)?;
+
+    // Alpha holds coverage rather than transparency, so leave it out
+    for pixel in image.pixels.chunks_exact_mut(4) {
+        pixel[3] = 0xff;
+    }
+
+    let output = system::encode_png(image.width, image.height, &image.pixels)?;
+    fs::write(args.output_path, output)?;
+
+    Ok(())
+}
@@ -0,0 +1,19 @@
+use clap::Parser;
+use std::error::Error;
+use std::fs;
+use std::path::PathBuf;
+
+/// Prints a disassembly of an RSP IMEM image, one instruction per line
+#[derive(Parser, Debug)]
+struct Args {
+    imem_path: PathBuf,
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let args = Args::parse();
+
+    let imem = fs::read(args.imem_path)?;
+    print!("{}", system::disassemble_imem(&imem));
+
+    Ok(())
+}
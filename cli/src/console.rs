@@ -0,0 +1,96 @@
+use std::error::Error;
+use std::io::{self, BufRead, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::sync::Arc;
+use std::thread;
+use system::{AudioReceiver, Debugger, Device};
+
+const PROMPT: &str = "(reality) ";
+
+// Interactive front end for the debugger. Commands are read from stdin on a
+// separate thread so that the window stays responsive while stopped.
+pub struct Console {
+    debugger: Debugger,
+    lines: Receiver<String>,
+    interrupted: Arc<AtomicBool>,
+}
+
+impl Console {
+    pub fn new() -> Result<Self, Box<dyn Error>> {
+        let (sender, lines) = mpsc::channel();
+
+        thread::spawn(move || {
+            for line in io::stdin().lock().lines() {
+                let Ok(line) = line else {
+                    break;
+                };
+
+                if sender.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+
+        let interrupted = Arc::new(AtomicBool::new(false));
+
+        ctrlc::set_handler({
+            let interrupted = interrupted.clone();
+            move || interrupted.store(true, Ordering::Relaxed)
+        })?;
+
+        println!("Debugger started. Type 'help' for a list of commands.");
+        prompt();
+
+        Ok(Self {
+            debugger: Debugger::new(),
+            lines,
+            interrupted,
+        })
+    }
+
+    // Returns false if the user has asked to quit
+    pub fn run_frame(&mut self, device: &mut Device, receiver: &mut impl AudioReceiver) -> bool {
+        if self.interrupted.swap(false, Ordering::Relaxed) && !self.debugger.is_stopped() {
+            println!("\n{}", self.debugger.interrupt(device));
+            prompt();
+        }
+
+        if !self.debugger.is_stopped() {
+            if let Some(message) = self.debugger.run_frame(device, receiver) {
+                println!("{}", message);
+                prompt();
+            }
+
+            return true;
+        }
+
+        loop {
+            let line = match self.lines.try_recv() {
+                Ok(line) => line,
+                Err(TryRecvError::Empty) => return true,
+                Err(TryRecvError::Disconnected) => return false,
+            };
+
+            if matches!(line.trim(), "quit" | "q") {
+                return false;
+            }
+
+            match self.debugger.execute(device, &line) {
+                Ok(output) => print!("{}", output),
+                Err(err) => println!("{}", err),
+            }
+
+            if !self.debugger.is_stopped() {
+                return true;
+            }
+
+            prompt();
+        }
+    }
+}
+
+fn prompt() {
+    print!("{}", PROMPT);
+    io::stdout().flush().unwrap();
+}
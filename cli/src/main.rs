@@ -1,40 +1,137 @@
 use audio::AudioReceiver;
-use clap::Parser;
+use clap::{Parser, Subcommand};
+use console::Console;
 use gamepad::Gamepad;
 use std::error::Error;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
 use std::sync::Arc;
 use std::time::Instant;
-use system::{Device, DeviceOptions, DisplayTarget};
+use system::{
+    Accessory, Device, DeviceOptions, DisplayTarget, GdbStatus, GdbStub, GdbTarget, MovieMode,
+    PlaybackStatus, RenderBackend, SaveType, ScreenshotMode,
+};
+use tracing::{error, info};
 use winit::dpi::Size;
 use winit::event::{ElementState, Event, KeyEvent, WindowEvent};
 use winit::event_loop::{ControlFlow, EventLoop};
 use winit::keyboard::{Key, NamedKey};
 use winit::window::WindowBuilder;
 
-#[cfg(feature = "profiling")]
-use tracing::info;
-
 mod audio;
+mod console;
 mod gamepad;
 mod log;
+mod test_rom;
 
 #[derive(Parser, Debug)]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
 struct Args {
-    rom_path: PathBuf,
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    #[arg(required = true)]
+    rom_path: Option<PathBuf>,
 
     #[arg(short, long)]
     pif_data_path: Option<PathBuf>,
 
     #[arg(short, long)]
     granularity: Option<u64>,
+
+    /// Render with the software RDP rather than on the GPU
+    #[arg(long)]
+    software_rdp: bool,
+
+    /// Memory to set aside for rewind history, in MiB
+    #[arg(long, default_value_t = 0)]
+    rewind_budget: usize,
+
+    /// Record controller input to a movie file
+    #[arg(long, conflicts_with = "play_movie")]
+    record_movie: Option<PathBuf>,
+
+    /// Play back controller input from a movie file
+    #[arg(long)]
+    play_movie: Option<PathBuf>,
+
+    /// Wait for a GDB connection on the given port before starting
+    #[arg(long, conflicts_with = "debug")]
+    gdb: Option<u16>,
+
+    /// Debug the RSP rather than the CPU over the GDB connection
+    #[arg(long, requires = "gdb")]
+    gdb_rsp: bool,
+
+    /// Start stopped in the interactive debugger (Ctrl-C breaks back in)
+    #[arg(long)]
+    debug: bool,
+
+    /// Write the RDP commands of the next frame here when F10 is pressed
+    #[arg(long)]
+    rdp_dump: Option<PathBuf>,
+
+    /// Write a replayable capture of the next full RDP frame here when F11 is
+    /// pressed (see the rdp-replay tool)
+    #[arg(long)]
+    rdp_capture: Option<PathBuf>,
+
+    /// Write audio output to a WAV file
+    #[arg(long)]
+    wav: Option<PathBuf>,
+
+    /// Don't play audio (useful alongside --wav)
+    #[arg(long)]
+    mute: bool,
+
+    /// Record video and audio to '<PATH>.y4m' and '<PATH>.wav'
+    #[arg(long)]
+    record: Option<PathBuf>,
+
+    /// Use this save type rather than the one detected from the ROM
+    /// (none, eeprom4k, eeprom16k, sram256k, sram768k, sram1m or flashram)
+    #[arg(long)]
+    save_type: Option<SaveType>,
+
+    /// Per-game settings to use over the bundled ones
+    #[arg(long)]
+    settings: Option<PathBuf>,
+
+    /// Leave the controller's accessory slot empty
+    #[arg(long)]
+    no_controller_pak: bool,
+
+    /// Where F12 saves screenshots (Shift+F12 saves them at window size)
+    #[arg(long, default_value = ".")]
+    screenshot_dir: PathBuf,
 }
 
-fn main() -> Result<(), Box<dyn Error>> {
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Run a test ROM headless, exiting with its result
+    TestRom(test_rom::TestRomArgs),
+}
+
+fn main() -> Result<ExitCode, Box<dyn Error>> {
     let args = Args::parse();
 
-    let rom_data = fs::read(args.rom_path)?;
+    // Returned rather than passed to 'process::exit', so that the log guard
+    // is dropped (and the log flushed) first
+    if let Some(Command::TestRom(test_args)) = args.command {
+        let _guard = log::init()?;
+        return Ok(ExitCode::from(test_rom::run(test_args)?));
+    }
+
+    // Only optional when a subcommand is given
+    let rom_path = args.rom_path.as_deref().unwrap();
+
+    let rom_name = rom_path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default();
+
+    let rom_data = fs::read(rom_path)?;
 
     let pif_data = if let Some(pif_data_path) = args.pif_data_path {
         Some(fs::read(pif_data_path)?)
@@ -42,6 +139,24 @@ fn main() -> Result<(), Box<dyn Error>> {
         None
     };
 
+    let movie = if let Some(play_movie) = &args.play_movie {
+        Some(MovieMode::Playback(fs::read(play_movie)?))
+    } else {
+        args.record_movie.as_ref().map(|_| MovieMode::Record)
+    };
+
+    // Saves are kept next to the ROM. Movies always start without save data,
+    // so that they play back the same way every time.
+    let save_path = movie.is_none().then(|| rom_path.with_extension(""));
+
+    let controller_pak = if args.no_controller_pak {
+        Accessory::None
+    } else {
+        Accessory::ControllerPak
+    };
+
+    let settings = args.settings.as_ref().map(fs::read_to_string).transpose()?;
+
     let _guard = log::init()?;
 
     let event_loop = EventLoop::new()?;
@@ -57,20 +172,61 @@ fn main() -> Result<(), Box<dyn Error>> {
 
     let mut device = Device::new(DeviceOptions {
         display_target: DisplayTarget {
-            window: window.clone(),
+            window: Some(Box::new(window.clone())),
             width: window.inner_size().width,
             height: window.inner_size().height,
         },
         pif_data,
         rom_data,
         granularity: args.granularity,
+        rdp_backend: if args.software_rdp {
+            RenderBackend::Software
+        } else {
+            RenderBackend::Hardware
+        },
+        rewind_budget: args.rewind_budget << 20,
+        movie,
+        debug_sink: None,
+        save_path,
+        save_type: args.save_type,
+        accessories: [
+            controller_pak,
+            Accessory::None,
+            Accessory::None,
+            Accessory::None,
+        ],
+        settings,
     })?;
 
-    let mut audio_receiver = AudioReceiver::new(device.sample_rate())?;
+    let mut audio_receiver =
+        AudioReceiver::new(device.sample_rate(), args.mute, args.wav.as_deref())?;
+
+    if let Some(record) = &args.record {
+        device.start_recording(&record.with_extension("y4m"), &record.with_extension("wav"))?;
+    }
+
+    let gdb_target = if args.gdb_rsp {
+        GdbTarget::Rsp
+    } else {
+        GdbTarget::Cpu
+    };
+
+    let mut gdb = args
+        .gdb
+        .map(|port| GdbStub::listen(port, gdb_target))
+        .transpose()?;
+
+    let mut console = args.debug.then(Console::new).transpose()?;
 
     let mut frame_counter: [Instant; 64] = [Instant::now(); 64];
     let mut frame_counter_index = 0;
 
+    let mut rewinding = false;
+    let mut dumping_rdp = false;
+    let mut shift_held = false;
+
+    let mut playback_status = device.playback_status();
+
     event_loop.run(move |event, elwt| {
         elwt.set_control_flow(ControlFlow::Poll);
 
@@ -88,8 +244,67 @@ fn main() -> Result<(), Box<dyn Error>> {
                             ..
                         },
                     ..
+                } => elwt.exit(),
+                // Hold Backspace to rewind
+                WindowEvent::KeyboardInput {
+                    event:
+                        KeyEvent {
+                            state,
+                            logical_key: Key::Named(NamedKey::Backspace),
+                            ..
+                        },
+                    ..
                 } => {
-                    elwt.exit();
+                    rewinding = state == ElementState::Pressed;
+                }
+                WindowEvent::KeyboardInput {
+                    event:
+                        KeyEvent {
+                            state: ElementState::Pressed,
+                            logical_key: Key::Named(NamedKey::F10),
+                            repeat: false,
+                            ..
+                        },
+                    ..
+                } if args.rdp_dump.is_some() => {
+                    device.start_rdp_capture();
+                    dumping_rdp = true;
+                }
+                WindowEvent::KeyboardInput {
+                    event:
+                        KeyEvent {
+                            state: ElementState::Pressed,
+                            logical_key: Key::Named(NamedKey::F11),
+                            repeat: false,
+                            ..
+                        },
+                    ..
+                } if args.rdp_capture.is_some() => {
+                    device.capture_rdp_frame();
+                }
+                WindowEvent::KeyboardInput {
+                    event:
+                        KeyEvent {
+                            state: ElementState::Pressed,
+                            logical_key: Key::Named(NamedKey::F12),
+                            repeat: false,
+                            ..
+                        },
+                    ..
+                } => {
+                    let mode = if shift_held {
+                        ScreenshotMode::Upscaled
+                    } else {
+                        ScreenshotMode::Native
+                    };
+
+                    match save_screenshot(&mut device, &args.screenshot_dir, &rom_name, mode) {
+                        Ok(path) => info!("Screenshot saved to {}", path.display()),
+                        Err(err) => error!("Failed to save screenshot: {}", err),
+                    }
+                }
+                WindowEvent::ModifiersChanged(modifiers) => {
+                    shift_held = modifiers.state().shift_key();
                 }
                 WindowEvent::Resized(size) => {
                     device.resize(size.width, size.height);
@@ -101,11 +316,64 @@ fn main() -> Result<(), Box<dyn Error>> {
             },
             Event::AboutToWait => {
                 device.update_joypads(gamepad.handle_events());
-                device.run_frame(&mut audio_receiver);
 
-                audio_receiver
-                    .set_sample_rate(device.sample_rate())
-                    .unwrap();
+                if let Some(console) = &mut console {
+                    if !console.run_frame(&mut device, &mut audio_receiver) {
+                        elwt.exit();
+                    }
+                } else if let Some(stub) = &mut gdb {
+                    match stub.run_frame(&mut device, &mut audio_receiver) {
+                        Ok(GdbStatus::Attached) => (),
+                        Ok(GdbStatus::Detached) => gdb = None,
+                        Ok(GdbStatus::Killed) => elwt.exit(),
+                        Err(err) => {
+                            error!("GDB connection failed: {}", err);
+                            gdb = None;
+                        }
+                    }
+                } else if rewinding {
+                    if let Err(err) = device.rewind(1) {
+                        error!("Rewind failed: {}", err);
+                        rewinding = false;
+                    }
+                } else {
+                    device.run_frame(&mut audio_receiver);
+
+                    if dumping_rdp {
+                        let path = args.rdp_dump.as_ref().unwrap();
+                        let text = device.take_rdp_capture().unwrap_or_default();
+
+                        match fs::write(path, text) {
+                            Ok(()) => info!("RDP commands written to {}", path.display()),
+                            Err(err) => error!("Failed to write RDP commands: {}", err),
+                        }
+
+                        dumping_rdp = false;
+                    }
+                }
+
+                if let Some(data) = device.take_rdp_frame_capture() {
+                    let path = args.rdp_capture.as_ref().unwrap();
+
+                    match fs::write(path, data) {
+                        Ok(()) => info!("RDP frame capture written to {}", path.display()),
+                        Err(err) => error!("Failed to write RDP frame capture: {}", err),
+                    }
+                }
+
+                let status = device.playback_status();
+
+                if status != playback_status {
+                    match status {
+                        Some(PlaybackStatus::Finished) => info!("Movie playback finished"),
+                        Some(PlaybackStatus::Desynced(frame)) => {
+                            error!("Movie playback desynced at frame {}", frame)
+                        }
+                        _ => (),
+                    }
+
+                    playback_status = status;
+                }
 
                 let now = Instant::now();
                 let delta = now - frame_counter[frame_counter_index];
@@ -146,9 +414,47 @@ fn main() -> Result<(), Box<dyn Error>> {
 
                 window.request_redraw();
             }
+            Event::LoopExiting => {
+                device.flush_saves();
+
+                if let Some(record_movie) = &args.record_movie {
+                    let data = device.movie_data().unwrap();
+
+                    if let Err(err) = fs::write(record_movie, data) {
+                        error!("Failed to write movie: {}", err);
+                    }
+                }
+
+                if let Err(err) = device.stop_recording() {
+                    error!("Failed to finish recording: {}", err);
+                }
+
+                if let Err(err) = audio_receiver.finish() {
+                    error!("Failed to finish WAV file: {}", err);
+                }
+            }
             _ => (),
         }
     })?;
 
-    Ok(())
+    Ok(ExitCode::SUCCESS)
+}
+
+// Saves to the first free '<rom name>-NNN.png' in the given directory
+fn save_screenshot(
+    device: &mut Device,
+    dir: &Path,
+    rom_name: &str,
+    mode: ScreenshotMode,
+) -> Result<PathBuf, Box<dyn Error>> {
+    let data = device.screenshot(mode)?;
+
+    let path = (0..)
+        .map(|index| dir.join(format!("{}-{:03}.png", rom_name, index)))
+        .find(|path| !path.exists())
+        .unwrap();
+
+    fs::write(&path, data)?;
+
+    Ok(path)
 }
@@ -0,0 +1,115 @@
+use clap::Args;
+use std::cell::RefCell;
+use std::error::Error;
+use std::fs;
+use std::path::PathBuf;
+use std::rc::Rc;
+use system::{Accessory, DebugSink, Device, DeviceOptions, DisplayTarget, RenderBackend, SaveType};
+use tracing::info;
+
+// Exit statuses, so that CI can tell a failure from a test that never finished
+const EXIT_PASS: u8 = 0;
+const EXIT_FAIL: u8 = 1;
+const EXIT_TIMEOUT: u8 = 2;
+
+#[derive(Args, Debug)]
+pub struct TestRomArgs {
+    rom_path: PathBuf,
+
+    #[arg(short, long)]
+    pif_data_path: Option<PathBuf>,
+
+    /// Render with the software RDP rather than on the GPU
+    #[arg(long)]
+    software_rdp: bool,
+
+    /// Use this save type rather than the one detected from the ROM
+    /// (none, eeprom4k, eeprom16k, sram256k, sram768k, sram1m or flashram)
+    #[arg(long)]
+    save_type: Option<SaveType>,
+
+    /// Give up after this many frames
+    #[arg(long, default_value_t = 3600)]
+    frames: u64,
+
+    /// Pass once this text appears in the ISViewer output
+    #[arg(long)]
+    pass: Option<String>,
+
+    /// Fail once this text appears in the ISViewer output
+    #[arg(long)]
+    fail: Option<String>,
+}
+
+// Echoes ISViewer output while keeping a copy to search for markers
+struct OutputLog(Rc<RefCell<String>>);
+
+impl DebugSink for OutputLog {
+    fn write(&mut self, text: &str, _frame: u64, _cycle: u64) {
+        println!("{}", text);
+        let mut output = self.0.borrow_mut();
+        output.push_str(text);
+        output.push('\n');
+    }
+}
+
+struct NullAudio;
+
+impl system::AudioReceiver for NullAudio {
+    fn queue_sample(&mut self, _samples: (i16, i16)) {}
+}
+
+// Runs the ROM headless until it reports a result, echoing its ISViewer
+// output. Writing zero to the exit code register (0x13FF001C) passes, and
+// any other value fails.
+pub fn run(args: TestRomArgs) -> Result<u8, Box<dyn Error>> {
+    let pif_data = args.pif_data_path.map(fs::read).transpose()?;
+    let output = Rc::new(RefCell::new(String::new()));
+
+    let mut device = Device::new(DeviceOptions {
+        display_target: DisplayTarget {
+            window: None,
+            width: 640,
+            height: 480,
+        },
+        pif_data,
+        rom_data: fs::read(&args.rom_path)?,
+        granularity: None,
+        rdp_backend: if args.software_rdp {
+            RenderBackend::Software
+        } else {
+            RenderBackend::Hardware
+        },
+        rewind_budget: 0,
+        movie: None,
+        debug_sink: Some(Box::new(OutputLog(output.clone()))),
+        save_path: None,
+        save_type: args.save_type,
+        accessories: [Accessory::None; 4],
+        settings: None,
+    })?;
+
+    for frame in 0..args.frames {
+        device.run_frame(&mut NullAudio);
+
+        let output = output.borrow();
+
+        if let Some(code) = device.exit_code() {
+            info!("Exit code {} written at frame {}", code, frame);
+            return Ok(if code == 0 { EXIT_PASS } else { EXIT_FAIL });
+        }
+
+        if args.fail.as_ref().is_some_and(|fail| output.contains(fail)) {
+            info!("Failure marker found at frame {}", frame);
+            return Ok(EXIT_FAIL);
+        }
+
+        if args.pass.as_ref().is_some_and(|pass| output.contains(pass)) {
+            info!("Pass marker found at frame {}", frame);
+            return Ok(EXIT_PASS);
+        }
+    }
+
+    info!("No result after {} frames", args.frames);
+    Ok(EXIT_TIMEOUT)
+}
@@ -2,28 +2,63 @@ use super::{RCP_CLOCK_RATE, VIDEO_DAC_RATE};
 use crate::interrupt::{RcpIntType, RcpInterrupt};
 use crate::memory::{Size, WriteMask};
 use crate::rdram::Rdram;
+use crate::snapshot::{Reader, Writer};
 use regs::Regs;
+use std::error::Error;
 use tracing::{debug, trace};
 
 mod regs;
 
 pub trait AudioReceiver {
     fn queue_sample(&mut self, samples: (i16, i16));
+
+    // Called before the first sample at a new rate
+    fn set_sample_rate(&mut self, _sample_rate: u32) {}
 }
 
+// A sample rate and the samples output at it
+pub type SampleRun = (u32, Vec<(i16, i16)>);
+
 #[derive(Debug)]
 struct Dma {
     dram_addr: u32,
     len: u32,
 }
 
+impl Dma {
+    fn save_state(dma: &Option<Dma>, writer: &mut Writer) {
+        writer.bool(dma.is_some());
+
+        if let Some(dma) = dma {
+            writer.u32(dma.dram_addr);
+            writer.u32(dma.len);
+        }
+    }
+
+    fn load_state(reader: &mut Reader) -> Result<Option<Dma>, Box<dyn Error>> {
+        if !reader.bool()? {
+            return Ok(None);
+        }
+
+        Ok(Some(Dma {
+            dram_addr: reader.u32()?,
+            len: reader.u32()?,
+        }))
+    }
+}
+
 pub struct AudioInterface {
     regs: Regs,
     cycles_remaining: u32,
     cycles_per_sample: u32,
     sample_rate: u32,
+    // Set when the rate changes, until the receiver has been told
+    rate_changed: bool,
     dma_active: Option<Dma>,
     dma_pending: Option<Dma>,
+    // Samples output since recording started, alongside those sent to the
+    // receiver, as runs of samples at the same rate
+    recorded: Option<Vec<SampleRun>>,
     rcp_int: RcpInterrupt,
 }
 
@@ -38,8 +73,10 @@ impl AudioInterface {
             cycles_remaining: cycles_per_sample,
             cycles_per_sample,
             sample_rate,
+            rate_changed: false,
             dma_active: None,
             dma_pending: None,
+            recorded: None,
             rcp_int,
         }
     }
@@ -48,6 +85,47 @@ impl AudioInterface {
         self.sample_rate
     }
 
+    pub fn set_recording(&mut self, recording: bool) {
+        self.recorded = recording.then(Vec::new);
+    }
+
+    // Returns the samples output since this was last called, along with the
+    // rate of each run of samples
+    pub fn take_recorded(&mut self) -> Vec<SampleRun> {
+        self.recorded
+            .as_mut()
+            .map(std::mem::take)
+            .unwrap_or_default()
+    }
+
+    pub fn save_state(&self, writer: &mut Writer) {
+        writer.tag(b"AI  ");
+        writer.reg(self.regs.dram_addr);
+        writer.reg(self.regs.control);
+        writer.reg(self.regs.dacrate);
+        writer.reg(self.regs.bitrate);
+        writer.u32(self.cycles_remaining);
+        writer.u32(self.cycles_per_sample);
+        writer.u32(self.sample_rate);
+        Dma::save_state(&self.dma_active, writer);
+        Dma::save_state(&self.dma_pending, writer);
+    }
+
+    pub fn load_state(&mut self, reader: &mut Reader) -> Result<(), Box<dyn Error>> {
+        reader.tag(b"AI  ")?;
+        self.regs.dram_addr = reader.reg()?;
+        self.regs.control = reader.reg()?;
+        self.regs.dacrate = reader.reg()?;
+        self.regs.bitrate = reader.reg()?;
+        self.cycles_remaining = reader.u32()?;
+        self.cycles_per_sample = reader.u32()?;
+        self.sample_rate = reader.u32()?;
+        self.rate_changed = true;
+        self.dma_active = Dma::load_state(reader)?;
+        self.dma_pending = Dma::load_state(reader)?;
+        Ok(())
+    }
+
     #[inline(always)]
     pub fn step(&mut self, rdram: &Rdram, receiver: &mut impl AudioReceiver) {
         self.cycles_remaining -= 1;
@@ -62,10 +140,9 @@ impl AudioInterface {
     fn step_inner(&mut self, rdram: &Rdram, receiver: &mut impl AudioReceiver) {
         self.cycles_remaining = self.cycles_per_sample;
 
-        if let Some(dma_active) = &mut self.dma_active {
+        let sample = if let Some(dma_active) = &mut self.dma_active {
             let left: u16 = rdram.read_single(dma_active.dram_addr as usize);
             let right: u16 = rdram.read_single((dma_active.dram_addr + 2) as usize);
-            receiver.queue_sample((left as i16, right as i16));
             trace!("AI DMA: 4 bytes read from {:08X}", dma_active.dram_addr);
 
             dma_active.dram_addr = (dma_active.dram_addr + 4) & 0x00ff_ffff;
@@ -81,8 +158,25 @@ impl AudioInterface {
                     self.rcp_int.raise(RcpIntType::AI);
                 }
             }
+
+            (left as i16, right as i16)
         } else {
-            receiver.queue_sample((0, 0));
+            (0, 0)
+        };
+
+        if std::mem::take(&mut self.rate_changed) {
+            receiver.set_sample_rate(self.sample_rate);
+        }
+
+        receiver.queue_sample(sample);
+
+        if let Some(recorded) = &mut self.recorded {
+            match recorded.last_mut() {
+                Some((sample_rate, samples)) if *sample_rate == self.sample_rate => {
+                    samples.push(sample)
+                }
+                _ => recorded.push((self.sample_rate, vec![sample])),
+            }
         }
     }
 
@@ -153,6 +247,7 @@ impl AudioInterface {
                 mask.write_reg("AI_DACRATE", &mut self.regs.dacrate);
                 (self.cycles_per_sample, self.sample_rate) =
                     calc_cycles_per_sample(self.regs.dacrate.dacrate());
+                self.rate_changed = true;
             }
             5 => mask.write_reg("AI_BITRATE", &mut self.regs.bitrate),
             _ => todo!("AI Register Write: {:08X} <= {:08X}", address, mask.raw()),
@@ -1,7 +1,11 @@
+pub use disassembler::disassemble;
+
 use crate::memory::Size;
+use crate::snapshot::{Reader, Writer};
 use cache::ICache;
 use cp0::{Cp0, Exception};
 use cp1::Cp1;
+use std::error::Error;
 use tracing::trace;
 
 #[cfg(feature = "dcache")]
@@ -10,6 +14,7 @@ use cache::{DCache, DCacheLine};
 mod cache;
 mod cp0;
 mod cp1;
+mod disassembler;
 mod instruction;
 
 const COLD_RESET_VECTOR: u32 = 0xbfc0_0000;
@@ -63,12 +68,14 @@ pub struct Cpu {
 }
 
 impl Cpu {
-    const REG_NAMES: [&'static str; 32] = [
+    pub const REG_NAMES: [&'static str; 32] = [
         "ZERO", "AT", "V0", "V1", "A0", "A1", "A2", "A3", "T0", "T1", "T2", "T3", "T4", "T5", "T6",
         "T7", "S0", "S1", "S2", "S3", "S4", "S5", "S6", "S7", "T8", "T9", "K0", "K1", "GP", "SP",
         "FP", "RA",
     ];
 
+    pub const CP0_REG_NAMES: [&'static str; 32] = Cp0::REG_NAMES;
+
     pub fn new(skip_pif_rom: bool) -> Self {
         let mut regs = [0; 32];
 
@@ -101,6 +108,165 @@ impl Cpu {
         }
     }
 
+    // Cheap digest of the register file, used to detect divergence when
+    // playing back input movies
+    pub fn checksum(&self) -> u64 {
+        self.regs
+            .iter()
+            .chain([self.hi, self.lo].iter())
+            .fold(self.pc[0] as u64, |acc, &value| {
+                acc.rotate_left(7) ^ value as u64
+            })
+    }
+
+    // True if the next step will begin executing an instruction
+    pub fn is_ready(&self) -> bool {
+        self.stall == 0 && !self.busy_wait
+    }
+
+    // Address of the next instruction to be executed
+    pub fn pc(&self) -> u32 {
+        self.pc[0]
+    }
+
+    // Jumps directly to the given address, discarding any pending branch
+    pub fn set_pc(&mut self, bus: &mut impl Bus, pc: u32) {
+        self.pc = [pc, pc.wrapping_add(4), pc.wrapping_add(8)];
+        self.delay = [false, false];
+        self.opcode[0] = self.read_opcode(bus, self.pc[0]);
+        self.opcode[1] = self.read_opcode(bus, self.pc[1]);
+    }
+
+    pub fn reg(&self, reg: usize) -> i64 {
+        self.regs[reg]
+    }
+
+    pub fn hi(&self) -> i64 {
+        self.hi
+    }
+
+    pub fn set_hi(&mut self, value: i64) {
+        self.hi = value;
+    }
+
+    pub fn lo(&self) -> i64 {
+        self.lo
+    }
+
+    pub fn set_lo(&mut self, value: i64) {
+        self.lo = value;
+    }
+
+    pub fn cp0_reg(&mut self, reg: usize) -> i64 {
+        self.cp0.read_reg(reg)
+    }
+
+    pub fn set_cp0_reg(&mut self, reg: usize, value: i64) {
+        self.cp0.write_reg(reg, value);
+    }
+
+    pub fn cp1_reg(&self, reg: usize) -> i64 {
+        self.cp1.raw_reg(reg)
+    }
+
+    pub fn set_cp1_reg(&mut self, reg: usize, value: i64) {
+        self.cp1.set_raw_reg(reg, value);
+    }
+
+    pub fn cp1_control_reg(&self, reg: usize) -> u32 {
+        self.cp1.read_control_reg(reg)
+    }
+
+    pub fn set_cp1_control_reg(&mut self, reg: usize, value: u32) {
+        self.cp1.write_control_reg(reg, value);
+    }
+
+    // Translates a virtual address without raising any exceptions
+    pub fn translate(&self, vaddr: u32) -> Option<u32> {
+        if (vaddr >> 30) == 2 {
+            return Some(vaddr & 0x1fff_ffff);
+        }
+
+        self.cp0
+            .translate(vaddr)
+            .filter(|result| result.valid)
+            .map(|result| result.paddr)
+    }
+
+    // Drops any cached instructions for the given physical address, for when
+    // memory is modified behind the CPU's back
+    pub fn invalidate_icache(&mut self, paddr: u32) {
+        self.icache.invalidate(paddr);
+    }
+
+    pub fn save_state(&self, writer: &mut Writer) {
+        writer.tag(b"CPU ");
+        writer.u64(self.stall);
+        writer.bool(self.busy_wait);
+
+        for opcode in self.opcode {
+            writer.u32(opcode);
+        }
+
+        for delay in self.delay {
+            writer.bool(delay);
+        }
+
+        for pc in self.pc {
+            writer.u32(pc);
+        }
+
+        for reg in self.regs {
+            writer.i64(reg);
+        }
+
+        writer.i64(self.hi);
+        writer.i64(self.lo);
+        writer.bool(self.ll_bit);
+
+        self.cp0.save_state(writer);
+        self.cp1.save_state(writer);
+        self.icache.save_state(writer);
+
+        #[cfg(feature = "dcache")]
+        self.dcache.save_state(writer);
+    }
+
+    pub fn load_state(&mut self, reader: &mut Reader) -> Result<(), Box<dyn Error>> {
+        reader.tag(b"CPU ")?;
+        self.stall = reader.u64()?;
+        self.busy_wait = reader.bool()?;
+
+        for opcode in &mut self.opcode {
+            *opcode = reader.u32()?;
+        }
+
+        for delay in &mut self.delay {
+            *delay = reader.bool()?;
+        }
+
+        for pc in &mut self.pc {
+            *pc = reader.u32()?;
+        }
+
+        for reg in &mut self.regs {
+            *reg = reader.i64()?;
+        }
+
+        self.hi = reader.i64()?;
+        self.lo = reader.i64()?;
+        self.ll_bit = reader.bool()?;
+
+        self.cp0.load_state(reader)?;
+        self.cp1.load_state(reader)?;
+        self.icache.load_state(reader)?;
+
+        #[cfg(feature = "dcache")]
+        self.dcache.load_state(reader)?;
+
+        Ok(())
+    }
+
     #[cfg(feature = "profiling")]
     pub fn stats(&self) -> &Stats {
         &self.stats
@@ -159,6 +325,12 @@ impl Cpu {
             return;
         }
 
+        trace!(
+            "{:08X}: {}",
+            self.pc[0],
+            disassemble(self.pc[0], self.opcode[0])
+        );
+
         instruction::execute(self, bus);
 
         self.delay[0] = self.delay[1];
@@ -172,7 +344,7 @@ impl Cpu {
         self.opcode[1] = self.read_opcode(bus, self.pc[1]);
     }
 
-    fn set_reg(&mut self, reg: usize, value: i64) {
+    pub fn set_reg(&mut self, reg: usize, value: i64) {
         self.regs[reg] = value;
         self.regs[0] = 0;
         trace!("  {}: {:016X}", Self::REG_NAMES[reg], value);
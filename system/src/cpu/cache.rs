@@ -1,5 +1,7 @@
 use crate::memory::Memory;
+use crate::snapshot::{Reader, Writer};
 use std::array;
+use std::error::Error;
 use tracing::trace;
 
 #[cfg(feature = "dcache")]
@@ -23,6 +25,28 @@ impl ICache {
         }
     }
 
+    pub fn save_state(&self, writer: &mut Writer) {
+        writer.tag(b"ICAC");
+
+        for line in &self.lines {
+            writer.bytes(line.data.as_bytes());
+            writer.u32(line.ptag);
+            writer.bool(line.valid);
+        }
+    }
+
+    pub fn load_state(&mut self, reader: &mut Reader) -> Result<(), Box<dyn Error>> {
+        reader.tag(b"ICAC")?;
+
+        for line in &mut self.lines {
+            reader.bytes_into(line.data.as_bytes_mut())?;
+            line.ptag = reader.u32()?;
+            line.valid = reader.bool()?;
+        }
+
+        Ok(())
+    }
+
     pub fn line_mut(&mut self, address: u32) -> &mut ICacheLine {
         let index = ((address >> 5) & 0x01ff) as usize;
         &mut self.lines[index]
@@ -49,6 +73,20 @@ impl ICache {
         line.data.read(vaddr as usize & 0x1f)
     }
 
+    // Lines are indexed by virtual address, so every line the physical
+    // address could be cached in has to be checked
+    pub fn invalidate(&mut self, paddr: u32) {
+        for high in 0..4 {
+            let index = ((high << 7) | ((paddr >> 5) & 0x7f)) as usize;
+            let line = &mut self.lines[index];
+
+            if line.matches(paddr) {
+                line.clear_valid_flag();
+                trace!("ICache Line {} invalidated", index);
+            }
+        }
+    }
+
     pub fn index_store_tag(&mut self, address: u32, ptag: u32, valid: bool) {
         let index = ((address >> 5) & 0x01ff) as usize;
         let line = &mut self.lines[index];
@@ -94,6 +132,30 @@ impl DCache {
         }
     }
 
+    pub fn save_state(&self, writer: &mut Writer) {
+        writer.tag(b"DCAC");
+
+        for line in &self.lines {
+            writer.bytes(line.data.as_bytes());
+            writer.u32(line.ptag);
+            writer.bool(line.valid);
+            writer.bool(line.dirty);
+        }
+    }
+
+    pub fn load_state(&mut self, reader: &mut Reader) -> Result<(), Box<dyn Error>> {
+        reader.tag(b"DCAC")?;
+
+        for line in &mut self.lines {
+            reader.bytes_into(line.data.as_bytes_mut())?;
+            line.ptag = reader.u32()?;
+            line.valid = reader.bool()?;
+            line.dirty = reader.bool()?;
+        }
+
+        Ok(())
+    }
+
     pub fn find_mut(&mut self, address: u32) -> Option<&mut DCacheLine> {
         let index = ((address >> 4) & 0x01ff) as usize;
         let line = &mut self.lines[index];
@@ -213,3 +275,30 @@ impl DCacheLine {
         self.dirty = false;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn invalidates_lines_by_physical_address() {
+        let mut icache = ICache::new();
+        let mut reloads = 0;
+
+        // Virtual page differs from the physical page in the index bits
+        let vaddr = 0x8000_3040;
+        let paddr = 0x0000_1040;
+
+        icache.read(vaddr, paddr, |_| reloads += 1);
+        icache.read(vaddr, paddr, |_| reloads += 1);
+        assert_eq!(reloads, 1);
+
+        icache.invalidate(paddr + 0x1000);
+        icache.read(vaddr, paddr, |_| reloads += 1);
+        assert_eq!(reloads, 1);
+
+        icache.invalidate(paddr + 0x1c);
+        icache.read(vaddr, paddr, |_| reloads += 1);
+        assert_eq!(reloads, 2);
+    }
+}
@@ -4,7 +4,9 @@ pub use regs::TagLo;
 pub use tlb::TlbResult;
 
 use super::{Bus, Cpu};
+use crate::snapshot::{Reader, Writer};
 use regs::{Regs, REG_NAMES};
+use std::error::Error;
 use std::ops::{BitAnd, BitOr, Not};
 use tlb::Tlb;
 use tracing::{debug, trace, warn};
@@ -41,6 +43,68 @@ impl Cp0 {
         }
     }
 
+    pub fn save_state(&self, writer: &mut Writer) {
+        let regs = &self.regs;
+
+        writer.tag(b"CP0 ");
+        writer.reg(regs.index);
+        writer.u32(regs.random);
+        writer.reg(regs.entry_lo0);
+        writer.reg(regs.entry_lo1);
+        writer.reg64(regs.context);
+        writer.reg(regs.page_mask);
+        writer.u32(regs.wired);
+        writer.u32(regs.bad_vaddr);
+        writer.u32(regs.count);
+        writer.reg64(regs.entry_hi);
+        writer.u32(regs.compare);
+        writer.reg(regs.status);
+        writer.reg(regs.cause);
+        writer.i64(regs.epc);
+        writer.reg(regs.config);
+        writer.u32(regs.ll_addr);
+        writer.reg(regs.watch_lo);
+        writer.reg(regs.watch_hi);
+        writer.reg64(regs.x_context);
+        writer.reg(regs.tag_lo);
+        writer.u32(regs.tag_hi);
+        writer.i64(regs.error_epc);
+        writer.u8(self.int_mask);
+
+        self.tlb.save_state(writer);
+    }
+
+    pub fn load_state(&mut self, reader: &mut Reader) -> Result<(), Box<dyn Error>> {
+        let regs = &mut self.regs;
+
+        reader.tag(b"CP0 ")?;
+        regs.index = reader.reg()?;
+        regs.random = reader.u32()?;
+        regs.entry_lo0 = reader.reg()?;
+        regs.entry_lo1 = reader.reg()?;
+        regs.context = reader.reg64()?;
+        regs.page_mask = reader.reg()?;
+        regs.wired = reader.u32()?;
+        regs.bad_vaddr = reader.u32()?;
+        regs.count = reader.u32()?;
+        regs.entry_hi = reader.reg64()?;
+        regs.compare = reader.u32()?;
+        regs.status = reader.reg()?;
+        regs.cause = reader.reg()?;
+        regs.epc = reader.i64()?;
+        regs.config = reader.reg()?;
+        regs.ll_addr = reader.u32()?;
+        regs.watch_lo = reader.reg()?;
+        regs.watch_hi = reader.reg()?;
+        regs.x_context = reader.reg64()?;
+        regs.tag_lo = reader.reg()?;
+        regs.tag_hi = reader.u32()?;
+        regs.error_epc = reader.i64()?;
+        self.int_mask = reader.u8()?;
+
+        self.tlb.load_state(reader)
+    }
+
     pub fn cp1_usable(&self) -> bool {
         self.regs.status.cu1()
     }
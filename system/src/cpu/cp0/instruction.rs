@@ -1,7 +1,5 @@
 use super::regs;
-use super::Cp0;
 use super::Cpu;
-use tracing::trace;
 
 mod tlb;
 mod transfer;
@@ -25,8 +23,6 @@ pub fn cop0(cpu: &mut Cpu) {
 }
 
 fn eret(cpu: &mut Cpu) {
-    trace!("{:08X}: ERET", cpu.pc[0]);
-
     let regs = &mut cpu.cp0.regs;
 
     if regs.status.erl() {
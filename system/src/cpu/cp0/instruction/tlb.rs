@@ -3,26 +3,21 @@ use super::Cpu;
 use tracing::trace;
 
 pub fn tlbr(cpu: &mut Cpu) {
-    trace!("{:08X}: TLBR", cpu.pc[0]);
     let index = cpu.cp0.regs.index.index() as usize;
     cpu.cp0.tlb.read_entry(&mut cpu.cp0.regs, index);
 }
 
 pub fn tlbwi(cpu: &mut Cpu) {
-    trace!("{:08X}: TLBWI", cpu.pc[0]);
     let index = cpu.cp0.regs.index.index() as usize;
     cpu.cp0.tlb.write_entry(&cpu.cp0.regs, index);
 }
 
 pub fn tlbwr(cpu: &mut Cpu) {
-    trace!("{:08X}: TLBWR", cpu.pc[0]);
     let index = cpu.cp0.regs.random as usize;
     cpu.cp0.tlb.write_entry(&cpu.cp0.regs, index);
 }
 
 pub fn tlbp(cpu: &mut Cpu) {
-    trace!("{:08X}: TLBP", cpu.pc[0]);
-
     let regs = &mut cpu.cp0.regs;
 
     let index = cpu.cp0.tlb.entries().position(|entry| {
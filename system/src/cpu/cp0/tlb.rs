@@ -1,4 +1,6 @@
 use super::regs::{EntryHi, EntryLo, PageMask, Regs};
+use crate::snapshot::{Reader, Writer};
+use std::error::Error;
 use std::slice::Iter;
 use tracing::trace;
 
@@ -35,6 +37,30 @@ impl Tlb {
         self.entries.iter()
     }
 
+    pub fn save_state(&self, writer: &mut Writer) {
+        writer.tag(b"TLB ");
+
+        for entry in &self.entries {
+            writer.reg(entry.entry_lo0);
+            writer.reg(entry.entry_lo1);
+            writer.reg64(entry.entry_hi);
+            writer.reg(entry.page_mask);
+        }
+    }
+
+    pub fn load_state(&mut self, reader: &mut Reader) -> Result<(), Box<dyn Error>> {
+        reader.tag(b"TLB ")?;
+
+        for entry in &mut self.entries {
+            entry.entry_lo0 = reader.reg()?;
+            entry.entry_lo1 = reader.reg()?;
+            entry.entry_hi = reader.reg64()?;
+            entry.page_mask = reader.reg()?;
+        }
+
+        Ok(())
+    }
+
     pub fn read_entry(&self, regs: &mut Regs, index: usize) {
         let entry = &self.entries[index];
 
@@ -2,15 +2,16 @@ pub use instruction::{cop1, ldc1, lwc1, sdc1, swc1};
 
 use super::cp0;
 use super::{Bus, Cpu};
+use crate::snapshot::{Reader, Writer};
 use bytemuck::Pod;
 use regs::Status;
+use std::error::Error;
 use tracing::trace;
 
 mod instruction;
 mod regs;
 
 pub trait Format: Pod {
-    const NAME: &'static str;
     fn cp1_reg(cpu: &Cpu, reg: usize) -> Self;
     fn set_cp1_reg(cpu: &mut Cpu, reg: usize, value: Self);
     fn to_f32(self) -> f32;
@@ -45,6 +46,36 @@ impl Cp1 {
         }
     }
 
+    pub fn save_state(&self, writer: &mut Writer) {
+        writer.tag(b"CP1 ");
+
+        for reg in self.regs {
+            writer.i64(reg);
+        }
+
+        writer.reg(self.status);
+    }
+
+    pub fn load_state(&mut self, reader: &mut Reader) -> Result<(), Box<dyn Error>> {
+        reader.tag(b"CP1 ")?;
+
+        for reg in &mut self.regs {
+            *reg = reader.i64()?;
+        }
+
+        self.status = reader.reg()?;
+        Ok(())
+    }
+
+    // Register contents without regard to the current FR mode
+    pub fn raw_reg(&self, reg: usize) -> i64 {
+        self.regs[reg]
+    }
+
+    pub fn set_raw_reg(&mut self, reg: usize, value: i64) {
+        self.regs[reg] = value;
+    }
+
     pub fn read_control_reg(&self, reg: usize) -> u32 {
         match reg {
             0 => 0x0a00,
@@ -70,8 +101,6 @@ impl Cp1 {
 }
 
 impl Format for i32 {
-    const NAME: &'static str = "W";
-
     fn cp1_reg(cpu: &Cpu, reg: usize) -> Self {
         if cpu.cp0.is_fr() || (reg & 1) == 0 {
             cpu.cp1.regs[reg] as i32
@@ -104,8 +133,6 @@ impl Format for i32 {
 impl Int for i32 {}
 
 impl Format for i64 {
-    const NAME: &'static str = "L";
-
     fn cp1_reg(cpu: &Cpu, reg: usize) -> Self {
         if cpu.cp0.is_fr() {
             cpu.cp1.regs[reg]
@@ -135,8 +162,6 @@ impl Format for i64 {
 impl Int for i64 {}
 
 impl Format for f32 {
-    const NAME: &'static str = "S";
-
     fn cp1_reg(cpu: &Cpu, reg: usize) -> Self {
         Self::from_bits(if cpu.cp0.is_fr() || (reg & 1) == 0 {
             cpu.cp1.regs[reg] as u32
@@ -181,8 +206,6 @@ impl Float for f32 {
 }
 
 impl Format for f64 {
-    const NAME: &'static str = "D";
-
     fn cp1_reg(cpu: &Cpu, mut reg: usize) -> Self {
         if !cpu.cp0.is_fr() {
             reg &= !1;
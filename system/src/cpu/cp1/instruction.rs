@@ -1,7 +1,7 @@
 pub use transfer::{ldc1, lwc1, sdc1, swc1};
 
 use super::cp0;
-use super::{Bus, Cp1, Cpu, Float, Format, Int};
+use super::{Bus, Cpu, Float, Format, Int};
 
 mod arithmetic;
 mod branch;
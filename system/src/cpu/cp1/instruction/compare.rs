@@ -6,11 +6,10 @@ use std::cmp::Ordering;
 use tracing::trace;
 
 macro_rules! condition {
-    ($struct:ident, $name:literal, $ordered:expr, $pattern:pat $(if $guard:expr)? $(,)?) => {
+    ($struct:ident, $ordered:expr, $pattern:pat $(if $guard:expr)? $(,)?) => {
         pub struct $struct;
 
         impl Condition for $struct {
-            const NAME: &'static str = $name;
             const ORDERED: bool = $ordered;
 
             fn test(ord: Option<Ordering>) -> bool {
@@ -21,7 +20,6 @@ macro_rules! condition {
 }
 
 pub trait Condition {
-    const NAME: &'static str;
     const ORDERED: bool;
     fn test(ord: Option<Ordering>) -> bool;
 }
@@ -30,15 +28,6 @@ pub fn c<C: Condition, F: Float>(cpu: &mut Cpu) {
     let ft = ((cpu.opcode[0] >> 16) & 31) as usize;
     let fs = ((cpu.opcode[0] >> 11) & 31) as usize;
 
-    trace!(
-        "{:08X}: C.{}.{} F{}, F{}",
-        cpu.pc[0],
-        C::NAME,
-        F::NAME,
-        fs,
-        ft
-    );
-
     let result = F::cp1_reg(cpu, fs).partial_cmp(&F::cp1_reg(cpu, ft));
 
     if C::ORDERED && result.is_none() {
@@ -51,34 +40,27 @@ pub fn c<C: Condition, F: Float>(cpu: &mut Cpu) {
     cpu.stall += 1;
 }
 
-condition!(F, "F", false, _ if false);
-condition!(UN, "UN", false, None);
-condition!(EQ, "EQ", false, Some(Ordering::Equal));
-condition!(UEQ, "UEQ", false, None | Some(Ordering::Equal));
-condition!(OLT, "OLT", false, Some(Ordering::Less));
-condition!(ULT, "ULT", false, None | Some(Ordering::Less));
-condition!(
-    OLE,
-    "OLE",
-    false,
-    Some(Ordering::Less) | Some(Ordering::Equal)
-);
+condition!(F, false, _ if false);
+condition!(UN, false, None);
+condition!(EQ, false, Some(Ordering::Equal));
+condition!(UEQ, false, None | Some(Ordering::Equal));
+condition!(OLT, false, Some(Ordering::Less));
+condition!(ULT, false, None | Some(Ordering::Less));
+condition!(OLE, false, Some(Ordering::Less) | Some(Ordering::Equal));
 condition!(
     ULE,
-    "ULE",
     false,
     None | Some(Ordering::Less) | Some(Ordering::Equal)
 );
-condition!(SF, "SF", true, _ if false);
-condition!(NGLE, "NGLE", true, None);
-condition!(SEQ, "EQ", true, Some(Ordering::Equal));
-condition!(NGL, "NGL", true, None | Some(Ordering::Equal));
-condition!(LT, "LT", true, Some(Ordering::Less));
-condition!(NGE, "NGE", true, None | Some(Ordering::Less));
-condition!(LE, "LE", true, Some(Ordering::Less) | Some(Ordering::Equal));
+condition!(SF, true, _ if false);
+condition!(NGLE, true, None);
+condition!(SEQ, true, Some(Ordering::Equal));
+condition!(NGL, true, None | Some(Ordering::Equal));
+condition!(LT, true, Some(Ordering::Less));
+condition!(NGE, true, None | Some(Ordering::Less));
+condition!(LE, true, Some(Ordering::Less) | Some(Ordering::Equal));
 condition!(
     NGT,
-    "NGT",
     true,
     None | Some(Ordering::Less) | Some(Ordering::Equal)
 );
@@ -1,13 +1,11 @@
 use super::cp0;
-use super::{Bus, Cp1, Cpu, Format};
+use super::{Bus, Cpu, Format};
 use tracing::trace;
 
 pub fn mfc1(cpu: &mut Cpu) {
     let rt = ((cpu.opcode[0] >> 16) & 31) as usize;
     let rd = ((cpu.opcode[0] >> 11) & 31) as usize;
 
-    trace!("{:08X}: MFC1 {}, F{}", cpu.pc[0], Cpu::REG_NAMES[rt], rd,);
-
     cpu.set_reg(rt, i32::cp1_reg(cpu, rd) as i64);
 }
 
@@ -15,8 +13,6 @@ pub fn dmfc1(cpu: &mut Cpu) {
     let rt = ((cpu.opcode[0] >> 16) & 31) as usize;
     let rd = ((cpu.opcode[0] >> 11) & 31) as usize;
 
-    trace!("{:08X}: DMFC1 {}, F{}", cpu.pc[0], Cpu::REG_NAMES[rt], rd,);
-
     cpu.set_reg(rt, i64::cp1_reg(cpu, rd));
 }
 
@@ -24,8 +20,6 @@ pub fn mtc1(cpu: &mut Cpu) {
     let rt = ((cpu.opcode[0] >> 16) & 31) as usize;
     let rd = ((cpu.opcode[0] >> 11) & 31) as usize;
 
-    trace!("{:08X}: MTC1 {}, F{}", cpu.pc[0], Cpu::REG_NAMES[rt], rd,);
-
     i32::set_cp1_reg(cpu, rd, cpu.regs[rt] as i32)
 }
 
@@ -33,8 +27,6 @@ pub fn dmtc1(cpu: &mut Cpu) {
     let rt = ((cpu.opcode[0] >> 16) & 31) as usize;
     let rd = ((cpu.opcode[0] >> 11) & 31) as usize;
 
-    trace!("{:08X}: DMTC1 {}, F{}", cpu.pc[0], Cpu::REG_NAMES[rt], rd,);
-
     i64::set_cp1_reg(cpu, rd, cpu.regs[rt])
 }
 
@@ -42,13 +34,6 @@ pub fn cfc1(cpu: &mut Cpu) {
     let rt = ((cpu.opcode[0] >> 16) & 31) as usize;
     let rd = ((cpu.opcode[0] >> 11) & 31) as usize;
 
-    trace!(
-        "{:08X}: CFC1 {}, {:?}",
-        cpu.pc[0],
-        Cpu::REG_NAMES[rt],
-        Cp1::CONTROL_REG_NAMES[rd]
-    );
-
     cpu.set_reg(rt, cpu.cp1.read_control_reg(rd) as i64);
 }
 
@@ -56,13 +41,6 @@ pub fn ctc1(cpu: &mut Cpu) {
     let rt = ((cpu.opcode[0] >> 16) & 31) as usize;
     let rd = ((cpu.opcode[0] >> 11) & 31) as usize;
 
-    trace!(
-        "{:08X}: CTC1 {}, {:?}",
-        cpu.pc[0],
-        Cpu::REG_NAMES[rt],
-        Cp1::CONTROL_REG_NAMES[rd]
-    );
-
     cpu.cp1.write_control_reg(rd, cpu.regs[rt] as u32);
 }
 
@@ -76,14 +54,6 @@ pub fn lwc1(cpu: &mut Cpu, bus: &mut impl Bus) {
     let rt = ((cpu.opcode[0] >> 16) & 31) as usize;
     let offset = (cpu.opcode[0] & 0xffff) as i16 as i64;
 
-    trace!(
-        "{:08X}: LWC1 F{}, {}({})",
-        cpu.pc[0],
-        rt,
-        offset,
-        Cpu::REG_NAMES[base],
-    );
-
     let address = cpu.regs[base].wrapping_add(offset) as u32;
     assert!((address & 3) == 0);
 
@@ -103,14 +73,6 @@ pub fn ldc1(cpu: &mut Cpu, bus: &mut impl Bus) {
     let rt = ((cpu.opcode[0] >> 16) & 31) as usize;
     let offset = (cpu.opcode[0] & 0xffff) as i16 as i64;
 
-    trace!(
-        "{:08X}: LDC1 F{}, {}({})",
-        cpu.pc[0],
-        rt,
-        offset,
-        Cpu::REG_NAMES[base],
-    );
-
     let address = cpu.regs[base].wrapping_add(offset) as u32;
     assert!((address & 7) == 0);
 
@@ -130,14 +92,6 @@ pub fn swc1(cpu: &mut Cpu, bus: &mut impl Bus) {
     let rt = ((cpu.opcode[0] >> 16) & 31) as usize;
     let offset = (cpu.opcode[0] & 0xffff) as i16 as i64;
 
-    trace!(
-        "{:08X}: SWC1 F{}, {}({})",
-        cpu.pc[0],
-        rt,
-        offset,
-        Cpu::REG_NAMES[base],
-    );
-
     let address = cpu.regs[base].wrapping_add(offset) as u32;
     let value = i32::cp1_reg(cpu, rt) as u32;
     assert!((address & 3) == 0);
@@ -155,14 +109,6 @@ pub fn sdc1(cpu: &mut Cpu, bus: &mut impl Bus) {
     let rt = ((cpu.opcode[0] >> 16) & 31) as usize;
     let offset = (cpu.opcode[0] & 0xffff) as i16 as i64;
 
-    trace!(
-        "{:08X}: SDC1 F{}, {}({})",
-        cpu.pc[0],
-        rt,
-        offset,
-        Cpu::REG_NAMES[base],
-    );
-
     let addr = cpu.regs[base].wrapping_add(offset) as u32;
     let value = i64::cp1_reg(cpu, rt) as u64;
     assert!((addr & 7) == 0);
@@ -0,0 +1,393 @@
+use super::{Cp0, Cp1, Cpu};
+
+const COMPARE_CONDITIONS: [&str; 16] = [
+    "F", "UN", "EQ", "UEQ", "OLT", "ULT", "OLE", "ULE", "SF", "NGLE", "SEQ", "NGL", "LT", "NGE",
+    "LE", "NGT",
+];
+
+// Decodes a single VR4300 instruction. 'pc' is the address of the
+// instruction, and is used to resolve branch and jump targets.
+pub fn disassemble(pc: u32, word: u32) -> String {
+    let ins = Instruction { pc, word };
+
+    match word >> 26 {
+        0o00 => special(ins),
+        0o01 => regimm(ins),
+        0o02 => ins.jump("J"),
+        0o03 => ins.jump("JAL"),
+        0o04 => ins.branch_rs_rt("BEQ"),
+        0o05 => ins.branch_rs_rt("BNE"),
+        0o06 => ins.branch_rs("BLEZ"),
+        0o07 => ins.branch_rs("BGTZ"),
+        0o10 => ins.i_type("ADDI"),
+        0o11 => ins.i_type("ADDIU"),
+        0o12 => ins.i_type("SLTI"),
+        0o13 => ins.i_type("SLTIU"),
+        0o14 => ins.i_type_hex("ANDI"),
+        0o15 => ins.i_type_hex("ORI"),
+        0o16 => ins.i_type_hex("XORI"),
+        0o17 => format!("LUI {}, 0x{:04X}", ins.rt(), ins.uimm()),
+        0o20 => cop0(ins),
+        0o21 => cop1(ins),
+        0o24 => ins.branch_rs_rt("BEQL"),
+        0o25 => ins.branch_rs_rt("BNEL"),
+        0o26 => ins.branch_rs("BLEZL"),
+        0o27 => ins.branch_rs("BGTZL"),
+        0o30 => ins.i_type("DADDI"),
+        0o31 => ins.i_type("DADDIU"),
+        0o32 => ins.load_store("LDL", ins.rt()),
+        0o33 => ins.load_store("LDR", ins.rt()),
+        0o40 => ins.load_store("LB", ins.rt()),
+        0o41 => ins.load_store("LH", ins.rt()),
+        0o42 => ins.load_store("LWL", ins.rt()),
+        0o43 => ins.load_store("LW", ins.rt()),
+        0o44 => ins.load_store("LBU", ins.rt()),
+        0o45 => ins.load_store("LHU", ins.rt()),
+        0o46 => ins.load_store("LWR", ins.rt()),
+        0o47 => ins.load_store("LWU", ins.rt()),
+        0o50 => ins.load_store("SB", ins.rt()),
+        0o51 => ins.load_store("SH", ins.rt()),
+        0o52 => ins.load_store("SWL", ins.rt()),
+        0o53 => ins.load_store("SW", ins.rt()),
+        0o54 => ins.load_store("SDL", ins.rt()),
+        0o55 => ins.load_store("SDR", ins.rt()),
+        0o56 => ins.load_store("SWR", ins.rt()),
+        0o57 => ins.load_store("CACHE", format!("0b{:05b}", ins.rt_index())),
+        0o60 => ins.load_store("LL", ins.rt()),
+        0o61 => ins.load_store("LWC1", ins.ft()),
+        0o64 => ins.load_store("LLD", ins.rt()),
+        0o65 => ins.load_store("LDC1", ins.ft()),
+        0o67 => ins.load_store("LD", ins.rt()),
+        0o70 => ins.load_store("SC", ins.rt()),
+        0o71 => ins.load_store("SWC1", ins.ft()),
+        0o74 => ins.load_store("SCD", ins.rt()),
+        0o75 => ins.load_store("SDC1", ins.ft()),
+        0o77 => ins.load_store("SD", ins.rt()),
+        _ => ins.unknown(),
+    }
+}
+
+fn special(ins: Instruction) -> String {
+    match ins.word & 63 {
+        0o00 if ins.word == 0 => "NOP".into(),
+        0o00 => ins.shift_fixed("SLL"),
+        0o02 => ins.shift_fixed("SRL"),
+        0o03 => ins.shift_fixed("SRA"),
+        0o04 => ins.shift_variable("SLLV"),
+        0o06 => ins.shift_variable("SRLV"),
+        0o07 => ins.shift_variable("SRAV"),
+        0o10 => format!("JR {}", ins.rs()),
+        0o11 => format!("JALR {}, {}", ins.rd(), ins.rs()),
+        0o14 => "SYSCALL".into(),
+        0o15 => "BREAK".into(),
+        0o17 => "SYNC".into(),
+        0o20 => format!("MFHI {}", ins.rd()),
+        0o21 => format!("MTHI {}", ins.rs()),
+        0o22 => format!("MFLO {}", ins.rd()),
+        0o23 => format!("MTLO {}", ins.rs()),
+        0o24 => ins.shift_variable("DSLLV"),
+        0o26 => ins.shift_variable("DSRLV"),
+        0o27 => ins.shift_variable("DSRAV"),
+        0o30 => ins.rs_rt("MULT"),
+        0o31 => ins.rs_rt("MULTU"),
+        0o32 => ins.rs_rt("DIV"),
+        0o33 => ins.rs_rt("DIVU"),
+        0o34 => ins.rs_rt("DMULT"),
+        0o35 => ins.rs_rt("DMULTU"),
+        0o36 => ins.rs_rt("DDIV"),
+        0o37 => ins.rs_rt("DDIVU"),
+        0o40 => ins.r_type("ADD"),
+        0o41 => ins.r_type("ADDU"),
+        0o42 => ins.r_type("SUB"),
+        0o43 => ins.r_type("SUBU"),
+        0o44 => ins.r_type("AND"),
+        0o45 => ins.r_type("OR"),
+        0o46 => ins.r_type("XOR"),
+        0o47 => ins.r_type("NOR"),
+        0o52 => ins.r_type("SLT"),
+        0o53 => ins.r_type("SLTU"),
+        0o54 => ins.r_type("DADD"),
+        0o55 => ins.r_type("DADDU"),
+        0o56 => ins.r_type("DSUB"),
+        0o57 => ins.r_type("DSUBU"),
+        0o60 => ins.rs_rt("TGE"),
+        0o61 => ins.rs_rt("TGEU"),
+        0o62 => ins.rs_rt("TLT"),
+        0o63 => ins.rs_rt("TLTU"),
+        0o64 => ins.rs_rt("TEQ"),
+        0o66 => ins.rs_rt("TNE"),
+        0o70 => ins.shift_fixed("DSLL"),
+        0o72 => ins.shift_fixed("DSRL"),
+        0o73 => ins.shift_fixed("DSRA"),
+        0o74 => ins.shift_fixed("DSLL32"),
+        0o76 => ins.shift_fixed("DSRL32"),
+        0o77 => ins.shift_fixed("DSRA32"),
+        _ => ins.unknown(),
+    }
+}
+
+fn regimm(ins: Instruction) -> String {
+    match (ins.word >> 16) & 31 {
+        0o00 => ins.branch_rs("BLTZ"),
+        0o01 => ins.branch_rs("BGEZ"),
+        0o02 => ins.branch_rs("BLTZL"),
+        0o03 => ins.branch_rs("BGEZL"),
+        0o10 => ins.rs_imm("TGEI"),
+        0o11 => ins.rs_imm("TGEIU"),
+        0o12 => ins.rs_imm("TLTI"),
+        0o13 => ins.rs_imm("TLTIU"),
+        0o14 => ins.rs_imm("TEQI"),
+        0o16 => ins.rs_imm("TNEI"),
+        0o20 => ins.branch_rs("BLTZAL"),
+        0o21 => ins.branch_rs("BGEZAL"),
+        0o22 => ins.branch_rs("BLTZALL"),
+        0o23 => ins.branch_rs("BGEZALL"),
+        _ => ins.unknown(),
+    }
+}
+
+fn cop0(ins: Instruction) -> String {
+    let cp0_reg = Cp0::REG_NAMES[ins.rd_index()];
+
+    match ins.rs_index() {
+        0o00 => format!("MFC0 {}, {}", ins.rt(), cp0_reg),
+        0o01 => format!("DMFC0 {}, {}", ins.rt(), cp0_reg),
+        0o04 => format!("MTC0 {}, {}", ins.rt(), cp0_reg),
+        0o05 => format!("DMTC0 {}, {}", ins.rt(), cp0_reg),
+        0o20..=0o37 => match ins.word & 63 {
+            0o01 => "TLBR".into(),
+            0o02 => "TLBWI".into(),
+            0o06 => "TLBWR".into(),
+            0o10 => "TLBP".into(),
+            0o30 => "ERET".into(),
+            _ => ins.unknown(),
+        },
+        _ => ins.unknown(),
+    }
+}
+
+fn cop1(ins: Instruction) -> String {
+    let control_reg = Cp1::CONTROL_REG_NAMES[ins.rd_index()];
+
+    let format = match ins.rs_index() {
+        0o00 => return format!("MFC1 {}, {}", ins.rt(), ins.fs()),
+        0o01 => return format!("DMFC1 {}, {}", ins.rt(), ins.fs()),
+        0o02 => return format!("CFC1 {}, {}", ins.rt(), control_reg),
+        0o04 => return format!("MTC1 {}, {}", ins.rt(), ins.fs()),
+        0o05 => return format!("DMTC1 {}, {}", ins.rt(), ins.fs()),
+        0o06 => return format!("CTC1 {}, {}", ins.rt(), control_reg),
+        0o10 => {
+            return match ins.rt_index() {
+                0o00 => ins.branch("BC1F"),
+                0o01 => ins.branch("BC1T"),
+                0o02 => ins.branch("BC1FL"),
+                0o03 => ins.branch("BC1TL"),
+                _ => ins.unknown(),
+            }
+        }
+        0o20 => "S",
+        0o21 => "D",
+        0o24 => "W",
+        0o25 => "L",
+        _ => return ins.unknown(),
+    };
+
+    let name = match ins.word & 63 {
+        0o00 => "ADD",
+        0o01 => "SUB",
+        0o02 => "MUL",
+        0o03 => "DIV",
+        0o04 => "SQRT",
+        0o05 => "ABS",
+        0o06 => "MOV",
+        0o07 => "NEG",
+        0o10 => "ROUND.L",
+        0o11 => "TRUNC.L",
+        0o12 => "CEIL.L",
+        0o13 => "FLOOR.L",
+        0o14 => "ROUND.W",
+        0o15 => "TRUNC.W",
+        0o16 => "CEIL.W",
+        0o17 => "FLOOR.W",
+        0o40 => "CVT.S",
+        0o41 => "CVT.D",
+        0o44 => "CVT.W",
+        0o45 => "CVT.L",
+        func @ 0o60..=0o77 => {
+            return format!(
+                "C.{}.{} {}, {}",
+                COMPARE_CONDITIONS[(func & 15) as usize],
+                format,
+                ins.fs(),
+                ins.ft()
+            )
+        }
+        _ => return ins.unknown(),
+    };
+
+    match ins.word & 63 {
+        0o00..=0o03 => format!(
+            "{}.{} {}, {}, {}",
+            name,
+            format,
+            ins.fd(),
+            ins.fs(),
+            ins.ft()
+        ),
+        _ => format!("{}.{} {}, {}", name, format, ins.fd(), ins.fs()),
+    }
+}
+
+#[derive(Copy, Clone)]
+struct Instruction {
+    pc: u32,
+    word: u32,
+}
+
+impl Instruction {
+    fn rs_index(self) -> usize {
+        ((self.word >> 21) & 31) as usize
+    }
+
+    fn rt_index(self) -> usize {
+        ((self.word >> 16) & 31) as usize
+    }
+
+    fn rd_index(self) -> usize {
+        ((self.word >> 11) & 31) as usize
+    }
+
+    fn rs(self) -> &'static str {
+        Cpu::REG_NAMES[self.rs_index()]
+    }
+
+    fn rt(self) -> &'static str {
+        Cpu::REG_NAMES[self.rt_index()]
+    }
+
+    fn rd(self) -> &'static str {
+        Cpu::REG_NAMES[self.rd_index()]
+    }
+
+    fn ft(self) -> String {
+        format!("F{}", self.rt_index())
+    }
+
+    fn fs(self) -> String {
+        format!("F{}", self.rd_index())
+    }
+
+    fn fd(self) -> String {
+        format!("F{}", (self.word >> 6) & 31)
+    }
+
+    fn sa(self) -> u32 {
+        (self.word >> 6) & 31
+    }
+
+    fn imm(self) -> i16 {
+        self.word as i16
+    }
+
+    fn uimm(self) -> u16 {
+        self.word as u16
+    }
+
+    fn branch_target(self) -> u32 {
+        self.pc
+            .wrapping_add(4)
+            .wrapping_add(((self.imm() as i32) << 2) as u32)
+    }
+
+    fn r_type(self, name: &str) -> String {
+        format!("{} {}, {}, {}", name, self.rd(), self.rs(), self.rt())
+    }
+
+    fn rs_rt(self, name: &str) -> String {
+        format!("{} {}, {}", name, self.rs(), self.rt())
+    }
+
+    fn rs_imm(self, name: &str) -> String {
+        format!("{} {}, {}", name, self.rs(), self.imm())
+    }
+
+    fn i_type(self, name: &str) -> String {
+        format!("{} {}, {}, {}", name, self.rt(), self.rs(), self.imm())
+    }
+
+    fn i_type_hex(self, name: &str) -> String {
+        format!(
+            "{} {}, {}, 0x{:04X}",
+            name,
+            self.rt(),
+            self.rs(),
+            self.uimm()
+        )
+    }
+
+    fn shift_fixed(self, name: &str) -> String {
+        format!("{} {}, {}, {}", name, self.rd(), self.rt(), self.sa())
+    }
+
+    fn shift_variable(self, name: &str) -> String {
+        format!("{} {}, {}, {}", name, self.rd(), self.rt(), self.rs())
+    }
+
+    fn load_store(self, name: &str, target: impl AsRef<str>) -> String {
+        format!(
+            "{} {}, {}({})",
+            name,
+            target.as_ref(),
+            self.imm(),
+            self.rs()
+        )
+    }
+
+    fn jump(self, name: &str) -> String {
+        let target = (self.pc.wrapping_add(4) & 0xf000_0000) | ((self.word & 0x03ff_ffff) << 2);
+        format!("{} 0x{:08X}", name, target)
+    }
+
+    fn branch(self, name: &str) -> String {
+        format!("{} 0x{:08X}", name, self.branch_target())
+    }
+
+    fn branch_rs(self, name: &str) -> String {
+        format!("{} {}, 0x{:08X}", name, self.rs(), self.branch_target())
+    }
+
+    fn branch_rs_rt(self, name: &str) -> String {
+        format!(
+            "{} {}, {}, 0x{:08X}",
+            name,
+            self.rs(),
+            self.rt(),
+            self.branch_target()
+        )
+    }
+
+    fn unknown(self) -> String {
+        format!(".word 0x{:08X}", self.word)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_common_instructions() {
+        assert_eq!(disassemble(0x8000_0400, 0x0000_0000), "NOP");
+        assert_eq!(disassemble(0x8000_0400, 0x27bd_ffe0), "ADDIU SP, SP, -32");
+        assert_eq!(disassemble(0x8000_0400, 0x8fbf_0014), "LW RA, 20(SP)");
+        assert_eq!(
+            disassemble(0x8000_0400, 0x1440_fffe),
+            "BNE V0, ZERO, 0x800003FC"
+        );
+        assert_eq!(disassemble(0x8000_0400, 0x0c00_0180), "JAL 0x80000600");
+        assert_eq!(disassemble(0x8000_0400, 0x4082_6000), "MTC0 V0, Status");
+        assert_eq!(disassemble(0x8000_0400, 0x4600_1080), "ADD.S F2, F2, F0");
+        assert_eq!(disassemble(0x8000_0400, 0x4620_103c), "C.LT.D F2, F0");
+        assert_eq!(disassemble(0x8000_0400, 0xec00_0000), ".word 0xEC000000");
+    }
+}
@@ -1,9 +1,7 @@
 use super::cp0::{self, Exception};
 use super::Cpu;
-use tracing::trace;
 
 pub trait ArithmeticOperator {
-    const NAME: &'static str;
     fn apply_checked(lhs: i64, rhs: i64) -> Option<i64>;
     fn apply_unchecked(lhs: i64, rhs: i64) -> i64;
 }
@@ -14,8 +12,6 @@ pub struct Sub;
 pub struct Dsub;
 
 impl ArithmeticOperator for Add {
-    const NAME: &'static str = "ADD";
-
     fn apply_checked(lhs: i64, rhs: i64) -> Option<i64> {
         (lhs as i32)
             .checked_add(rhs as i32)
@@ -28,8 +24,6 @@ impl ArithmeticOperator for Add {
 }
 
 impl ArithmeticOperator for Dadd {
-    const NAME: &'static str = "DADD";
-
     fn apply_checked(lhs: i64, rhs: i64) -> Option<i64> {
         lhs.checked_add(rhs)
     }
@@ -40,8 +34,6 @@ impl ArithmeticOperator for Dadd {
 }
 
 impl ArithmeticOperator for Sub {
-    const NAME: &'static str = "SUB";
-
     fn apply_checked(lhs: i64, rhs: i64) -> Option<i64> {
         (lhs as i32)
             .checked_sub(rhs as i32)
@@ -54,8 +46,6 @@ impl ArithmeticOperator for Sub {
 }
 
 impl ArithmeticOperator for Dsub {
-    const NAME: &'static str = "DSUB";
-
     fn apply_checked(lhs: i64, rhs: i64) -> Option<i64> {
         lhs.checked_sub(rhs)
     }
@@ -70,15 +60,6 @@ pub fn i_type_checked<Op: ArithmeticOperator>(cpu: &mut Cpu) {
     let rt = ((cpu.opcode[0] >> 16) & 31) as usize;
     let imm = (cpu.opcode[0] & 0xffff) as i16 as i64;
 
-    trace!(
-        "{:08X}: {}I {}, {}, {}",
-        cpu.pc[0],
-        Op::NAME,
-        Cpu::REG_NAMES[rt],
-        Cpu::REG_NAMES[rs],
-        imm
-    );
-
     let Some(result) = Op::apply_checked(cpu.regs[rs], imm) else {
         cp0::except(cpu, Exception::ArithmeticOverflow);
         return;
@@ -92,15 +73,6 @@ pub fn i_type_unchecked<Op: ArithmeticOperator>(cpu: &mut Cpu) {
     let rt = ((cpu.opcode[0] >> 16) & 31) as usize;
     let imm = (cpu.opcode[0] & 0xffff) as i16 as i64;
 
-    trace!(
-        "{:08X}: {}IU {}, {}, {}",
-        cpu.pc[0],
-        Op::NAME,
-        Cpu::REG_NAMES[rt],
-        Cpu::REG_NAMES[rs],
-        imm
-    );
-
     cpu.set_reg(rt, Op::apply_unchecked(cpu.regs[rs], imm));
 }
 
@@ -109,15 +81,6 @@ pub fn r_type_checked<Op: ArithmeticOperator>(cpu: &mut Cpu) {
     let rt = ((cpu.opcode[0] >> 16) & 31) as usize;
     let rd = ((cpu.opcode[0] >> 11) & 31) as usize;
 
-    trace!(
-        "{:08X}: {} {}, {}, {}",
-        cpu.pc[0],
-        Op::NAME,
-        Cpu::REG_NAMES[rd],
-        Cpu::REG_NAMES[rs],
-        Cpu::REG_NAMES[rt],
-    );
-
     let Some(result) = Op::apply_checked(cpu.regs[rs], cpu.regs[rt]) else {
         cp0::except(cpu, Exception::ArithmeticOverflow);
         return;
@@ -131,14 +94,5 @@ pub fn r_type_unchecked<Op: ArithmeticOperator>(cpu: &mut Cpu) {
     let rt = ((cpu.opcode[0] >> 16) & 31) as usize;
     let rd = ((cpu.opcode[0] >> 11) & 31) as usize;
 
-    trace!(
-        "{:08X}: {}U {}, {}, {}",
-        cpu.pc[0],
-        Op::NAME,
-        Cpu::REG_NAMES[rd],
-        Cpu::REG_NAMES[rs],
-        Cpu::REG_NAMES[rt],
-    );
-
     cpu.set_reg(rd, Op::apply_unchecked(cpu.regs[rs], cpu.regs[rt]));
 }
@@ -1,8 +1,6 @@
 use super::Cpu;
-use tracing::trace;
 
 pub trait BitwiseOperator {
-    const NAME: &'static str;
     fn apply(lhs: i64, rhs: i64) -> i64;
 }
 
@@ -12,32 +10,24 @@ pub struct Xor;
 pub struct Nor;
 
 impl BitwiseOperator for And {
-    const NAME: &'static str = "AND";
-
     fn apply(lhs: i64, rhs: i64) -> i64 {
         lhs & rhs
     }
 }
 
 impl BitwiseOperator for Or {
-    const NAME: &'static str = "OR";
-
     fn apply(lhs: i64, rhs: i64) -> i64 {
         lhs | rhs
     }
 }
 
 impl BitwiseOperator for Xor {
-    const NAME: &'static str = "XOR";
-
     fn apply(lhs: i64, rhs: i64) -> i64 {
         lhs ^ rhs
     }
 }
 
 impl BitwiseOperator for Nor {
-    const NAME: &'static str = "NOR";
-
     fn apply(lhs: i64, rhs: i64) -> i64 {
         !(lhs | rhs)
     }
@@ -48,15 +38,6 @@ pub fn i_type<Op: BitwiseOperator>(cpu: &mut Cpu) {
     let rt = ((cpu.opcode[0] >> 16) & 31) as usize;
     let imm = (cpu.opcode[0] & 0xffff) as u64 as i64;
 
-    trace!(
-        "{:08X}: {}I {}, {}, 0x{:04X}",
-        cpu.pc[0],
-        Op::NAME,
-        Cpu::REG_NAMES[rt],
-        Cpu::REG_NAMES[rs],
-        imm
-    );
-
     cpu.set_reg(rt, Op::apply(cpu.regs[rs], imm));
 }
 
@@ -65,14 +46,5 @@ pub fn r_type<Op: BitwiseOperator>(cpu: &mut Cpu) {
     let rt = ((cpu.opcode[0] >> 16) & 31) as usize;
     let rd = ((cpu.opcode[0] >> 11) & 31) as usize;
 
-    trace!(
-        "{:08X}: {} {}, {}, {}",
-        cpu.pc[0],
-        Op::NAME,
-        Cpu::REG_NAMES[rd],
-        Cpu::REG_NAMES[rs],
-        Cpu::REG_NAMES[rt],
-    );
-
     cpu.set_reg(rd, Op::apply(cpu.regs[rs], cpu.regs[rt]));
 }
@@ -2,7 +2,6 @@ use super::{Bus, Cp0, Cpu};
 use tracing::trace;
 
 pub trait LoadOperator {
-    const NAME: &'static str;
     fn apply(cpu: &mut Cpu, bus: &mut impl Bus, reg: usize, addr: u32) -> Option<i64>;
 }
 
@@ -21,8 +20,6 @@ pub struct Ll;
 pub struct Lld;
 
 impl LoadOperator for Lb {
-    const NAME: &'static str = "LB";
-
     fn apply(cpu: &mut Cpu, bus: &mut impl Bus, _reg: usize, addr: u32) -> Option<i64> {
         let value = cpu.read_data::<u8>(bus, addr)?;
         trace!("  [{:08X} => {:02X}]", addr, value);
@@ -31,8 +28,6 @@ impl LoadOperator for Lb {
 }
 
 impl LoadOperator for Lbu {
-    const NAME: &'static str = "LBU";
-
     fn apply(cpu: &mut Cpu, bus: &mut impl Bus, _reg: usize, addr: u32) -> Option<i64> {
         let value = cpu.read_data::<u8>(bus, addr)?;
         trace!("  [{:08X} => {:02X}]", addr, value);
@@ -41,8 +36,6 @@ impl LoadOperator for Lbu {
 }
 
 impl LoadOperator for Lh {
-    const NAME: &'static str = "LH";
-
     fn apply(cpu: &mut Cpu, bus: &mut impl Bus, _reg: usize, addr: u32) -> Option<i64> {
         assert!((addr & 1) == 0);
         let value = cpu.read_data::<u16>(bus, addr)?;
@@ -52,8 +45,6 @@ impl LoadOperator for Lh {
 }
 
 impl LoadOperator for Lhu {
-    const NAME: &'static str = "LHU";
-
     fn apply(cpu: &mut Cpu, bus: &mut impl Bus, _reg: usize, addr: u32) -> Option<i64> {
         assert!((addr & 1) == 0);
         let value = cpu.read_data::<u16>(bus, addr)?;
@@ -63,8 +54,6 @@ impl LoadOperator for Lhu {
 }
 
 impl LoadOperator for Lw {
-    const NAME: &'static str = "LW";
-
     fn apply(cpu: &mut Cpu, bus: &mut impl Bus, _reg: usize, addr: u32) -> Option<i64> {
         assert!((addr & 3) == 0);
         let value = cpu.read_data::<u32>(bus, addr)?;
@@ -74,8 +63,6 @@ impl LoadOperator for Lw {
 }
 
 impl LoadOperator for Lwu {
-    const NAME: &'static str = "LWU";
-
     fn apply(cpu: &mut Cpu, bus: &mut impl Bus, _reg: usize, addr: u32) -> Option<i64> {
         assert!((addr & 3) == 0);
         let value = cpu.read_data::<u32>(bus, addr)?;
@@ -85,8 +72,6 @@ impl LoadOperator for Lwu {
 }
 
 impl LoadOperator for Lwl {
-    const NAME: &'static str = "LWL";
-
     fn apply(cpu: &mut Cpu, bus: &mut impl Bus, reg: usize, addr: u32) -> Option<i64> {
         let value = cpu.read_data::<u32>(bus, addr & !3)?;
         trace!("  [{:08X} => {:08X}]", addr, value);
@@ -96,8 +81,6 @@ impl LoadOperator for Lwl {
 }
 
 impl LoadOperator for Lwr {
-    const NAME: &'static str = "LWR";
-
     fn apply(cpu: &mut Cpu, bus: &mut impl Bus, reg: usize, addr: u32) -> Option<i64> {
         let value = cpu.read_data::<u32>(bus, addr & !3)?;
         trace!("  [{:08X} => {:08X}]", addr, value);
@@ -107,8 +90,6 @@ impl LoadOperator for Lwr {
 }
 
 impl LoadOperator for Ld {
-    const NAME: &'static str = "LD";
-
     fn apply(cpu: &mut Cpu, bus: &mut impl Bus, _reg: usize, addr: u32) -> Option<i64> {
         assert!((addr & 7) == 0);
         let value = cpu.read_data::<u64>(bus, addr)?;
@@ -118,8 +99,6 @@ impl LoadOperator for Ld {
 }
 
 impl LoadOperator for Ldl {
-    const NAME: &'static str = "LDL";
-
     fn apply(cpu: &mut Cpu, bus: &mut impl Bus, reg: usize, addr: u32) -> Option<i64> {
         let value = cpu.read_data::<u64>(bus, addr & !7)?;
         trace!("  [{:08X} => {:016X}]", addr, value);
@@ -129,8 +108,6 @@ impl LoadOperator for Ldl {
 }
 
 impl LoadOperator for Ldr {
-    const NAME: &'static str = "LDR";
-
     fn apply(cpu: &mut Cpu, bus: &mut impl Bus, reg: usize, addr: u32) -> Option<i64> {
         // TODO: Stall cycles
         let value = cpu.read_data::<u64>(bus, addr & !7)?;
@@ -141,8 +118,6 @@ impl LoadOperator for Ldr {
 }
 
 impl LoadOperator for Ll {
-    const NAME: &'static str = "LL";
-
     fn apply(cpu: &mut Cpu, bus: &mut impl Bus, _reg: usize, addr: u32) -> Option<i64> {
         assert!((addr & 3) == 0);
         let value = cpu.read_data::<u32>(bus, addr)?;
@@ -157,8 +132,6 @@ impl LoadOperator for Ll {
 }
 
 impl LoadOperator for Lld {
-    const NAME: &'static str = "LLD";
-
     fn apply(cpu: &mut Cpu, bus: &mut impl Bus, _reg: usize, addr: u32) -> Option<i64> {
         assert!((addr & 7) == 0);
         let value = cpu.read_data::<u64>(bus, addr)?;
@@ -176,13 +149,6 @@ pub fn lui(cpu: &mut Cpu) {
     let rt = ((cpu.opcode[0] >> 16) & 31) as usize;
     let imm = (cpu.opcode[0] & 0xffff) as i16;
 
-    trace!(
-        "{:08X}: LUI {}, 0x{:04X}",
-        cpu.pc[0],
-        Cpu::REG_NAMES[rt],
-        imm
-    );
-
     cpu.set_reg(rt, ((imm as i32) << 16) as i64);
 }
 
@@ -191,15 +157,6 @@ pub fn load<Op: LoadOperator>(cpu: &mut Cpu, bus: &mut impl Bus) {
     let rt = ((cpu.opcode[0] >> 16) & 31) as usize;
     let offset = (cpu.opcode[0] & 0xffff) as i16 as i64;
 
-    trace!(
-        "{:08X}: {} {}, {}({})",
-        cpu.pc[0],
-        Op::NAME,
-        Cpu::REG_NAMES[rt],
-        offset,
-        Cpu::REG_NAMES[base],
-    );
-
     let address = cpu.regs[base].wrapping_add(offset) as u32;
 
     if let Some(value) = Op::apply(cpu, bus, rt, address) {
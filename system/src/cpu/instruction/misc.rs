@@ -1,8 +1,7 @@
 use super::{Bus, Cpu};
 use tracing::trace;
 
-pub fn sync(cpu: &mut Cpu) {
-    trace!("{:08X}: SYNC", cpu.pc[0]);
+pub fn sync(_cpu: &mut Cpu) {
     // This is a NOP on the VR4300
 }
 
@@ -11,14 +10,6 @@ pub fn cache(cpu: &mut Cpu, _bus: &mut impl Bus) {
     let op = (cpu.opcode[0] >> 16) & 31;
     let offset = (cpu.opcode[0] & 0xffff) as i16;
 
-    trace!(
-        "{:08X}: CACHE 0b{:05b}, {}({})",
-        cpu.pc[0],
-        op,
-        offset,
-        Cpu::REG_NAMES[base]
-    );
-
     let vaddr = cpu.regs[base].wrapping_add(offset as i64) as u32;
 
     let paddr = if vaddr >> 30 == 2 {
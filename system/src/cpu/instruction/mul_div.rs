@@ -2,7 +2,6 @@ use super::Cpu;
 use tracing::trace;
 
 pub trait MulDivOperator {
-    const NAME: &'static str;
     const STALL: u64;
     fn apply(lhs: i64, rhs: i64) -> (i64, i64);
 }
@@ -17,7 +16,6 @@ pub struct Ddiv;
 pub struct Ddivu;
 
 impl MulDivOperator for Mult {
-    const NAME: &'static str = "MULT";
     const STALL: u64 = 5;
 
     fn apply(lhs: i64, rhs: i64) -> (i64, i64) {
@@ -27,7 +25,6 @@ impl MulDivOperator for Mult {
 }
 
 impl MulDivOperator for Dmult {
-    const NAME: &'static str = "DMULT";
     const STALL: u64 = 8;
 
     fn apply(lhs: i64, rhs: i64) -> (i64, i64) {
@@ -37,7 +34,6 @@ impl MulDivOperator for Dmult {
 }
 
 impl MulDivOperator for Multu {
-    const NAME: &'static str = "MULTU";
     const STALL: u64 = 5;
 
     fn apply(lhs: i64, rhs: i64) -> (i64, i64) {
@@ -47,7 +43,6 @@ impl MulDivOperator for Multu {
 }
 
 impl MulDivOperator for Dmultu {
-    const NAME: &'static str = "DMULTU";
     const STALL: u64 = 8;
 
     fn apply(lhs: i64, rhs: i64) -> (i64, i64) {
@@ -57,7 +52,6 @@ impl MulDivOperator for Dmultu {
 }
 
 impl MulDivOperator for Div {
-    const NAME: &'static str = "DIV";
     const STALL: u64 = 37;
 
     fn apply(lhs: i64, rhs: i64) -> (i64, i64) {
@@ -76,7 +70,6 @@ impl MulDivOperator for Div {
 }
 
 impl MulDivOperator for Ddiv {
-    const NAME: &'static str = "DDIV";
     const STALL: u64 = 69;
 
     fn apply(lhs: i64, rhs: i64) -> (i64, i64) {
@@ -89,7 +82,6 @@ impl MulDivOperator for Ddiv {
 }
 
 impl MulDivOperator for Divu {
-    const NAME: &'static str = "DIVU";
     const STALL: u64 = 37;
 
     fn apply(lhs: i64, rhs: i64) -> (i64, i64) {
@@ -105,7 +97,6 @@ impl MulDivOperator for Divu {
 }
 
 impl MulDivOperator for Ddivu {
-    const NAME: &'static str = "DDIVU";
     const STALL: u64 = 69;
 
     fn apply(lhs: i64, rhs: i64) -> (i64, i64) {
@@ -124,14 +115,6 @@ pub fn mul_div<Op: MulDivOperator>(cpu: &mut Cpu) {
     let rs = ((cpu.opcode[0] >> 21) & 31) as usize;
     let rt = ((cpu.opcode[0] >> 16) & 31) as usize;
 
-    trace!(
-        "{:08X}: {} {}, {}",
-        cpu.pc[0],
-        Op::NAME,
-        Cpu::REG_NAMES[rs],
-        Cpu::REG_NAMES[rt],
-    );
-
     (cpu.hi, cpu.lo) = Op::apply(cpu.regs[rs], cpu.regs[rt]);
 
     trace!("  HI: {:016X}", cpu.hi);
@@ -142,26 +125,22 @@ pub fn mul_div<Op: MulDivOperator>(cpu: &mut Cpu) {
 
 pub fn mfhi(cpu: &mut Cpu) {
     let rd = ((cpu.opcode[0] >> 11) & 31) as usize;
-    trace!("{:08X}: MFHI {}", cpu.pc[0], Cpu::REG_NAMES[rd],);
     cpu.set_reg(rd, cpu.hi);
 }
 
 pub fn mflo(cpu: &mut Cpu) {
     let rd = ((cpu.opcode[0] >> 11) & 31) as usize;
-    trace!("{:08X}: MFLO {}", cpu.pc[0], Cpu::REG_NAMES[rd],);
     cpu.set_reg(rd, cpu.lo);
 }
 
 pub fn mthi(cpu: &mut Cpu) {
     let rs = ((cpu.opcode[0] >> 21) & 31) as usize;
-    trace!("{:08X}: MTHI {}", cpu.pc[0], Cpu::REG_NAMES[rs],);
     cpu.hi = cpu.regs[rs];
     trace!("  HI: {:016X}", cpu.hi);
 }
 
 pub fn mtlo(cpu: &mut Cpu) {
     let rs = ((cpu.opcode[0] >> 21) & 31) as usize;
-    trace!("{:08X}: MTLO {}", cpu.pc[0], Cpu::REG_NAMES[rs],);
     cpu.lo = cpu.regs[rs];
     trace!("  LO: {:016X}", cpu.lo);
 }
@@ -2,7 +2,6 @@ use super::{Bus, Cpu};
 use tracing::trace;
 
 pub trait StoreOperator {
-    const NAME: &'static str;
     fn apply(cpu: &mut Cpu, bus: &mut impl Bus, reg: usize, addr: u32);
 }
 
@@ -18,8 +17,6 @@ pub struct Sc;
 pub struct Scd;
 
 impl StoreOperator for Sb {
-    const NAME: &'static str = "SB";
-
     fn apply(cpu: &mut Cpu, bus: &mut impl Bus, reg: usize, addr: u32) {
         let value = cpu.regs[reg] as u8;
         trace!("  [{:08X} <= {:02X}]", addr, value);
@@ -28,8 +25,6 @@ impl StoreOperator for Sb {
 }
 
 impl StoreOperator for Sh {
-    const NAME: &'static str = "SH";
-
     fn apply(cpu: &mut Cpu, bus: &mut impl Bus, reg: usize, addr: u32) {
         assert!((addr & 1) == 0);
         let value = cpu.regs[reg] as u16;
@@ -39,8 +34,6 @@ impl StoreOperator for Sh {
 }
 
 impl StoreOperator for Sw {
-    const NAME: &'static str = "SW";
-
     fn apply(cpu: &mut Cpu, bus: &mut impl Bus, reg: usize, addr: u32) {
         assert!((addr & 3) == 0);
         let value = cpu.regs[reg] as u32;
@@ -50,8 +43,6 @@ impl StoreOperator for Sw {
 }
 
 impl StoreOperator for Swl {
-    const NAME: &'static str = "SWL";
-
     fn apply(cpu: &mut Cpu, bus: &mut impl Bus, reg: usize, addr: u32) {
         let value = cpu.regs[reg] as u32;
         trace!("  [{:08X} <= {:08X}]", addr, value);
@@ -69,8 +60,6 @@ impl StoreOperator for Swl {
 }
 
 impl StoreOperator for Swr {
-    const NAME: &'static str = "SWR";
-
     fn apply(cpu: &mut Cpu, bus: &mut impl Bus, reg: usize, addr: u32) {
         let value = cpu.regs[reg] as u32;
         trace!("  [{:08X} <= {:08X}]", addr, value);
@@ -88,8 +77,6 @@ impl StoreOperator for Swr {
 }
 
 impl StoreOperator for Sd {
-    const NAME: &'static str = "SD";
-
     fn apply(cpu: &mut Cpu, bus: &mut impl Bus, reg: usize, addr: u32) {
         assert!((addr & 7) == 0);
         let value = cpu.regs[reg] as u64;
@@ -99,8 +86,6 @@ impl StoreOperator for Sd {
 }
 
 impl StoreOperator for Sdl {
-    const NAME: &'static str = "SDL";
-
     fn apply(cpu: &mut Cpu, bus: &mut impl Bus, reg: usize, addr: u32) {
         let value = cpu.regs[reg] as u64;
         trace!("  [{:08X} <= {:08X}]", addr, value);
@@ -132,8 +117,6 @@ impl StoreOperator for Sdl {
 }
 
 impl StoreOperator for Sdr {
-    const NAME: &'static str = "SDR";
-
     fn apply(cpu: &mut Cpu, bus: &mut impl Bus, reg: usize, addr: u32) {
         let value = cpu.regs[reg] as u64;
         trace!("  [{:08X} <= {:08X}]", addr, value);
@@ -165,8 +148,6 @@ impl StoreOperator for Sdr {
 }
 
 impl StoreOperator for Sc {
-    const NAME: &'static str = "SC";
-
     fn apply(cpu: &mut Cpu, bus: &mut impl Bus, reg: usize, addr: u32) {
         assert!((addr & 3) == 0);
         let value = cpu.regs[reg] as u32;
@@ -180,8 +161,6 @@ impl StoreOperator for Sc {
 }
 
 impl StoreOperator for Scd {
-    const NAME: &'static str = "SCD";
-
     fn apply(cpu: &mut Cpu, bus: &mut impl Bus, reg: usize, addr: u32) {
         assert!((addr & 7) == 0);
         let value = cpu.regs[reg] as u64;
@@ -199,15 +178,6 @@ pub fn store<Op: StoreOperator>(cpu: &mut Cpu, bus: &mut impl Bus) {
     let rt = ((cpu.opcode[0] >> 16) & 31) as usize;
     let offset = (cpu.opcode[0] & 0xffff) as i16 as i64;
 
-    trace!(
-        "{:08X}: {} {}, {}({})",
-        cpu.pc[0],
-        Op::NAME,
-        Cpu::REG_NAMES[rt],
-        offset,
-        Cpu::REG_NAMES[base],
-    );
-
     let address = cpu.regs[base].wrapping_add(offset) as u32;
 
     Op::apply(cpu, bus, rt, address);
@@ -0,0 +1,736 @@
+use crate::audio::AudioReceiver;
+use crate::cpu::{self, Bus as _, Cpu};
+use crate::rdp;
+use crate::rsp::{self, Core};
+use crate::run_control::{RunControl, RunResult, Target};
+use crate::watch::{WatchHit, WatchKind, Watchpoint};
+use crate::{Device, Processor};
+use std::collections::BTreeSet;
+use std::error::Error;
+use std::fmt::Write;
+
+const HELP: &str = "\
+continue (c)                      Resume emulation
+step (s) [cpu|rsp] [count]        Execute one or more instructions
+break (b) [cpu|rsp] <address>     Set a breakpoint
+delete (d) [cpu|rsp] <address>    Remove a breakpoint
+breakpoints (bl)                  List breakpoints
+watch [r|w|rw] <address> [length] Stop when physical memory is accessed
+unwatch <index>                   Remove a watchpoint
+watchpoints (wl)                  List watchpoints
+regs (r) [cpu|cp0|cp1|rsp|vu]     Show registers
+x <address> [length]              Show virtual memory
+xp <address> [length]             Show physical memory
+xr <address> [length]             Show RSP memory (DMEM at 0000, IMEM at 1000)
+w <address> <bytes>               Edit virtual memory
+wp <address> <bytes>              Edit physical memory
+wr <address> <bytes>              Edit RSP memory
+dis [cpu|rsp] [address] [count]   Show code (around the PC by default)
+io <sp|dpc|mi|vi|ai|pi|si>        Show interface registers
+rdp                               Show RDP state
+help (h)                          Show this message
+quit (q)                          Exit the emulator
+";
+
+const DEFAULT_LENGTH: usize = 64;
+const DEFAULT_WATCH_LENGTH: usize = 4;
+const MAX_LENGTH: usize = 0x1000;
+
+const DEFAULT_DISASSEMBLY_COUNT: usize = 8;
+
+// CP0 registers that can be read without side effects
+const CP0_REGS: [usize; 19] = [
+    0, 1, 2, 3, 4, 5, 6, 8, 9, 10, 11, 12, 13, 14, 16, 17, 20, 29, 30,
+];
+
+// Registers that can be read without side effects, by interface
+const SP_REGS: [(&str, u32); 8] = [
+    ("SP_DMA_SPADDR", 0x0404_0000),
+    ("SP_DMA_RAMADDR", 0x0404_0004),
+    ("SP_DMA_RDLEN", 0x0404_0008),
+    ("SP_DMA_WRLEN", 0x0404_000c),
+    ("SP_STATUS", 0x0404_0010),
+    ("SP_DMA_FULL", 0x0404_0014),
+    ("SP_DMA_BUSY", 0x0404_0018),
+    ("SP_PC", 0x0408_0000),
+];
+
+const DPC_REGS: [(&str, u32); 8] = [
+    ("DPC_START", 0x0410_0000),
+    ("DPC_END", 0x0410_0004),
+    ("DPC_CURRENT", 0x0410_0008),
+    ("DPC_STATUS", 0x0410_000c),
+    ("DPC_CLOCK", 0x0410_0010),
+    ("DPC_BUFBUSY", 0x0410_0014),
+    ("DPC_PIPEBUSY", 0x0410_0018),
+    ("DPC_TMEM", 0x0410_001c),
+];
+
+const MI_REGS: [(&str, u32); 3] = [
+    ("MI_VERSION", 0x0430_0004),
+    ("MI_INTERRUPT", 0x0430_0008),
+    ("MI_MASK", 0x0430_000c),
+];
+
+const VI_REGS: [(&str, u32); 15] = [
+    ("VI_CTRL", 0x0440_0000),
+    ("VI_ORIGIN", 0x0440_0004),
+    ("VI_WIDTH", 0x0440_0008),
+    ("VI_V_INTR", 0x0440_000c),
+    ("VI_V_CURRENT", 0x0440_0010),
+    ("VI_BURST", 0x0440_0014),
+    ("VI_V_SYNC", 0x0440_0018),
+    ("VI_H_SYNC", 0x0440_001c),
+    ("VI_H_SYNC_LEAP", 0x0440_0020),
+    ("VI_H_VIDEO", 0x0440_0024),
+    ("VI_V_VIDEO", 0x0440_0028),
+    ("VI_V_BURST", 0x0440_002c),
+    ("VI_X_SCALE", 0x0440_0030),
+    ("VI_Y_SCALE", 0x0440_0034),
+    ("VI_TEST_ADDR", 0x0440_0038),
+];
+
+const AI_REGS: [(&str, u32); 2] = [("AI_LENGTH", 0x0450_0004), ("AI_STATUS", 0x0450_000c)];
+
+const PI_REGS: [(&str, u32); 11] = [
+    ("PI_DRAM_ADDR", 0x0460_0000),
+    ("PI_CART_ADDR", 0x0460_0004),
+    ("PI_STATUS", 0x0460_0010),
+    ("PI_BSD_DOM1_LAT", 0x0460_0014),
+    ("PI_BSD_DOM1_PWD", 0x0460_0018),
+    ("PI_BSD_DOM1_PGS", 0x0460_001c),
+    ("PI_BSD_DOM1_RLS", 0x0460_0020),
+    ("PI_BSD_DOM2_LAT", 0x0460_0024),
+    ("PI_BSD_DOM2_PWD", 0x0460_0028),
+    ("PI_BSD_DOM2_PGS", 0x0460_002c),
+    ("PI_BSD_DOM2_RLS", 0x0460_0030),
+];
+
+const SI_REGS: [(&str, u32); 1] = [("SI_STATUS", 0x0480_0018)];
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+enum AddressSpace {
+    Virtual,
+    Physical,
+    Rsp,
+}
+
+// Command interpreter for interactive debugging of the CPU and RSP
+pub struct Debugger {
+    cpu_breakpoints: BTreeSet<u32>,
+    rsp_breakpoints: BTreeSet<u32>,
+    run_control: RunControl,
+    // Processor used by commands that don't name one
+    focus: Target,
+}
+
+impl Debugger {
+    // The device starts off stopped
+    pub fn new() -> Self {
+        Self {
+            cpu_breakpoints: BTreeSet::new(),
+            rsp_breakpoints: BTreeSet::new(),
+            run_control: RunControl::new(),
+            focus: Target::Cpu,
+        }
+    }
+
+    pub fn is_stopped(&self) -> bool {
+        self.run_control.is_stopped()
+    }
+
+    // Stops the device, returning a description of where it stopped
+    pub fn interrupt(&mut self, device: &mut Device) -> String {
+        self.run_control.stop();
+        format!("Interrupted\n{}", location(device, self.focus))
+    }
+
+    // Runs the device for up to one frame if it is not stopped. If it stops
+    // during the frame, returns a description of why and where.
+    pub fn run_frame(
+        &mut self,
+        device: &mut Device,
+        receiver: &mut impl AudioReceiver,
+    ) -> Option<String> {
+        let cpu_breakpoints = &self.cpu_breakpoints;
+        let rsp_breakpoints = &self.rsp_breakpoints;
+
+        let result = self
+            .run_control
+            .run_frame(device, receiver, |processor| match processor {
+                Processor::Cpu(cpu) => cpu_breakpoints.contains(&cpu.pc()),
+                Processor::Rsp(rsp) => rsp_breakpoints.contains(&rsp.core().current_pc()),
+            });
+
+        let hit = match result {
+            RunResult::Running => return None,
+            RunResult::Stopped => None,
+            RunResult::Breakpoint(target) => Some(target),
+        };
+
+        if let Some(hit) = device.bus.rdram.watch().take_hit() {
+            return Some(format!(
+                "{}\n{}",
+                describe_hit(&hit),
+                location(device, self.focus)
+            ));
+        }
+
+        let Some(target) = hit else {
+            return Some(location(device, self.focus));
+        };
+
+        self.focus = target;
+
+        Some(format!(
+            "{} breakpoint\n{}",
+            target_name(target),
+            location(device, target)
+        ))
+    }
+
+    // Executes a single command. Commands that resume the device return
+    // immediately; 'run_frame' reports when it stops again.
+    pub fn execute(
+        &mut self,
+        device: &mut Device,
+        command: &str,
+    ) -> Result<String, Box<dyn Error>> {
+        let mut args = command.split_whitespace();
+
+        let Some(name) = args.next() else {
+            return Ok(String::new());
+        };
+
+        let mut args: Vec<&str> = args.collect();
+
+        let output = match name {
+            "continue" | "c" => {
+                self.run_control.resume(device);
+
+                String::new()
+            }
+            "step" | "s" => {
+                let target = self.parse_target(&mut args);
+                let count = args.first().map_or(Ok(1), |arg| parse_count(arg))?;
+
+                if count == 0 {
+                    return Err("Step count must be at least 1".into());
+                }
+
+                self.focus = target;
+
+                self.run_control.step(device, target, count as u64);
+
+                String::new()
+            }
+            "break" | "b" | "delete" | "d" => {
+                let target = self.parse_target(&mut args);
+                let address = parse_address(args.first().ok_or("Missing address")?)?;
+
+                let (breakpoints, address) = match target {
+                    Target::Cpu => (&mut self.cpu_breakpoints, address),
+                    Target::Rsp => (&mut self.rsp_breakpoints, address & 0x0ffc),
+                };
+
+                if name.starts_with('b') {
+                    breakpoints.insert(address);
+                } else if !breakpoints.remove(&address) {
+                    return Err("No breakpoint at that address".into());
+                }
+
+                String::new()
+            }
+            "breakpoints" | "bl" => {
+                let mut output = String::new();
+
+                for address in &self.cpu_breakpoints {
+                    writeln!(output, "CPU {:08X}", address)?;
+                }
+
+                for address in &self.rsp_breakpoints {
+                    writeln!(output, "RSP {:03X}", address)?;
+                }
+
+                output
+            }
+            "watch" => {
+                let kind = match args.first().copied() {
+                    Some("r") => Some(WatchKind::Read),
+                    Some("w") => Some(WatchKind::Write),
+                    Some("rw") => Some(WatchKind::Access),
+                    _ => None,
+                };
+
+                if kind.is_some() {
+                    args.remove(0);
+                }
+
+                let start = parse_address(args.first().ok_or("Missing address")?)?;
+
+                let len = args
+                    .get(1)
+                    .map_or(Ok(DEFAULT_WATCH_LENGTH), |arg| parse_count(arg))?;
+
+                if len == 0 {
+                    return Err("Watch length must be at least 1".into());
+                }
+
+                let index = device.bus.rdram.watch_mut().add(Watchpoint {
+                    start,
+                    len: len.min(u32::MAX as usize) as u32,
+                    kind: kind.unwrap_or(WatchKind::Write),
+                });
+
+                format!("Watchpoint {}\n", index)
+            }
+            "unwatch" => {
+                let index = parse_count(args.first().ok_or("Missing watchpoint index")?)?;
+
+                device
+                    .bus
+                    .rdram
+                    .watch_mut()
+                    .remove(index)
+                    .ok_or("No watchpoint with that index")?;
+
+                String::new()
+            }
+            "watchpoints" | "wl" => {
+                let mut output = String::new();
+
+                for (index, watchpoint) in device.bus.rdram.watch().watchpoints().iter().enumerate()
+                {
+                    let kind = match watchpoint.kind {
+                        WatchKind::Read => "r",
+                        WatchKind::Write => "w",
+                        WatchKind::Access => "rw",
+                    };
+
+                    writeln!(
+                        output,
+                        "{}: {:<2} {:08X} +{}",
+                        index, kind, watchpoint.start, watchpoint.len
+                    )?;
+                }
+
+                output
+            }
+            "regs" | "r" => match args.first().copied() {
+                None if self.focus == Target::Rsp => rsp_regs(device)?,
+                None | Some("cpu") => cpu_regs(device)?,
+                Some("cp0") => cp0_regs(device)?,
+                Some("cp1") => cp1_regs(device)?,
+                Some("rsp") => rsp_regs(device)?,
+                Some("vu") => vu_regs(device)?,
+                Some(other) => return Err(format!("Unknown register set: {}", other).into()),
+            },
+            "x" | "xp" | "xr" => {
+                let space = address_space(name);
+                let address = parse_address(args.first().ok_or("Missing address")?)?;
+
+                let len = args
+                    .get(1)
+                    .map_or(Ok(DEFAULT_LENGTH), |arg| parse_count(arg))?
+                    .min(MAX_LENGTH);
+
+                hexdump(device, space, address, len)?
+            }
+            "w" | "wp" | "wr" => {
+                let space = address_space(name);
+                let address = parse_address(args.first().ok_or("Missing address")?)?;
+                let data = parse_bytes(&args[1..])?;
+
+                for (offset, &byte) in data.iter().enumerate() {
+                    write_byte(device, space, address.wrapping_add(offset as u32), byte)
+                        .ok_or_else(|| {
+                            format!(
+                                "Address {:08X} is not writable",
+                                address.wrapping_add(offset as u32)
+                            )
+                        })?;
+                }
+
+                String::new()
+            }
+            "dis" => {
+                let target = self.parse_target(&mut args);
+
+                let address = args
+                    .first()
+                    .map(|arg| parse_address(arg))
+                    .transpose()?
+                    .unwrap_or_else(|| {
+                        let pc = target.pc(device);
+                        let before = (DEFAULT_DISASSEMBLY_COUNT / 2) as u32 * 4;
+
+                        match target {
+                            Target::Cpu => pc.wrapping_sub(before),
+                            Target::Rsp => pc.saturating_sub(before),
+                        }
+                    });
+
+                let count = args
+                    .get(1)
+                    .map_or(Ok(DEFAULT_DISASSEMBLY_COUNT), |arg| parse_count(arg))?
+                    .min(MAX_LENGTH / 4);
+
+                disassemble(device, target, address, count)?
+            }
+            "io" => {
+                let regs: &[(&str, u32)] = match args.first().copied() {
+                    Some("sp") => &SP_REGS,
+                    Some("dpc") => &DPC_REGS,
+                    Some("mi") => &MI_REGS,
+                    Some("vi") => &VI_REGS,
+                    Some("ai") => &AI_REGS,
+                    Some("pi") => &PI_REGS,
+                    Some("si") => &SI_REGS,
+                    Some(other) => return Err(format!("Unknown interface: {}", other).into()),
+                    None => return Err("Missing interface name".into()),
+                };
+
+                let mut output = String::new();
+
+                for &(name, address) in regs {
+                    let value: u32 = device.bus.read_single(address);
+                    writeln!(output, "{:<16}{:08X}", name, value)?;
+                }
+
+                output
+            }
+            "rdp" => rdp_state(device)?,
+            "help" | "h" => HELP.into(),
+            _ => return Err(format!("Unknown command: {}", name).into()),
+        };
+
+        Ok(output)
+    }
+
+    fn parse_target(&self, args: &mut Vec<&str>) -> Target {
+        match args.first().copied() {
+            Some("cpu") => {
+                args.remove(0);
+                Target::Cpu
+            }
+            Some("rsp") => {
+                args.remove(0);
+                Target::Rsp
+            }
+            _ => self.focus,
+        }
+    }
+}
+
+impl Default for Debugger {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn describe_hit(hit: &WatchHit) -> String {
+    format!(
+        "Watchpoint {}: {} {} {:0width$X} at {:08X} (PC {:08X})",
+        hit.index,
+        hit.source,
+        if hit.write { "wrote" } else { "read" },
+        hit.value,
+        hit.address,
+        hit.pc,
+        width = hit.size * 2
+    )
+}
+
+fn target_name(target: Target) -> &'static str {
+    match target {
+        Target::Cpu => "CPU",
+        Target::Rsp => "RSP",
+    }
+}
+
+fn address_space(command: &str) -> AddressSpace {
+    match command.as_bytes().get(1) {
+        Some(b'p') => AddressSpace::Physical,
+        Some(b'r') => AddressSpace::Rsp,
+        _ => AddressSpace::Virtual,
+    }
+}
+
+fn location(device: &Device, target: Target) -> String {
+    let pc = target.pc(device);
+    disassemble(device, target, pc, 1).unwrap_or_default()
+}
+
+fn read_byte(device: &Device, space: AddressSpace, address: u32) -> Option<u8> {
+    match space {
+        AddressSpace::Virtual => device.bus.read_memory(device.cpu.translate(address)?),
+        AddressSpace::Physical => device.bus.read_memory(address),
+        AddressSpace::Rsp => Some(device.bus.rsp.mem().read((address & 0x1fff) as usize)),
+    }
+}
+
+fn write_byte(device: &mut Device, space: AddressSpace, address: u32, value: u8) -> Option<()> {
+    match space {
+        AddressSpace::Virtual => {
+            let paddr = device.cpu.translate(address)?;
+            device.write_memory(paddr, value)
+        }
+        AddressSpace::Physical => device.write_memory(address, value),
+        AddressSpace::Rsp => {
+            device
+                .bus
+                .rsp
+                .mem_mut()
+                .write((address & 0x1fff) as usize, value);
+
+            Some(())
+        }
+    }
+}
+
+fn read_word(device: &Device, space: AddressSpace, address: u32) -> Option<u32> {
+    (0..4).try_fold(0, |word, offset| {
+        Some((word << 8) | read_byte(device, space, address.wrapping_add(offset))? as u32)
+    })
+}
+
+fn hexdump(
+    device: &Device,
+    space: AddressSpace,
+    address: u32,
+    len: usize,
+) -> Result<String, Box<dyn Error>> {
+    let mut output = String::new();
+
+    for row in (0..len).step_by(16) {
+        let row_address = address.wrapping_add(row as u32);
+        write!(output, "{:08X}:", row_address)?;
+
+        for offset in 0..16.min(len - row) {
+            match read_byte(device, space, row_address.wrapping_add(offset as u32)) {
+                Some(byte) => write!(output, " {:02X}", byte)?,
+                None => output.push_str(" ??"),
+            }
+        }
+
+        output.push('\n');
+    }
+
+    Ok(output)
+}
+
+fn disassemble(
+    device: &Device,
+    target: Target,
+    address: u32,
+    count: usize,
+) -> Result<String, Box<dyn Error>> {
+    let mut output = String::new();
+
+    for index in 0..count {
+        let address = address.wrapping_add(index as u32 * 4);
+
+        let (pc, word) = match target {
+            Target::Cpu => (
+                device.cpu.pc(),
+                read_word(device, AddressSpace::Virtual, address),
+            ),
+            Target::Rsp => (
+                device.bus.rsp.core().current_pc(),
+                read_word(device, AddressSpace::Rsp, 0x1000 | (address & 0x0ffc)),
+            ),
+        };
+
+        let marker = if address == pc { '>' } else { ' ' };
+
+        match word {
+            Some(word) => writeln!(
+                output,
+                "{} {} {:08X}: {:08X}  {}",
+                marker,
+                target_name(target),
+                address,
+                word,
+                match target {
+                    Target::Cpu => cpu::disassemble(address, word),
+                    Target::Rsp => rsp::disassemble(address, word),
+                }
+            )?,
+            None => writeln!(
+                output,
+                "{} {} {:08X}: ????????",
+                marker,
+                target_name(target),
+                address
+            )?,
+        }
+    }
+
+    Ok(output)
+}
+
+fn cpu_regs(device: &Device) -> Result<String, Box<dyn Error>> {
+    let cpu = &device.cpu;
+    let mut output = String::new();
+
+    for (index, name) in Cpu::REG_NAMES.iter().enumerate() {
+        write!(output, "{:>4}: {:016X}", name, cpu.reg(index))?;
+        output.push(if (index & 3) == 3 { '\n' } else { ' ' });
+    }
+
+    writeln!(
+        output,
+        "  PC: {:08X}         HI: {:016X}   LO: {:016X}",
+        cpu.pc(),
+        cpu.hi(),
+        cpu.lo()
+    )?;
+
+    Ok(output)
+}
+
+fn cp0_regs(device: &mut Device) -> Result<String, Box<dyn Error>> {
+    let mut output = String::new();
+
+    for (index, &reg) in CP0_REGS.iter().enumerate() {
+        let value = device.cpu.cp0_reg(reg);
+        write!(output, "{:>8}: {:016X}", Cpu::CP0_REG_NAMES[reg], value)?;
+        output.push(if (index % 3) == 2 { '\n' } else { ' ' });
+    }
+
+    output.push('\n');
+    Ok(output)
+}
+
+fn cp1_regs(device: &Device) -> Result<String, Box<dyn Error>> {
+    let cpu = &device.cpu;
+    let mut output = String::new();
+
+    for index in 0..32 {
+        write!(
+            output,
+            "{:>4}: {:016X}",
+            format!("F{}", index),
+            cpu.cp1_reg(index)
+        )?;
+        output.push(if (index & 3) == 3 { '\n' } else { ' ' });
+    }
+
+    writeln!(output, "FCSR: {:08X}", cpu.cp1_control_reg(31))?;
+    Ok(output)
+}
+
+fn rsp_regs(device: &Device) -> Result<String, Box<dyn Error>> {
+    let core = device.bus.rsp.core();
+    let mut output = String::new();
+
+    for (index, name) in Core::REG_NAMES.iter().enumerate() {
+        write!(output, "{:>4}: {:08X}", name, core.reg(index))?;
+        output.push(if (index & 7) == 7 { '\n' } else { ' ' });
+    }
+
+    writeln!(
+        output,
+        "  PC: {:03X}  SP_STATUS: {:08X}",
+        core.current_pc(),
+        device.bus.rsp.status()
+    )?;
+
+    Ok(output)
+}
+
+fn vu_regs(device: &Device) -> Result<String, Box<dyn Error>> {
+    let cp2 = device.bus.rsp.core().cp2();
+    let mut output = String::new();
+
+    for index in 0..32 {
+        let vector = cp2.reg(index);
+        write!(output, "{:>6}:", format!("V{}", index))?;
+
+        for lane in 0..8 {
+            write!(output, " {:04X}", vector.lane(lane))?;
+        }
+
+        output.push('\n');
+    }
+
+    // Accumulator is stored in reverse lane order
+    for (name, shift) in [("ACC_HI", 32), ("ACC_MD", 16), ("ACC_LO", 0)] {
+        write!(output, "{:>6}:", name)?;
+
+        for lane in 0..8 {
+            write!(output, " {:04X}", (cp2.acc()[7 - lane] >> shift) as u16)?;
+        }
+
+        output.push('\n');
+    }
+
+    for index in 0..3 {
+        writeln!(
+            output,
+            "{:>6}: {:04X}",
+            Core::CP2_CONTROL_REG_NAMES[index],
+            cp2.control_reg(index) as u16
+        )?;
+    }
+
+    Ok(output)
+}
+
+fn rdp_state(device: &Device) -> Result<String, Box<dyn Error>> {
+    let rdp = &device.bus.rdp;
+    let mut output = String::new();
+
+    for &(name, address) in &DPC_REGS {
+        let value: u32 = device.bus.read_single(address);
+        writeln!(output, "{:<16}{:08X}", name, value)?;
+    }
+
+    writeln!(
+        output,
+        "Running: {}, pending commands: {}",
+        rdp.is_running(),
+        rdp.pending_commands()
+    )?;
+
+    writeln!(output, "State commands:")?;
+
+    for line in rdp::disassemble(&rdp.state_words()).lines() {
+        writeln!(output, "  {}", line)?;
+    }
+
+    Ok(output)
+}
+
+fn parse_address(arg: &str) -> Result<u32, Box<dyn Error>> {
+    let hex = arg.trim_start_matches("0x");
+    u32::from_str_radix(hex, 16).map_err(|_| format!("Invalid address: {}", arg).into())
+}
+
+// Counts are decimal unless prefixed with '0x'
+fn parse_count(arg: &str) -> Result<usize, Box<dyn Error>> {
+    let result = match arg.strip_prefix("0x") {
+        Some(hex) => usize::from_str_radix(hex, 16),
+        None => arg.parse(),
+    };
+
+    result.map_err(|_| format!("Invalid count: {}", arg).into())
+}
+
+fn parse_bytes(args: &[&str]) -> Result<Vec<u8>, Box<dyn Error>> {
+    let hex: String = args.concat();
+
+    if !hex.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+        return Err(format!("Invalid data: {}", hex).into());
+    }
+
+    if hex.is_empty() || (hex.len() & 1) != 0 {
+        return Err("Expected an even number of hex digits".into());
+    }
+
+    // Every character is a single byte, so slicing can't split one
+    Ok((0..hex.len())
+        .step_by(2)
+        .map(|index| u8::from_str_radix(&hex[index..(index + 2)], 16).unwrap())
+        .collect())
+}
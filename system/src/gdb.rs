@@ -0,0 +1,436 @@
+use crate::audio::AudioReceiver;
+use crate::run_control::{RunControl, RunResult, Target};
+use crate::{Device, Processor};
+use packet::Input;
+use std::collections::HashSet;
+use std::error::Error;
+use std::io::{ErrorKind, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use tracing::{debug, info, warn};
+
+mod cpu;
+mod packet;
+mod rsp;
+
+const SIGINT: u8 = 2;
+const SIGTRAP: u8 = 5;
+
+const PACKET_SIZE: usize = 0x4000;
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum GdbStatus {
+    Attached,
+    Detached,
+    Killed,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum GdbTarget {
+    Cpu,
+    Rsp,
+}
+
+// Implements enough of the GDB remote serial protocol to debug code running
+// on either the VR4300 or the RSP
+pub struct GdbStub {
+    target: GdbTarget,
+    stream: TcpStream,
+    input: Vec<u8>,
+    breakpoints: HashSet<u32>,
+    run_control: RunControl,
+    signal: u8,
+}
+
+impl GdbStub {
+    // Blocks until a debugger connects. The device starts off stopped.
+    pub fn listen(port: u16, target: GdbTarget) -> Result<Self, Box<dyn Error>> {
+        let listener = TcpListener::bind(("127.0.0.1", port))?;
+        info!("Waiting for GDB connection on port {}", port);
+
+        let (stream, address) = listener.accept()?;
+        info!("GDB connected from {}", address);
+
+        stream.set_nodelay(true)?;
+        stream.set_nonblocking(true)?;
+
+        Ok(Self {
+            target,
+            stream,
+            input: Vec::new(),
+            breakpoints: HashSet::new(),
+            run_control: RunControl::new(),
+            signal: SIGTRAP,
+        })
+    }
+
+    pub fn is_stopped(&self) -> bool {
+        self.run_control.is_stopped()
+    }
+
+    // Handles any pending requests, then runs the device for up to one frame
+    // if the debugger has resumed it
+    pub fn run_frame(
+        &mut self,
+        device: &mut Device,
+        receiver: &mut impl AudioReceiver,
+    ) -> Result<GdbStatus, Box<dyn Error>> {
+        let status = self.poll(device)?;
+
+        if status != GdbStatus::Attached {
+            return Ok(status);
+        }
+
+        let target = self.target;
+        let breakpoints = &self.breakpoints;
+
+        let result = self.run_control.run_frame(device, receiver, |processor| {
+            target
+                .pc(processor)
+                .is_some_and(|pc| breakpoints.contains(&pc))
+        });
+
+        if result != RunResult::Running {
+            self.stop(SIGTRAP)?;
+        }
+
+        Ok(GdbStatus::Attached)
+    }
+
+    fn poll(&mut self, device: &mut Device) -> Result<GdbStatus, Box<dyn Error>> {
+        let mut buf = [0u8; 4096];
+
+        loop {
+            match self.stream.read(&mut buf) {
+                Ok(0) => {
+                    info!("GDB disconnected");
+                    return Ok(GdbStatus::Detached);
+                }
+                Ok(len) => self.input.extend_from_slice(&buf[0..len]),
+                Err(err) if err.kind() == ErrorKind::WouldBlock => break,
+                Err(err) if err.kind() == ErrorKind::Interrupted => (),
+                Err(err) => return Err(err.into()),
+            }
+        }
+
+        while let Some((input, valid)) = packet::parse(&mut self.input) {
+            let data = match input {
+                Input::Interrupt => {
+                    if !self.is_stopped() {
+                        self.stop(SIGINT)?;
+                    }
+
+                    continue;
+                }
+                Input::Packet(data) => data,
+            };
+
+            if !valid {
+                warn!("GDB packet has bad checksum");
+                self.write_raw(b"-")?;
+                continue;
+            }
+
+            self.write_raw(b"+")?;
+
+            let Some(command) = packet::command(&data) else {
+                warn!("GDB packet is not ASCII: {:02X?}", data);
+                self.send("")?;
+                continue;
+            };
+
+            debug!("GDB: {}", command);
+
+            match command {
+                "k" => return Ok(GdbStatus::Killed),
+                "D" => {
+                    self.send("OK")?;
+                    return Ok(GdbStatus::Detached);
+                }
+                _ => {
+                    if let Some(response) = self.handle(device, command) {
+                        self.send(&response)?;
+                    }
+                }
+            }
+        }
+
+        Ok(GdbStatus::Attached)
+    }
+
+    // Returns None if the response should be deferred (i.e. until the device
+    // next stops)
+    fn handle(&mut self, device: &mut Device, command: &str) -> Option<String> {
+        if command.is_empty() {
+            return Some(String::new());
+        }
+
+        let (name, args) = command.split_at(1);
+
+        let response = match name {
+            "?" => format!("S{:02x}", self.signal),
+            "g" => (0..self.target.reg_count())
+                .map(|regnum| packet::to_hex(&self.target.read_reg(device, regnum).unwrap()))
+                .collect(),
+            "G" => {
+                let mut args = args;
+
+                for regnum in 0..self.target.reg_count() {
+                    let (value, rest) =
+                        args.split_at((self.target.reg_size(regnum) * 2).min(args.len()));
+
+                    if value.is_empty() {
+                        break;
+                    }
+
+                    args = rest;
+
+                    // Writing some registers has side effects, so only write
+                    // those that have actually changed
+                    if packet::to_hex(&self.target.read_reg(device, regnum).unwrap())
+                        == value.to_ascii_lowercase()
+                    {
+                        continue;
+                    }
+
+                    if self.write_reg(device, regnum, value).is_none() {
+                        return Some("E01".into());
+                    }
+                }
+
+                "OK".into()
+            }
+            "p" => usize::from_str_radix(args, 16)
+                .ok()
+                .and_then(|regnum| self.target.read_reg(device, regnum))
+                .map_or_else(|| "E01".into(), |value| packet::to_hex(&value)),
+            "P" => args
+                .split_once('=')
+                .and_then(|(regnum, value)| {
+                    self.write_reg(device, usize::from_str_radix(regnum, 16).ok()?, value)
+                })
+                .map_or_else(|| "E01".into(), |()| "OK".into()),
+            "m" => args
+                .split_once(',')
+                .and_then(|(address, len)| {
+                    let len = usize::from_str_radix(len, 16).ok()?;
+                    self.read_memory(device, packet::parse_address(address)?, len)
+                })
+                .unwrap_or_else(|| "E14".into()),
+            "M" => args
+                .split_once(':')
+                .and_then(|(header, data)| {
+                    let (address, _) = header.split_once(',')?;
+                    let data = packet::from_hex(data)?;
+                    self.write_memory(device, packet::parse_address(address)?, &data)
+                })
+                .map_or_else(|| "E14".into(), |()| "OK".into()),
+            "c" | "s" => {
+                if !args.is_empty() {
+                    let Some(pc) = packet::parse_address(args) else {
+                        return Some("E01".into());
+                    };
+
+                    self.target.set_pc(device, pc);
+                }
+
+                if name == "c" {
+                    self.run_control.resume(device);
+                } else {
+                    self.run_control.step(device, self.target.run_target(), 1);
+                }
+
+                return None;
+            }
+            "Z" | "z" => {
+                let mut parts = args.split(',');
+                let kind = parts.next();
+                let address = parts.next().and_then(packet::parse_address);
+
+                match (kind, address) {
+                    // Software and hardware breakpoints are treated the same
+                    (Some("0" | "1"), Some(address)) => {
+                        let address = self.target.breakpoint_address(address);
+
+                        if name == "Z" {
+                            self.breakpoints.insert(address);
+                        } else {
+                            self.breakpoints.remove(&address);
+                        }
+
+                        "OK".into()
+                    }
+                    _ => String::new(),
+                }
+            }
+            "H" | "T" => "OK".into(),
+            "q" => query(self.target, args),
+            _ => String::new(),
+        };
+
+        Some(response)
+    }
+
+    fn write_reg(&self, device: &mut Device, regnum: usize, hex: &str) -> Option<()> {
+        let bytes = packet::from_hex(hex)?;
+
+        if regnum >= self.target.reg_count() || bytes.len() != self.target.reg_size(regnum) {
+            return None;
+        }
+
+        self.target.write_reg(device, regnum, &bytes)
+    }
+
+    // Reads as many bytes as possible, stopping at the first inaccessible
+    // address
+    fn read_memory(&self, device: &Device, address: u32, len: usize) -> Option<String> {
+        let len = len.min(PACKET_SIZE / 2);
+
+        let data: Vec<u8> = (0..len)
+            .map_while(|offset| {
+                self.target
+                    .read_byte(device, address.wrapping_add(offset as u32))
+            })
+            .collect();
+
+        (data.len() == len || !data.is_empty()).then(|| packet::to_hex(&data))
+    }
+
+    fn write_memory(&self, device: &mut Device, address: u32, data: &[u8]) -> Option<()> {
+        for (offset, &byte) in data.iter().enumerate() {
+            self.target
+                .write_byte(device, address.wrapping_add(offset as u32), byte)?;
+        }
+
+        Some(())
+    }
+
+    fn stop(&mut self, signal: u8) -> Result<(), Box<dyn Error>> {
+        self.run_control.stop();
+        self.signal = signal;
+        self.send(&format!("S{:02x}", signal))
+    }
+
+    fn send(&mut self, response: &str) -> Result<(), Box<dyn Error>> {
+        debug!("GDB Response: {}", response);
+        self.write_raw(&packet::encode(response.as_bytes()))
+    }
+
+    fn write_raw(&mut self, data: &[u8]) -> Result<(), Box<dyn Error>> {
+        self.stream.set_nonblocking(false)?;
+        self.stream.write_all(data)?;
+        self.stream.set_nonblocking(true)?;
+        Ok(())
+    }
+}
+
+fn query(target: GdbTarget, args: &str) -> String {
+    if args.starts_with("Supported") {
+        return format!("PacketSize={:x};qXfer:features:read+", PACKET_SIZE);
+    }
+
+    if let Some(args) = args.strip_prefix("Xfer:features:read:target.xml:") {
+        let Some((offset, len)) = args.split_once(',') else {
+            return "E01".into();
+        };
+
+        let (Ok(offset), Ok(len)) = (
+            usize::from_str_radix(offset, 16),
+            usize::from_str_radix(len, 16),
+        ) else {
+            return "E01".into();
+        };
+
+        let xml = target.target_xml();
+        let start = offset.min(xml.len());
+        let end = (start + len).min(xml.len());
+        let prefix = if end == xml.len() { 'l' } else { 'm' };
+        return format!("{}{}", prefix, &xml[start..end]);
+    }
+
+    match args {
+        "Attached" => "1".into(),
+        "fThreadInfo" => "m1".into(),
+        "sThreadInfo" => "l".into(),
+        _ => String::new(),
+    }
+}
+
+impl GdbTarget {
+    fn target_xml(self) -> String {
+        match self {
+            Self::Cpu => cpu::target_xml(),
+            Self::Rsp => rsp::target_xml(),
+        }
+    }
+
+    fn reg_count(self) -> usize {
+        match self {
+            Self::Cpu => cpu::REG_COUNT,
+            Self::Rsp => rsp::REG_COUNT,
+        }
+    }
+
+    fn reg_size(self, regnum: usize) -> usize {
+        match self {
+            Self::Cpu => cpu::reg_size(regnum),
+            Self::Rsp => rsp::reg_size(regnum),
+        }
+    }
+
+    fn read_reg(self, device: &mut Device, regnum: usize) -> Option<Vec<u8>> {
+        match self {
+            Self::Cpu => cpu::read_reg(device, regnum),
+            Self::Rsp => rsp::read_reg(device, regnum),
+        }
+    }
+
+    fn write_reg(self, device: &mut Device, regnum: usize, bytes: &[u8]) -> Option<()> {
+        match self {
+            Self::Cpu => cpu::write_reg(device, regnum, bytes),
+            Self::Rsp => rsp::write_reg(device, regnum, bytes),
+        }
+    }
+
+    // Returns the PC if the given processor is the one being debugged
+    fn pc(self, processor: Processor) -> Option<u32> {
+        match self {
+            Self::Cpu => cpu::pc(processor),
+            Self::Rsp => rsp::pc(processor),
+        }
+    }
+
+    fn run_target(self) -> Target {
+        match self {
+            Self::Cpu => Target::Cpu,
+            Self::Rsp => Target::Rsp,
+        }
+    }
+
+    fn set_pc(self, device: &mut Device, pc: u32) {
+        match self {
+            Self::Cpu => cpu::set_pc(device, pc),
+            Self::Rsp => rsp::set_pc(device, pc),
+        }
+    }
+
+    fn breakpoint_address(self, address: u32) -> u32 {
+        match self {
+            Self::Cpu => cpu::breakpoint_address(address),
+            Self::Rsp => rsp::breakpoint_address(address),
+        }
+    }
+
+    fn read_byte(self, device: &Device, address: u32) -> Option<u8> {
+        match self {
+            Self::Cpu => cpu::read_byte(device, address),
+            Self::Rsp => rsp::read_byte(device, address),
+        }
+    }
+
+    fn write_byte(self, device: &mut Device, address: u32, value: u8) -> Option<()> {
+        match self {
+            Self::Cpu => cpu::write_byte(device, address, value),
+            Self::Rsp => rsp::write_byte(device, address, value),
+        }
+    }
+}
@@ -0,0 +1,172 @@
+use crate::{Device, Processor};
+use std::fmt::Write;
+
+// CP0 registers beyond the three GDB already knows about, which are appended
+// after the standard MIPS register set
+const CP0_EXTRA_REGS: [(usize, &str); 16] = [
+    (0, "index"),
+    (1, "random"),
+    (2, "entrylo0"),
+    (3, "entrylo1"),
+    (4, "context"),
+    (5, "pagemask"),
+    (6, "wired"),
+    (9, "count"),
+    (10, "entryhi"),
+    (11, "compare"),
+    (14, "epc"),
+    (16, "config"),
+    (17, "lladdr"),
+    (20, "xcontext"),
+    (29, "taghi"),
+    (30, "errorepc"),
+];
+
+pub const REG_COUNT: usize = 72 + CP0_EXTRA_REGS.len();
+
+// Register numbers follow GDB's MIPS layout, with extra CP0 registers added
+// at the end
+pub fn target_xml() -> String {
+    let mut xml = String::new();
+
+    xml.push_str("<?xml version=\"1.0\"?>\n");
+    xml.push_str("<!DOCTYPE target SYSTEM \"gdb-target.dtd\">\n");
+    xml.push_str("<target version=\"1.0\">\n");
+    xml.push_str("<architecture>mips:4300</architecture>\n");
+
+    xml.push_str("<feature name=\"org.gnu.gdb.mips.cpu\">\n");
+
+    for index in 0..32 {
+        writeln!(
+            xml,
+            "<reg name=\"r{}\" bitsize=\"64\" regnum=\"{}\"/>",
+            index, index
+        )
+        .unwrap();
+    }
+
+    xml.push_str("<reg name=\"lo\" bitsize=\"64\" regnum=\"33\"/>\n");
+    xml.push_str("<reg name=\"hi\" bitsize=\"64\" regnum=\"34\"/>\n");
+    xml.push_str("<reg name=\"pc\" bitsize=\"64\" regnum=\"37\"/>\n");
+    xml.push_str("</feature>\n");
+
+    xml.push_str("<feature name=\"org.gnu.gdb.mips.cp0\">\n");
+    xml.push_str("<reg name=\"status\" bitsize=\"32\" regnum=\"32\"/>\n");
+    xml.push_str("<reg name=\"badvaddr\" bitsize=\"64\" regnum=\"35\"/>\n");
+    xml.push_str("<reg name=\"cause\" bitsize=\"32\" regnum=\"36\"/>\n");
+
+    for (index, (_, name)) in CP0_EXTRA_REGS.iter().enumerate() {
+        writeln!(
+            xml,
+            "<reg name=\"{}\" bitsize=\"64\" regnum=\"{}\" group=\"system\"/>",
+            name,
+            72 + index
+        )
+        .unwrap();
+    }
+
+    xml.push_str("</feature>\n");
+
+    xml.push_str("<feature name=\"org.gnu.gdb.mips.fpu\">\n");
+
+    for index in 0..32 {
+        writeln!(
+            xml,
+            "<reg name=\"f{}\" bitsize=\"64\" type=\"ieee_double\" regnum=\"{}\"/>",
+            index,
+            38 + index
+        )
+        .unwrap();
+    }
+
+    xml.push_str("<reg name=\"fcsr\" bitsize=\"32\" group=\"float\" regnum=\"70\"/>\n");
+    xml.push_str("<reg name=\"fir\" bitsize=\"32\" group=\"float\" regnum=\"71\"/>\n");
+    xml.push_str("</feature>\n");
+    xml.push_str("</target>\n");
+
+    xml
+}
+
+pub fn reg_size(regnum: usize) -> usize {
+    match regnum {
+        32 | 36 | 70 | 71 => 4,
+        _ => 8,
+    }
+}
+
+pub fn read_reg(device: &mut Device, regnum: usize) -> Option<Vec<u8>> {
+    let cpu = &mut device.cpu;
+
+    let value = match regnum {
+        0..=31 => cpu.reg(regnum),
+        32 => cpu.cp0_reg(12),
+        33 => cpu.lo(),
+        34 => cpu.hi(),
+        35 => cpu.cp0_reg(8),
+        36 => cpu.cp0_reg(13),
+        37 => cpu.pc() as i32 as i64,
+        38..=69 => cpu.cp1_reg(regnum - 38),
+        70 => cpu.cp1_control_reg(31) as i64,
+        71 => cpu.cp1_control_reg(0) as i64,
+        72..=87 => cpu.cp0_reg(CP0_EXTRA_REGS[regnum - 72].0),
+        _ => return None,
+    };
+
+    Some(value.to_be_bytes()[(8 - reg_size(regnum))..].to_vec())
+}
+
+pub fn write_reg(device: &mut Device, regnum: usize, bytes: &[u8]) -> Option<()> {
+    let value = bytes
+        .iter()
+        .fold(0i64, |value, &byte| (value << 8) | byte as i64);
+
+    // 32-bit registers are sign-extended, as they would be by MTC0
+    let value = if bytes.len() == 4 {
+        value as i32 as i64
+    } else {
+        value
+    };
+
+    let cpu = &mut device.cpu;
+
+    match regnum {
+        0..=31 => cpu.set_reg(regnum, value),
+        32 => cpu.set_cp0_reg(12, value),
+        33 => cpu.set_lo(value),
+        34 => cpu.set_hi(value),
+        35 => cpu.set_cp0_reg(8, value),
+        36 => cpu.set_cp0_reg(13, value),
+        37 => cpu.set_pc(&mut device.bus, value as u32),
+        38..=69 => cpu.set_cp1_reg(regnum - 38, value),
+        70 => cpu.set_cp1_control_reg(31, value as u32),
+        71 => cpu.set_cp1_control_reg(0, value as u32),
+        72..=87 => cpu.set_cp0_reg(CP0_EXTRA_REGS[regnum - 72].0, value),
+        _ => return None,
+    }
+
+    Some(())
+}
+
+pub fn pc(processor: Processor) -> Option<u32> {
+    match processor {
+        Processor::Cpu(cpu) => Some(cpu.pc()),
+        _ => None,
+    }
+}
+
+pub fn set_pc(device: &mut Device, pc: u32) {
+    device.cpu.set_pc(&mut device.bus, pc);
+}
+
+pub fn breakpoint_address(address: u32) -> u32 {
+    address
+}
+
+pub fn read_byte(device: &Device, address: u32) -> Option<u8> {
+    device.bus.read_memory(device.cpu.translate(address)?)
+}
+
+pub fn write_byte(device: &mut Device, address: u32, value: u8) -> Option<()> {
+    let paddr = device.cpu.translate(address)?;
+    device.write_memory(paddr, value)
+}
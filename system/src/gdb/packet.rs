@@ -0,0 +1,160 @@
+use std::fmt::Write;
+
+pub enum Input {
+    Packet(Vec<u8>),
+    Interrupt,
+}
+
+// Extracts the next complete packet (or interrupt request) from the input
+// buffer. Acknowledgements and anything with a bad checksum are dropped.
+pub fn parse(buffer: &mut Vec<u8>) -> Option<(Input, bool)> {
+    loop {
+        let &first = buffer.first()?;
+
+        if first == 0x03 {
+            buffer.remove(0);
+            return Some((Input::Interrupt, true));
+        }
+
+        if first != b'$' {
+            buffer.remove(0);
+            continue;
+        }
+
+        let end = buffer.iter().position(|&byte| byte == b'#')?;
+
+        if buffer.len() < end + 3 {
+            return None;
+        }
+
+        let data = unescape(&buffer[1..end]);
+
+        let valid = std::str::from_utf8(&buffer[(end + 1)..(end + 3)])
+            .ok()
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+            .is_some_and(|expected| expected == checksum(&buffer[1..end]));
+
+        buffer.drain(0..(end + 3));
+        return Some((Input::Packet(data), valid));
+    }
+}
+
+// Returns the packet as text. Binary data is only sent with commands that
+// aren't supported, so anything that isn't ASCII is rejected rather than
+// risking slicing through a multi-byte character.
+pub fn command(data: &[u8]) -> Option<&str> {
+    std::str::from_utf8(data)
+        .ok()
+        .filter(|text| text.is_ascii())
+}
+
+pub fn encode(data: &[u8]) -> Vec<u8> {
+    let mut escaped = Vec::with_capacity(data.len());
+
+    for &byte in data {
+        if matches!(byte, b'$' | b'#' | b'}' | b'*') {
+            escaped.push(b'}');
+            escaped.push(byte ^ 0x20);
+        } else {
+            escaped.push(byte);
+        }
+    }
+
+    let mut packet = Vec::with_capacity(escaped.len() + 4);
+    packet.push(b'$');
+    packet.extend_from_slice(&escaped);
+    packet.push(b'#');
+    packet.extend_from_slice(format!("{:02x}", checksum(&escaped)).as_bytes());
+    packet
+}
+
+pub fn to_hex(data: &[u8]) -> String {
+    data.iter().fold(String::new(), |mut hex, byte| {
+        write!(hex, "{:02x}", byte).unwrap();
+        hex
+    })
+}
+
+pub fn from_hex(hex: &str) -> Option<Vec<u8>> {
+    if (hex.len() & 1) != 0 {
+        return None;
+    }
+
+    (0..hex.len())
+        .step_by(2)
+        .map(|index| u8::from_str_radix(hex.get(index..(index + 2))?, 16).ok())
+        .collect()
+}
+
+// Addresses may be sent sign-extended to 64 bits
+pub fn parse_address(hex: &str) -> Option<u32> {
+    u64::from_str_radix(hex, 16).ok().map(|value| value as u32)
+}
+
+fn unescape(data: &[u8]) -> Vec<u8> {
+    let mut result = Vec::with_capacity(data.len());
+    let mut iter = data.iter();
+
+    while let Some(&byte) = iter.next() {
+        if byte == b'}' {
+            if let Some(&next) = iter.next() {
+                result.push(next ^ 0x20);
+            }
+        } else {
+            result.push(byte);
+        }
+    }
+
+    result
+}
+
+fn checksum(data: &[u8]) -> u8 {
+    data.iter().fold(0u8, |sum, &byte| sum.wrapping_add(byte))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_packets() {
+        let mut buffer = b"+$m80000400,4#59\x03$g#00$qSup".to_vec();
+
+        let Some((Input::Packet(data), true)) = parse(&mut buffer) else {
+            panic!("Expected valid packet");
+        };
+
+        assert_eq!(data, b"m80000400,4");
+        assert!(matches!(parse(&mut buffer), Some((Input::Interrupt, true))));
+        assert!(matches!(
+            parse(&mut buffer),
+            Some((Input::Packet(_), false))
+        ));
+        assert!(parse(&mut buffer).is_none());
+        assert_eq!(buffer, b"$qSup");
+    }
+
+    #[test]
+    fn rejects_non_ascii_packets() {
+        let mut buffer = b"$\xe9g#50$G00#a7".to_vec();
+
+        let Some((Input::Packet(data), true)) = parse(&mut buffer) else {
+            panic!("Expected valid packet");
+        };
+
+        assert_eq!(command(&data), None);
+
+        let Some((Input::Packet(data), true)) = parse(&mut buffer) else {
+            panic!("Expected valid packet");
+        };
+
+        assert_eq!(command(&data), Some("G00"));
+    }
+
+    #[test]
+    fn escapes_responses() {
+        assert_eq!(encode(b"OK"), b"$OK#9a");
+        assert_eq!(encode(b"a#b"), b"$a}\x03b#43");
+        assert_eq!(unescape(b"a}\x03b"), b"a#b");
+    }
+}
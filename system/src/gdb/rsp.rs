@@ -0,0 +1,196 @@
+use crate::{Device, Processor};
+use std::fmt::Write;
+
+// DMEM appears at 0x0000 and IMEM at 0x1000, as they do in the RSP's own
+// address space. The PC is reported within IMEM, so code can be read back.
+const MEM_MASK: u32 = 0x1fff;
+const IMEM_BASE: u32 = 0x1000;
+
+pub const REG_COUNT: usize = 76;
+
+// The scalar unit follows GDB's MIPS layout (with unused registers reading as
+// zero), followed by the vector unit as a custom feature
+pub fn target_xml() -> String {
+    let mut xml = String::new();
+
+    xml.push_str("<?xml version=\"1.0\"?>\n");
+    xml.push_str("<!DOCTYPE target SYSTEM \"gdb-target.dtd\">\n");
+    xml.push_str("<target version=\"1.0\">\n");
+    xml.push_str("<architecture>mips</architecture>\n");
+
+    xml.push_str("<feature name=\"org.gnu.gdb.mips.cpu\">\n");
+
+    for index in 0..32 {
+        writeln!(
+            xml,
+            "<reg name=\"r{}\" bitsize=\"32\" regnum=\"{}\"/>",
+            index, index
+        )
+        .unwrap();
+    }
+
+    xml.push_str("<reg name=\"lo\" bitsize=\"32\" regnum=\"33\"/>\n");
+    xml.push_str("<reg name=\"hi\" bitsize=\"32\" regnum=\"34\"/>\n");
+    xml.push_str("<reg name=\"pc\" bitsize=\"32\" regnum=\"37\"/>\n");
+    xml.push_str("</feature>\n");
+
+    xml.push_str("<feature name=\"org.gnu.gdb.mips.cp0\">\n");
+    xml.push_str("<reg name=\"status\" bitsize=\"32\" regnum=\"32\"/>\n");
+    xml.push_str("<reg name=\"badvaddr\" bitsize=\"32\" regnum=\"35\"/>\n");
+    xml.push_str("<reg name=\"cause\" bitsize=\"32\" regnum=\"36\"/>\n");
+    xml.push_str("</feature>\n");
+
+    xml.push_str("<feature name=\"org.n64.rsp.vu\">\n");
+    xml.push_str("<vector id=\"v8u16\" type=\"uint16\" count=\"8\"/>\n");
+
+    for index in 0..32 {
+        writeln!(
+            xml,
+            "<reg name=\"v{}\" bitsize=\"128\" type=\"v8u16\" group=\"vector\" regnum=\"{}\"/>",
+            index,
+            38 + index
+        )
+        .unwrap();
+    }
+
+    for (index, name) in ["acc_hi", "acc_md", "acc_lo"].iter().enumerate() {
+        writeln!(
+            xml,
+            "<reg name=\"{}\" bitsize=\"128\" type=\"v8u16\" group=\"vector\" regnum=\"{}\"/>",
+            name,
+            70 + index
+        )
+        .unwrap();
+    }
+
+    for (index, name) in ["vco", "vcc", "vce"].iter().enumerate() {
+        writeln!(
+            xml,
+            "<reg name=\"{}\" bitsize=\"16\" group=\"vector\" regnum=\"{}\"/>",
+            name,
+            73 + index
+        )
+        .unwrap();
+    }
+
+    xml.push_str("</feature>\n");
+    xml.push_str("</target>\n");
+
+    xml
+}
+
+pub fn reg_size(regnum: usize) -> usize {
+    match regnum {
+        38..=72 => 16,
+        73..=75 => 2,
+        _ => 4,
+    }
+}
+
+pub fn read_reg(device: &mut Device, regnum: usize) -> Option<Vec<u8>> {
+    let rsp = &device.bus.rsp;
+    let core = rsp.core();
+
+    let value = match regnum {
+        0..=31 => core.reg(regnum) as u32,
+        32 => rsp.status(),
+        33..=36 => 0,
+        37 => IMEM_BASE | core.current_pc(),
+        38..=69 => {
+            let vector = core.cp2().reg(regnum - 38);
+            return Some(
+                (0..8)
+                    .flat_map(|lane| vector.lane(lane).to_be_bytes())
+                    .collect(),
+            );
+        }
+        70..=72 => {
+            // Accumulator is stored in reverse lane order
+            let shift = (72 - regnum) * 16;
+            let acc = core.cp2().acc();
+
+            return Some(
+                (0..8)
+                    .flat_map(|lane| ((acc[7 - lane] >> shift) as u16).to_be_bytes())
+                    .collect(),
+            );
+        }
+        73..=75 => {
+            let value = core.cp2().control_reg(regnum - 73) as u16;
+            return Some(value.to_be_bytes().to_vec());
+        }
+        _ => return None,
+    };
+
+    Some(value.to_be_bytes().to_vec())
+}
+
+pub fn write_reg(device: &mut Device, regnum: usize, bytes: &[u8]) -> Option<()> {
+    let lanes: Vec<u16> = bytes
+        .chunks(2)
+        .map(|chunk| u16::from_be_bytes([chunk[0], chunk[1]]))
+        .collect();
+
+    let core = device.bus.rsp.core_mut();
+
+    match regnum {
+        0..=31 => core.set_reg(
+            regnum,
+            i32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]),
+        ),
+        // Status is controlled through SP_STATUS writes instead
+        32..=36 => (),
+        37 => core.set_pc(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])),
+        38..=69 => {
+            let mut vector = core.cp2().reg(regnum - 38);
+
+            for (lane, &value) in lanes.iter().enumerate() {
+                vector.set_lane(lane, value);
+            }
+
+            core.cp2_mut().set_reg(regnum - 38, vector);
+        }
+        70..=72 => {
+            let shift = (72 - regnum) * 16;
+            let acc = core.cp2_mut().acc_mut();
+
+            for (lane, &value) in lanes.iter().enumerate() {
+                let slice = &mut acc[7 - lane];
+                *slice = (*slice & !(0xffff << shift)) | ((value as u64) << shift);
+            }
+        }
+        73..=75 => core.cp2_mut().set_control_reg(regnum - 73, lanes[0] as i32),
+        _ => return None,
+    }
+
+    Some(())
+}
+
+pub fn pc(processor: Processor) -> Option<u32> {
+    match processor {
+        Processor::Rsp(rsp) => Some(IMEM_BASE | rsp.core().current_pc()),
+        _ => None,
+    }
+}
+
+pub fn set_pc(device: &mut Device, pc: u32) {
+    device.bus.rsp.core_mut().set_pc(pc);
+}
+
+pub fn breakpoint_address(address: u32) -> u32 {
+    IMEM_BASE | (address & 0x0ffc)
+}
+
+pub fn read_byte(device: &Device, address: u32) -> Option<u8> {
+    Some(device.bus.rsp.mem().read((address & MEM_MASK) as usize))
+}
+
+pub fn write_byte(device: &mut Device, address: u32, value: u8) -> Option<()> {
+    device
+        .bus
+        .rsp
+        .mem_mut()
+        .write((address & MEM_MASK) as usize, value);
+
+    Some(())
+}
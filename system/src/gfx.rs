@@ -1,36 +1,64 @@
 use crate::rdram::Rdram;
 use std::error::Error;
+use tracing::debug;
 
-pub struct DisplayTarget<T: wgpu::WindowHandle + 'static> {
-    pub window: T,
+const OFFSCREEN_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba8UnormSrgb;
+
+pub struct DisplayTarget {
+    // If no window is provided, output is rendered to an offscreen texture
+    pub window: Option<Box<dyn wgpu::WindowHandle>>,
     pub width: u32,
     pub height: u32,
 }
 
+enum OutputTarget {
+    Surface {
+        surface: wgpu::Surface<'static>,
+        config: wgpu::SurfaceConfiguration,
+    },
+    Offscreen(wgpu::Texture),
+}
+
+pub enum OutputTexture<'a> {
+    Surface(wgpu::SurfaceTexture),
+    Offscreen(&'a wgpu::Texture),
+}
+
 pub struct GfxContext {
-    surface: wgpu::Surface<'static>,
     device: wgpu::Device,
     queue: wgpu::Queue,
-    config: wgpu::SurfaceConfiguration,
+    output: OutputTarget,
 }
 
 impl GfxContext {
-    pub fn new(
-        display_target: DisplayTarget<impl wgpu::WindowHandle>,
-    ) -> Result<Self, Box<dyn Error>> {
+    pub fn new(display_target: DisplayTarget) -> Result<Self, Box<dyn Error>> {
         let instance = wgpu::Instance::new(wgpu::InstanceDescriptor {
             backends: wgpu::Backends::all(),
             ..Default::default()
         });
 
-        let surface = instance.create_surface(display_target.window)?;
+        let surface = display_target
+            .window
+            .map(|window| instance.create_surface(window))
+            .transpose()?;
+
+        let request_adapter = |force_fallback_adapter| {
+            pollster::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions {
+                power_preference: wgpu::PowerPreference::HighPerformance,
+                compatible_surface: surface.as_ref(),
+                force_fallback_adapter,
+            }))
+        };
 
-        let adapter = pollster::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions {
-            power_preference: wgpu::PowerPreference::HighPerformance,
-            compatible_surface: Some(&surface),
-            force_fallback_adapter: false,
-        }))
-        .ok_or("Failed to find adapter compatible with window surface")?;
+        let adapter = if let Some(adapter) = request_adapter(false) {
+            adapter
+        } else if surface.is_none() {
+            // Headless machines may not have a GPU at all, so try a software adapter instead
+            debug!("No hardware adapter found. Requesting fallback adapter.");
+            request_adapter(true).ok_or("Failed to find adapter for offscreen rendering")?
+        } else {
+            return Err("Failed to find adapter compatible with window surface".into());
+        };
 
         let (device, queue) = pollster::block_on(adapter.request_device(
             &wgpu::DeviceDescriptor {
@@ -41,33 +69,42 @@ impl GfxContext {
             None,
         ))?;
 
-        let capabilities = surface.get_capabilities(&adapter);
-
-        let output_format = capabilities
-            .formats
-            .iter()
-            .copied()
-            .find(|f| f.is_srgb())
-            .unwrap_or(capabilities.formats[0]);
-
-        let config = wgpu::SurfaceConfiguration {
-            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
-            format: output_format,
-            width: display_target.width,
-            height: display_target.height,
-            present_mode: wgpu::PresentMode::AutoVsync,
-            alpha_mode: wgpu::CompositeAlphaMode::Auto,
-            view_formats: vec![],
-            desired_maximum_frame_latency: 2,
+        let output = if let Some(surface) = surface {
+            let capabilities = surface.get_capabilities(&adapter);
+
+            let output_format = capabilities
+                .formats
+                .iter()
+                .copied()
+                .find(|f| f.is_srgb())
+                .unwrap_or(capabilities.formats[0]);
+
+            let config = wgpu::SurfaceConfiguration {
+                usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+                format: output_format,
+                width: display_target.width,
+                height: display_target.height,
+                present_mode: wgpu::PresentMode::AutoVsync,
+                alpha_mode: wgpu::CompositeAlphaMode::Auto,
+                view_formats: vec![],
+                desired_maximum_frame_latency: 2,
+            };
+
+            surface.configure(&device, &config);
+
+            OutputTarget::Surface { surface, config }
+        } else {
+            OutputTarget::Offscreen(create_offscreen_texture(
+                &device,
+                display_target.width,
+                display_target.height,
+            ))
         };
 
-        surface.configure(&device, &config);
-
         Ok(Self {
-            surface,
             device,
             queue,
-            config,
+            output,
         })
     }
 
@@ -79,23 +116,168 @@ impl GfxContext {
         &self.queue
     }
 
-    pub fn surface_texture(&self) -> Result<wgpu::SurfaceTexture, wgpu::SurfaceError> {
-        self.surface.get_current_texture()
+    pub fn is_headless(&self) -> bool {
+        matches!(self.output, OutputTarget::Offscreen(..))
+    }
+
+    pub fn output_texture(&self) -> Result<OutputTexture<'_>, wgpu::SurfaceError> {
+        match &self.output {
+            OutputTarget::Surface { surface, .. } => {
+                Ok(OutputTexture::Surface(surface.get_current_texture()?))
+            }
+            OutputTarget::Offscreen(texture) => Ok(OutputTexture::Offscreen(texture)),
+        }
     }
 
     pub fn output_format(&self) -> wgpu::TextureFormat {
-        self.config.format
+        match &self.output {
+            OutputTarget::Surface { config, .. } => config.format,
+            OutputTarget::Offscreen(..) => OFFSCREEN_FORMAT,
+        }
+    }
+
+    pub fn output_size(&self) -> (u32, u32) {
+        match &self.output {
+            OutputTarget::Surface { config, .. } => (config.width, config.height),
+            OutputTarget::Offscreen(texture) => (texture.width(), texture.height()),
+        }
+    }
+
+    // Copies the contents of a texture back from the GPU as RGBA8 pixels. The
+    // texture must be in one of the 8-bit RGBA or BGRA formats.
+    pub fn read_texture(&self, texture: &wgpu::Texture) -> Result<Vec<u8>, Box<dyn Error>> {
+        let width = texture.width() as usize;
+        let height = texture.height() as usize;
+
+        // Rows copied to a buffer must be suitably aligned
+        let row_len = width * 4;
+        let align = wgpu::COPY_BYTES_PER_ROW_ALIGNMENT as usize;
+        let padded_row_len = row_len.div_ceil(align) * align;
+
+        let buffer = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Texture Read Buffer"),
+            size: (padded_row_len * height) as u64,
+            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        });
+
+        let mut encoder = self
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("Texture Read Command Encoder"),
+            });
+
+        encoder.copy_texture_to_buffer(
+            texture.as_image_copy(),
+            wgpu::ImageCopyBuffer {
+                buffer: &buffer,
+                layout: wgpu::ImageDataLayout {
+                    offset: 0,
+                    bytes_per_row: Some(padded_row_len as u32),
+                    rows_per_image: Some(height as u32),
+                },
+            },
+            texture.size(),
+        );
+
+        self.queue.submit(std::iter::once(encoder.finish()));
+
+        let (sender, receiver) = futures_intrusive::channel::shared::oneshot_channel();
+
+        let buffer_slice = buffer.slice(..);
+        buffer_slice.map_async(wgpu::MapMode::Read, move |v| sender.send(v).unwrap());
+
+        self.device.poll(wgpu::Maintain::Wait);
+
+        pollster::block_on(receiver.receive()).ok_or("Texture read was cancelled")??;
+
+        let swap_red_blue = matches!(
+            texture.format(),
+            wgpu::TextureFormat::Bgra8Unorm | wgpu::TextureFormat::Bgra8UnormSrgb
+        );
+
+        let mut pixels = Vec::with_capacity(row_len * height);
+
+        for row in buffer_slice.get_mapped_range().chunks_exact(padded_row_len) {
+            for pixel in row[0..row_len].chunks_exact(4) {
+                if swap_red_blue {
+                    pixels.extend_from_slice(&[pixel[2], pixel[1], pixel[0], pixel[3]]);
+                } else {
+                    pixels.extend_from_slice(pixel);
+                }
+            }
+        }
+
+        Ok(pixels)
     }
 
     pub fn resize(&mut self, width: u32, height: u32) {
-        if width == self.config.width && height == self.config.height {
-            return;
+        match &mut self.output {
+            OutputTarget::Surface { surface, config } => {
+                if width == config.width && height == config.height {
+                    return;
+                }
+
+                config.width = width;
+                config.height = height;
+                surface.configure(&self.device, config);
+            }
+            OutputTarget::Offscreen(texture) => {
+                if width == texture.width() && height == texture.height() {
+                    return;
+                }
+
+                *texture = create_offscreen_texture(&self.device, width, height);
+            }
         }
+    }
+}
 
-        self.config.width = width;
-        self.config.height = height;
-        self.surface.configure(&self.device, &self.config);
+impl<'a> OutputTexture<'a> {
+    pub fn texture(&self) -> &wgpu::Texture {
+        match self {
+            Self::Surface(surface_texture) => &surface_texture.texture,
+            Self::Offscreen(texture) => texture,
+        }
     }
+
+    pub fn present(self) {
+        if let Self::Surface(surface_texture) = self {
+            surface_texture.present();
+        }
+    }
+}
+
+fn create_offscreen_texture(device: &wgpu::Device, width: u32, height: u32) -> wgpu::Texture {
+    device.create_texture(&wgpu::TextureDescriptor {
+        label: Some("Offscreen Output Texture"),
+        size: wgpu::Extent3d {
+            width: width.max(1),
+            height: height.max(1),
+            depth_or_array_layers: 1,
+        },
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: OFFSCREEN_FORMAT,
+        usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+        view_formats: &[],
+    })
+}
+
+// Encodes RGBA8 pixels as a PNG image
+pub fn encode_png(width: u32, height: u32, pixels: &[u8]) -> Result<Vec<u8>, Box<dyn Error>> {
+    let mut data = Vec::new();
+
+    let mut encoder = png::Encoder::new(&mut data, width, height);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+
+    let mut writer = encoder.write_header()?;
+    writer.write_image_data(pixels)?;
+    writer.finish()?;
+
+    Ok(data)
 }
 
 pub fn decode_rgba16(word: u16) -> u32 {
@@ -1,6 +1,7 @@
+use crate::serial::Accessory;
 use crc::Crc;
-use phf::{phf_map, Map};
 use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
 use tracing::debug;
 
 #[derive(Copy, Clone, Eq, PartialEq, Debug)]
@@ -16,13 +17,28 @@ pub enum CicType {
 
 #[derive(Copy, Clone, Eq, PartialEq, Debug)]
 pub enum SaveType {
+    None,
     Eeprom4K,
     Eeprom16K,
+    Sram256K,
+    // Three banks of 256Kbit
+    Sram768K,
+    // Four banks of 256Kbit (homebrew only)
+    Sram1M,
+    FlashRam,
 }
 
 pub struct Header {
+    pub code: String,
+    pub version: u8,
+    // CRCs of the boot code, as stored in the header
+    pub crcs: [u32; 2],
     pub cic_type: CicType,
-    pub save_type: SaveType,
+    // Only homebrew ROMs say which save type they use. For anything else, it
+    // comes from the settings database.
+    pub save_type: Option<SaveType>,
+    // Accessories the ROM asks for, by port (None if it doesn't say)
+    pub accessories: [Option<Accessory>; 4],
 }
 
 pub fn parse(rom: &[u8]) -> Header {
@@ -42,21 +58,48 @@ pub fn parse(rom: &[u8]) -> Header {
         _ => CicType::Unknown,
     };
 
-    let code_without_region = String::from_utf8_lossy(&code[0..=2]);
+    // The Advanced Homebrew ROM Header puts the save type in the version byte
+    let homebrew = &code[1..=2] == b"ED";
 
-    let save_type = *SAVE_TYPE_MAP
-        .get(&code_without_region)
-        .unwrap_or(&SaveType::Eeprom4K);
+    let save_type = homebrew.then_some(match version >> 4 {
+        1 => SaveType::Eeprom4K,
+        2 => SaveType::Eeprom16K,
+        3 => SaveType::Sram256K,
+        4 => SaveType::Sram768K,
+        5 => SaveType::FlashRam,
+        6 => SaveType::Sram1M,
+        _ => SaveType::None,
+    });
+
+    let mut accessories = [None; 4];
+
+    if homebrew {
+        for (accessory, &byte) in accessories.iter_mut().zip(&rom[0x34..=0x37]) {
+            *accessory = match byte {
+                // A controller, without saying what is plugged into it
+                0x00 => None,
+                0x01 => Some(Accessory::ControllerPak),
+                _ => Some(Accessory::None),
+            };
+        }
+    }
 
     debug!("Title: {}", String::from_utf8_lossy(title));
     debug!("Code: {}", String::from_utf8_lossy(code));
     debug!("Version: {}", version);
     debug!("CIC Type: {} (checksum: {})", cic_type, ipl3_checksum);
-    debug!("Save Type: {}", save_type);
+    if let Some(save_type) = save_type {
+        debug!("Save Type: {}", save_type);
+    }
 
     Header {
+        code: String::from_utf8_lossy(code).into_owned(),
+        version,
+        crcs: [0x10, 0x14]
+            .map(|offset| u32::from_be_bytes(rom[offset..(offset + 4)].try_into().unwrap())),
         cic_type,
         save_type,
+        accessories,
     }
 }
 
@@ -83,13 +126,66 @@ impl Display for SaveType {
             f,
             "{}",
             match self {
+                SaveType::None => "None",
                 SaveType::Eeprom4K => "EEPROM (4Kbit)",
                 SaveType::Eeprom16K => "EEPROM (16Kbit)",
+                SaveType::Sram256K => "SRAM (256Kbit)",
+                SaveType::Sram768K => "SRAM (768Kbit)",
+                SaveType::Sram1M => "SRAM (1Mbit)",
+                SaveType::FlashRam => "FlashRAM (1Mbit)",
             }
         )
     }
 }
 
-const SAVE_TYPE_MAP: Map<&'static str, SaveType> = phf_map! {
-    "NYS" => SaveType::Eeprom16K,
-};
+impl FromStr for SaveType {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Ok(match value {
+            "none" => SaveType::None,
+            "eeprom4k" => SaveType::Eeprom4K,
+            "eeprom16k" => SaveType::Eeprom16K,
+            "sram256k" => SaveType::Sram256K,
+            "sram768k" => SaveType::Sram768K,
+            "sram1m" => SaveType::Sram1M,
+            "flashram" => SaveType::FlashRam,
+            _ => {
+                return Err(format!(
+                    "unknown save type '{}' (expected none, eeprom4k, eeprom16k, sram256k, \
+                     sram768k, sram1m or flashram)",
+                    value
+                ))
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_homebrew_header() {
+        let mut rom = vec![0; 0x1000];
+        rom[0x3b..=0x3e].copy_from_slice(b"NEDA");
+        rom[0x3f] = 0x51;
+        rom[0x34..=0x37].copy_from_slice(&[0x01, 0x00, 0x02, 0xff]);
+
+        let header = parse(&rom);
+        assert_eq!(header.save_type, Some(SaveType::FlashRam));
+
+        assert_eq!(
+            header.accessories,
+            [
+                Some(Accessory::ControllerPak),
+                None,
+                Some(Accessory::None),
+                Some(Accessory::None)
+            ]
+        );
+
+        rom[0x3b..=0x3e].copy_from_slice(b"NZLE");
+        assert_eq!(parse(&rom).save_type, None);
+    }
+}
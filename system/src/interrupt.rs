@@ -1,5 +1,7 @@
+use crate::snapshot::{Reader, Writer};
 use bitflags::bitflags;
 use std::cell::Cell;
+use std::error::Error;
 use std::rc::Rc;
 use tracing::debug;
 
@@ -39,6 +41,17 @@ impl CpuInterrupt {
         self.status.get()
     }
 
+    pub fn save_state(&self, writer: &mut Writer) {
+        writer.tag(b"CINT");
+        writer.u8(self.status.get().bits());
+    }
+
+    pub fn load_state(&mut self, reader: &mut Reader) -> Result<(), Box<dyn Error>> {
+        reader.tag(b"CINT")?;
+        self.status.set(CpuIntType::from_bits_retain(reader.u8()?));
+        Ok(())
+    }
+
     pub fn raise(&mut self, int_type: CpuIntType) {
         let prev_status = self.status.get();
         self.status.set(prev_status | int_type);
@@ -78,6 +91,20 @@ impl RcpInterrupt {
         self.mask.get()
     }
 
+    pub fn save_state(&self, writer: &mut Writer) {
+        writer.tag(b"RINT");
+        writer.u8(self.status.get().bits());
+        writer.u8(self.mask.get().bits());
+    }
+
+    pub fn load_state(&mut self, reader: &mut Reader) -> Result<(), Box<dyn Error>> {
+        reader.tag(b"RINT")?;
+        self.status.set(RcpIntType::from_bits_retain(reader.u8()?));
+        self.mask.set(RcpIntType::from_bits_retain(reader.u8()?));
+        self.update();
+        Ok(())
+    }
+
     pub fn set_mask(&mut self, mask: RcpIntType) {
         self.mask.set(mask);
         debug!("RCP Interrupt Mask: {:06b}", mask);
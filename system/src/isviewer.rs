@@ -0,0 +1,109 @@
+use crate::memory::{Memory, Size};
+
+// ISViewer occupies the top 64 KiB of cartridge space. Text is written to the
+// buffer, then its length is written to the 'put' register to output it.
+pub const BASE_ADDRESS: u32 = 0x13ff_0000;
+
+const SIZE: usize = 0x0001_0000;
+const MAGIC: u32 = 0x4953_3634; // 'IS64'
+const PUT: u32 = 0x0014;
+// Not part of ISViewer: lets test ROMs report a result
+const EXIT_CODE: u32 = 0x001c;
+const BUFFER: usize = 0x0020;
+
+pub trait DebugSink {
+    // Called with each piece of text the game outputs, along with the VI
+    // frame and RCP cycle on which it was output
+    fn write(&mut self, text: &str, frame: u64, cycle: u64);
+}
+
+// Used when no other sink is given
+pub struct StdoutSink;
+
+impl DebugSink for StdoutSink {
+    fn write(&mut self, text: &str, _frame: u64, _cycle: u64) {
+        println!("{}", text);
+    }
+}
+
+pub struct IsViewer {
+    mem: Memory<u64>,
+    sink: Box<dyn DebugSink>,
+    exit_code: Option<u32>,
+}
+
+impl IsViewer {
+    pub fn new(sink: Box<dyn DebugSink>) -> Self {
+        let mut mem = Memory::with_byte_len(SIZE);
+        mem.write(0, MAGIC);
+
+        Self {
+            mem,
+            sink,
+            exit_code: None,
+        }
+    }
+
+    pub fn exit_code(&self) -> Option<u32> {
+        self.exit_code
+    }
+
+    pub fn read<T: Size>(&self, address: u32) -> T {
+        self.mem.read(address as usize)
+    }
+
+    pub fn write<T: Size>(&mut self, address: u32, value: T, frame: u64, cycle: u64) {
+        self.mem.write(address as usize, value);
+
+        match address {
+            PUT => {
+                let len = value.to_usize().unwrap().min(SIZE - BUFFER);
+                let text = String::from_utf8_lossy(&self.mem[BUFFER..(BUFFER + len)]);
+                self.sink.write(&text, frame, cycle);
+            }
+            EXIT_CODE => self.exit_code = Some(value.to_u32().unwrap()),
+            _ => (),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    struct Capture(Rc<RefCell<Vec<(String, u64, u64)>>>);
+
+    impl DebugSink for Capture {
+        fn write(&mut self, text: &str, frame: u64, cycle: u64) {
+            self.0.borrow_mut().push((text.to_owned(), frame, cycle));
+        }
+    }
+
+    #[test]
+    fn outputs_text_from_the_end_of_the_buffer() {
+        let messages = Rc::new(RefCell::new(Vec::new()));
+        let mut isviewer = IsViewer::new(Box::new(Capture(messages.clone())));
+
+        assert_eq!(isviewer.read::<u32>(0), MAGIC);
+
+        let text = vec![b'x'; 0x8000];
+
+        for (index, chunk) in text.chunks_exact(4).enumerate() {
+            let word = u32::from_be_bytes(chunk.try_into().unwrap());
+            isviewer.write(BUFFER as u32 + index as u32 * 4, word, 0, 0);
+        }
+
+        isviewer.write(BUFFER as u32 + 0x8000, u32::from_be_bytes(*b"end!"), 0, 0);
+        assert_eq!(isviewer.read::<u32>(BUFFER as u32 + 0x8000), 0x656e_6421);
+
+        isviewer.write(PUT, 0x8004u32, 3, 1234);
+
+        let messages = messages.borrow();
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].0.len(), 0x8004);
+        assert!(messages[0].0.ends_with("xend!"));
+        assert_eq!((messages[0].1, messages[0].2), (3, 1234));
+    }
+}
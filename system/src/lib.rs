@@ -1,21 +1,42 @@
 pub use audio::AudioReceiver;
-pub use gfx::DisplayTarget;
-pub use serial::JoypadState;
+pub use cpu::disassemble as disassemble_cpu;
+pub use debugger::Debugger;
+pub use gdb::{GdbStatus, GdbStub, GdbTarget};
+pub use gfx::{encode_png, DisplayTarget};
+pub use header::SaveType;
+pub use isviewer::DebugSink;
+pub use movie::{MovieMode, PlaybackStatus};
+pub use rdp::{disassemble as disassemble_rdp, Image, RenderBackend};
+pub use rsp::{disassemble as disassemble_rsp, disassemble_imem};
+pub use serial::{Accessory, JoypadState};
+pub use video::{AntiAliasMode, DisplayMode, Frame, ScreenshotMode};
+pub use wav::WavWriter;
 
 use audio::AudioInterface;
 use cpu::Cpu;
+use crc::Crc;
 use gfx::GfxContext;
 use interrupt::{CpuInterrupt, RcpInterrupt};
-use memory::{Mapping, Memory, Size};
+use isviewer::{IsViewer, StdoutSink};
+use memory::{Mapping, Size};
 use mips_interface::MipsInterface;
+use movie::{Movie, Settings as MovieSettings};
 use peripheral::PeripheralInterface;
 use rdp::Rdp;
 use rdram::Rdram;
+use recorder::Recorder;
+use rewind::Rewind;
 use rsp::Rsp;
 use serial::SerialInterface;
+use snapshot::{Reader, Writer};
 use std::error::Error;
-use tracing::warn;
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tracing::{error, warn};
 use video::VideoInterface;
+use watch::AccessSource;
 
 #[cfg(feature = "profiling")]
 use cpu::Stats as CpuStats;
@@ -24,17 +45,29 @@ use rsp::Stats as RspStats;
 
 mod audio;
 mod cpu;
+mod debugger;
+mod gdb;
 mod gfx;
 mod header;
 mod interrupt;
+mod isviewer;
 mod memory;
 mod mips_interface;
+mod movie;
 mod peripheral;
 mod rdp;
 mod rdram;
+mod recorder;
+mod rewind;
 mod rsp;
+mod run_control;
+mod save;
 mod serial;
+mod settings;
+mod snapshot;
 mod video;
+mod watch;
+mod wav;
 
 const RCP_CLOCK_RATE: f64 = 62500000.0;
 
@@ -53,14 +86,38 @@ struct Bus {
     ai: AudioInterface,
     pi: PeripheralInterface,
     si: SerialInterface,
-    systest_buffer: Memory<u64>,
+    isviewer: IsViewer,
+    cycles: u64,
 }
 
-pub struct DeviceOptions<T: wgpu::WindowHandle + 'static> {
-    pub display_target: DisplayTarget<T>,
+pub struct DeviceOptions {
+    pub display_target: DisplayTarget,
     pub pif_data: Option<Vec<u8>>,
     pub rom_data: Vec<u8>,
     pub granularity: Option<u64>,
+    pub rdp_backend: RenderBackend,
+    // Memory budget for rewind history, in bytes (zero disables rewind)
+    pub rewind_budget: usize,
+    pub movie: Option<MovieMode>,
+    // Receives ISViewer output (printed to stdout if not set)
+    pub debug_sink: Option<Box<dyn DebugSink>>,
+    // Save files are named by adding an extension to this path. Without it,
+    // saves are kept in memory only.
+    pub save_path: Option<PathBuf>,
+    // Overrides the save type detected from the ROM header
+    pub save_type: Option<SaveType>,
+    // Used for any port the ROM header or game settings don't specify an
+    // accessory for
+    pub accessories: [Accessory; 4],
+    // Per-game settings (see games.ini for the format), which take priority
+    // over the bundled settings
+    pub settings: Option<String>,
+}
+
+// Replays a capture taken with 'Device::capture_rdp_frame' on an offscreen
+// target, returning the color image it draws
+pub fn replay_rdp_capture(data: &[u8]) -> Result<Image, Box<dyn Error>> {
+    rdp::replay(data)
 }
 
 #[cfg(feature = "profiling")]
@@ -72,14 +129,35 @@ pub struct Stats {
 pub struct Device {
     cpu: Cpu,
     bus: Bus,
-    gfx: GfxContext,
-    cycles: u64,
+    // Not created when running headless with the software RDP, so that no GPU
+    // is needed
+    gfx: Option<GfxContext>,
     granularity: u64,
+    rewind: Option<Rewind>,
+    // Steps deferred by the debugger stopping part way through a cycle
+    cpu_steps: u64,
+    rsp_steps: u64,
+    recorder: Option<Recorder<BufWriter<File>, BufWriter<File>>>,
+    // Cycle count when recording started
+    recording_start: u64,
+}
+
+// A processor that is about to execute an instruction
+#[derive(Copy, Clone)]
+enum Processor<'a> {
+    Cpu(&'a Cpu),
+    Rsp(&'a Rsp),
 }
 
 impl Device {
-    pub fn new(options: DeviceOptions<impl wgpu::WindowHandle>) -> Result<Self, Box<dyn Error>> {
-        let gfx = GfxContext::new(options.display_target)?;
+    pub fn new(options: DeviceOptions) -> Result<Self, Box<dyn Error>> {
+        let gfx = if options.rdp_backend == RenderBackend::Software
+            && options.display_target.window.is_none()
+        {
+            None
+        } else {
+            Some(GfxContext::new(options.display_target)?)
+        };
 
         let mut memory_map = vec![Mapping::None; 512];
 
@@ -94,6 +172,7 @@ impl Device {
         memory_map[0x047] = Mapping::RdramInterface;
         memory_map[0x048] = Mapping::SerialInterface;
         memory_map[0x050..=0x05f].fill(Mapping::DDRegisters);
+        memory_map[0x080..=0x0ff].fill(Mapping::CartridgeRam);
         memory_map[0x100..=0x1fb].fill(Mapping::CartridgeRom);
         memory_map[0x1fc] = Mapping::Pif;
 
@@ -106,33 +185,107 @@ impl Device {
         let skip_pif_rom = options.pif_data.is_none();
 
         let header = header::parse(&options.rom_data);
+        let settings = settings::lookup(&header, options.settings.as_deref())?;
+
+        let save_type = options
+            .save_type
+            .or(settings.save_type)
+            .or(header.save_type)
+            .unwrap_or(SaveType::Eeprom4K);
+
+        let cic_type = settings.cic_type.unwrap_or(header.cic_type);
+
+        let granularity = options
+            .granularity
+            .or(settings.granularity)
+            .unwrap_or(DEFAULT_GRANULARITY);
+
+        let expansion_pak = settings.expansion_pak.unwrap_or(true);
+
+        let mut accessories = options.accessories;
+
+        for ((accessory, requested), configured) in accessories
+            .iter_mut()
+            .zip(header.accessories)
+            .zip(settings.accessories)
+        {
+            *accessory = configured.or(requested).unwrap_or(*accessory);
+        }
+
+        // The RTC starts from the host clock, unless playing back a movie
+        let host_time = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |time| time.as_secs() as i64);
+
+        let movie = if let Some(mode) = options.movie {
+            let crc = Crc::<u32>::new(&crc::CRC_32_CKSUM);
+
+            let settings = MovieSettings {
+                rom_hash: crc.checksum(&options.rom_data),
+                pif_hash: options.pif_data.as_ref().map(|data| crc.checksum(data)),
+                granularity,
+                rdp_backend: options.rdp_backend as u8,
+                save_type: save_type as u8,
+                cic_type: cic_type as u8,
+                accessories: accessories.map(|accessory| accessory as u8),
+                expansion_pak,
+            };
+
+            Some(match mode {
+                MovieMode::Record => Movie::record(settings, host_time),
+                MovieMode::Playback(data) => Movie::play(settings, &data)?,
+            })
+        } else {
+            None
+        };
+
+        let mut si = SerialInterface::new(
+            rcp_int.clone(),
+            options.pif_data,
+            cic_type,
+            save_type,
+            accessories,
+            settings
+                .rtc
+                .unwrap_or(false)
+                .then(|| movie.as_ref().map_or(host_time, Movie::rtc_start)),
+            options.save_path.as_deref(),
+        )?;
+
+        si.set_movie(movie);
 
         Ok(Self {
             cpu: Cpu::new(skip_pif_rom),
             bus: Bus {
                 memory_map,
                 cpu_int,
-                rdram: Rdram::new(header.cic_type),
+                rdram: Rdram::new(cic_type, expansion_pak),
                 rsp: Rsp::new(
                     rcp_int.clone(),
                     skip_pif_rom.then(|| &options.rom_data[0..0x1000]),
                 ),
-                rdp: Rdp::new(rcp_int.clone(), &gfx),
+                rdp: Rdp::new(rcp_int.clone(), gfx.as_ref(), options.rdp_backend)?,
                 mi: MipsInterface::new(rcp_int.clone()),
-                vi: VideoInterface::new(rcp_int.clone(), &gfx, skip_pif_rom)?,
+                vi: VideoInterface::new(rcp_int.clone(), gfx.as_ref(), skip_pif_rom)?,
                 ai: AudioInterface::new(rcp_int.clone()),
-                pi: PeripheralInterface::new(rcp_int.clone(), options.rom_data, skip_pif_rom),
-                si: SerialInterface::new(
+                pi: PeripheralInterface::new(
                     rcp_int,
-                    options.pif_data,
-                    header.cic_type,
-                    header.save_type,
-                ),
-                systest_buffer: Memory::with_byte_len(512),
+                    options.rom_data,
+                    skip_pif_rom,
+                    save_type,
+                    options.save_path.as_deref(),
+                )?,
+                si,
+                isviewer: IsViewer::new(options.debug_sink.unwrap_or_else(|| Box::new(StdoutSink))),
+                cycles: 0,
             },
             gfx,
-            cycles: 0,
-            granularity: options.granularity.unwrap_or(DEFAULT_GRANULARITY),
+            granularity,
+            rewind: (options.rewind_budget > 0).then(|| Rewind::new(options.rewind_budget)),
+            cpu_steps: 0,
+            rsp_steps: 0,
+            recorder: None,
+            recording_start: 0,
         })
     }
 
@@ -155,11 +308,193 @@ impl Device {
     }
 
     pub fn resize(&mut self, width: u32, height: u32) {
-        self.gfx.resize(width, height);
+        if let Some(gfx) = &mut self.gfx {
+            gfx.resize(width, height);
+        }
+    }
+
+    pub fn is_headless(&self) -> bool {
+        self.gfx.as_ref().is_none_or(GfxContext::is_headless)
     }
 
+    // Not required in headless mode, but will render the upscaled output to
+    // the offscreen texture if called (and there is a GPU to do so)
     pub fn present(&mut self) -> Result<(), wgpu::SurfaceError> {
-        self.bus.vi.present(&self.gfx)
+        match self.gfx.as_ref() {
+            Some(gfx) => self.bus.vi.present(gfx),
+            None => Ok(()),
+        }
+    }
+
+    // Returns the decoded RGBA8 pixels of the most recently completed field
+    pub fn frame(&self) -> Frame<'_> {
+        self.bus.vi.frame()
+    }
+
+    // Encodes the most recently completed frame as a PNG image
+    pub fn screenshot(&mut self, mode: ScreenshotMode) -> Result<Vec<u8>, Box<dyn Error>> {
+        self.bus.vi.screenshot(self.gfx.as_ref(), mode)
+    }
+
+    // Captures the state of the entire machine. Cartridge ROM is not included,
+    // so the state can only be loaded into a device running the same ROM.
+    pub fn save_state(&mut self) -> Vec<u8> {
+        // Make sure anything the hardware renderer has drawn is in RDRAM
+        self.bus.rdp.sync(self.gfx.as_ref(), &mut self.bus.rdram);
+
+        let mut writer = Writer::new();
+        writer.tag(b"DEV ");
+        writer.u64(self.bus.pi.rom_id());
+        writer.u64(self.bus.cycles);
+
+        self.bus.rdram.save_state(&mut writer);
+        self.cpu.save_state(&mut writer);
+        self.bus.cpu_int.save_state(&mut writer);
+        self.bus.mi.save_state(&mut writer);
+        self.bus.rsp.save_state(&mut writer);
+        self.bus.rdp.save_state(&mut writer);
+        self.bus.vi.save_state(&mut writer);
+        self.bus.ai.save_state(&mut writer);
+        self.bus.pi.save_state(&mut writer);
+        self.bus.si.save_state(&mut writer);
+
+        writer.finish()
+    }
+
+    // If this returns an error, the device may be left in an inconsistent
+    // state and should be reset (or another state loaded) before continuing
+    pub fn load_state(&mut self, data: &[u8]) -> Result<(), Box<dyn Error>> {
+        let mut reader = Reader::new(data)?;
+        reader.tag(b"DEV ")?;
+
+        if reader.u64()? != self.bus.pi.rom_id() {
+            return Err("Save state is for a different ROM".into());
+        }
+
+        self.bus.cycles = reader.u64()?;
+
+        self.bus.rdram.load_state(&mut reader)?;
+        self.cpu.load_state(&mut reader)?;
+        self.bus.cpu_int.load_state(&mut reader)?;
+        self.bus.mi.load_state(&mut reader)?;
+        self.bus.rsp.load_state(&mut reader)?;
+
+        self.bus
+            .rdp
+            .load_state(&mut reader, &mut self.bus.rdram, self.gfx.as_ref())?;
+
+        self.bus
+            .vi
+            .load_state(&mut reader, &self.bus.rdram, self.gfx.as_ref())?;
+
+        self.bus.ai.load_state(&mut reader)?;
+        self.bus.pi.load_state(&mut reader)?;
+        self.bus.si.load_state(&mut reader)?;
+
+        reader.finish()
+    }
+
+    // Steps back by at least the given number of frames (snapshots are only
+    // taken every few frames, so this is rounded up), returning how many were
+    // actually rewound. This will be zero if rewind is disabled or there is
+    // no history left.
+    pub fn rewind(&mut self, frames: usize) -> Result<usize, Box<dyn Error>> {
+        if self.bus.si.movie().is_some() {
+            return Err("Cannot rewind while recording or playing back a movie".into());
+        }
+
+        let Some(mut rewind) = self.rewind.take() else {
+            return Ok(0);
+        };
+
+        let result = match rewind.rewind(frames) {
+            Some((state, frames)) => self.load_state(state).map(|()| frames),
+            None => Ok(0),
+        };
+
+        self.rewind = Some(rewind);
+        result
+    }
+
+    // Returns the movie recorded so far, if recording
+    pub fn movie_data(&self) -> Option<Vec<u8>> {
+        self.bus
+            .si
+            .movie()
+            .filter(|movie| !movie.is_playback())
+            .map(Movie::encode)
+    }
+
+    pub fn playback_status(&self) -> Option<PlaybackStatus> {
+        self.bus
+            .si
+            .movie()
+            .filter(|movie| movie.is_playback())
+            .map(Movie::status)
+    }
+
+    // Records every RDP command word submitted from now on
+    pub fn start_rdp_capture(&mut self) {
+        self.bus.rdp.start_capture();
+    }
+
+    // Stops recording and returns the disassembled command stream
+    pub fn take_rdp_capture(&mut self) -> Option<String> {
+        self.bus
+            .rdp
+            .take_capture()
+            .map(|words| rdp::disassemble(&words))
+    }
+
+    // Records video to a Y4M file and audio to a WAV file, from the end of
+    // the current frame until 'stop_recording' is called
+    pub fn start_recording(
+        &mut self,
+        video_path: &Path,
+        audio_path: &Path,
+    ) -> Result<(), Box<dyn Error>> {
+        self.stop_recording()?;
+
+        let video = BufWriter::new(File::create(video_path)?);
+        let audio = BufWriter::new(File::create(audio_path)?);
+
+        self.recorder = Some(Recorder::new(
+            video,
+            audio,
+            self.bus.vi.frame_rate(),
+            self.bus.ai.sample_rate(),
+        )?);
+        self.recording_start = self.bus.cycles;
+        self.bus.ai.set_recording(true);
+
+        Ok(())
+    }
+
+    pub fn stop_recording(&mut self) -> Result<(), Box<dyn Error>> {
+        self.bus.ai.set_recording(false);
+
+        if let Some(recorder) = self.recorder.take() {
+            recorder.finish()?;
+        }
+
+        Ok(())
+    }
+
+    // The value a test ROM has written to 0x13FF001C to report its result,
+    // if it has done so
+    pub fn exit_code(&self) -> Option<u32> {
+        self.bus.isviewer.exit_code()
+    }
+
+    // Records the RDP's work between the next two SYNC_FULL commands, for
+    // replaying with 'replay_rdp_capture'
+    pub fn capture_rdp_frame(&mut self) {
+        self.bus.rdp.capture_frame();
+    }
+
+    // Returns the capture once it has finished
+    pub fn take_rdp_frame_capture(&mut self) -> Option<Vec<u8>> {
+        self.bus.rdp.take_frame_capture()
     }
 
     pub fn update_joypads(&mut self, joypads: &[JoypadState; 4]) {
@@ -169,9 +504,77 @@ impl Device {
     pub fn run_frame(&mut self, receiver: &mut impl AudioReceiver) {
         if self.granularity == 0 {
             while !self.step(receiver) {}
-            return;
+        } else {
+            self.run_frame_batched(receiver);
         }
 
+        self.end_frame();
+    }
+
+    // Runs until the end of the frame, or until 'stop' returns true just
+    // before the CPU or RSP begins executing an instruction. Returns true if
+    // stopped.
+    fn run_frame_until(
+        &mut self,
+        receiver: &mut impl AudioReceiver,
+        mut stop: impl FnMut(Processor) -> bool,
+    ) -> bool {
+        loop {
+            let (frame_done, stopped) = self.step_debug(receiver, &mut stop);
+
+            if frame_done {
+                self.end_frame();
+            }
+
+            if stopped || frame_done {
+                return stopped;
+            }
+        }
+    }
+
+    // Writes any save data that has changed to disk immediately, rather than
+    // waiting for the game to stop writing
+    pub fn flush_saves(&mut self) {
+        self.bus.pi.flush_saves();
+        self.bus.si.flush_saves();
+    }
+
+    fn end_frame(&mut self) {
+        let checksum = self.cpu.checksum();
+
+        self.bus.pi.tick_saves();
+        self.bus.si.tick_saves();
+
+        if let Some(movie) = self.bus.si.movie_mut() {
+            movie.end_frame(checksum);
+        }
+
+        if self.rewind.as_mut().is_some_and(Rewind::end_frame) {
+            let state = self.save_state();
+            self.rewind.as_mut().unwrap().push(state);
+        }
+
+        if let Some(recorder) = &mut self.recorder {
+            let recorded = self.bus.ai.take_recorded();
+            let elapsed =
+                self.bus.cycles.saturating_sub(self.recording_start) as f64 / RCP_CLOCK_RATE;
+
+            let result = recorded
+                .iter()
+                .try_for_each(|(sample_rate, samples)| {
+                    recorder.write_samples(samples, *sample_rate)
+                })
+                .and_then(|()| recorder.write_frame(&self.bus.vi.frame(), elapsed));
+
+            if let Err(err) = result {
+                error!("Recording stopped: {}", err);
+                self.recorder = None;
+                self.bus.ai.set_recording(false);
+            }
+        }
+    }
+
+    fn run_frame_batched(&mut self, receiver: &mut impl AudioReceiver) {
         let mut frame_done = false;
 
         while !frame_done {
@@ -181,7 +584,9 @@ impl Device {
             }
 
             for _ in 0..self.granularity {
-                self.bus.rdp.step_core(&mut self.bus.rdram, &self.gfx);
+                self.bus
+                    .rdp
+                    .step_core(&mut self.bus.rdram, self.gfx.as_ref());
                 self.bus.rdp.step_dma(&self.bus.rdram, self.bus.rsp.mem());
             }
 
@@ -193,41 +598,154 @@ impl Device {
                 }
 
                 self.bus.ai.step(&self.bus.rdram, receiver);
-                self.bus.pi.step(&mut self.bus.rdram);
-                self.bus.si.step(&mut self.bus.rdram);
-                frame_done |= self.bus.vi.step(&self.bus.rdram, &self.gfx);
+                self.bus.pi.step(&mut self.bus.rdram, self.bus.cycles);
+                self.bus.si.step(&mut self.bus.rdram, self.bus.cycles);
+                frame_done |= self.bus.vi.step(&self.bus.rdram, self.gfx.as_ref());
+            }
+
+            self.bus.cycles += self.granularity;
+        }
+    }
+
+    // As 'step', but checks with 'stop' before each CPU or RSP instruction. If
+    // either is stopped, the rest of the cycle still runs, and the stopped
+    // processor catches up on its remaining steps next time.
+    fn step_debug(
+        &mut self,
+        receiver: &mut impl AudioReceiver,
+        stop: &mut impl FnMut(Processor) -> bool,
+    ) -> (bool, bool) {
+        self.bus.cycles += 1;
+        self.cpu_steps += if (self.bus.cycles & 1) == 0 { 2 } else { 1 };
+        self.rsp_steps += 1;
+
+        let mut stopped = false;
+
+        while self.cpu_steps > 0 {
+            if self.cpu.is_ready() && stop(Processor::Cpu(&self.cpu)) {
+                stopped = true;
+                break;
             }
 
-            self.cycles += self.granularity;
+            self.set_access_source(AccessSource::Cpu, self.cpu.pc());
+            self.cpu.step(&mut self.bus);
+            self.cpu_steps -= 1;
+
+            // Stop straight after the instruction that triggered a watchpoint
+            if self.bus.rdram.watch().has_hit() {
+                stopped = true;
+                break;
+            }
         }
+
+        while self.rsp_steps > 0 {
+            if self.bus.rsp.is_running() && stop(Processor::Rsp(&self.bus.rsp)) {
+                stopped = true;
+                break;
+            }
+
+            self.bus.rsp.step_core(self.bus.rdp.shared());
+            self.rsp_steps -= 1;
+        }
+
+        let cpu_pc = self.cpu.pc();
+
+        self.set_access_source(AccessSource::RspDma, self.bus.rsp.core().current_pc());
+        self.bus.rsp.step_dma(&mut self.bus.rdram);
+
+        self.set_access_source(AccessSource::Rdp, cpu_pc);
+        self.bus
+            .rdp
+            .step_core(&mut self.bus.rdram, self.gfx.as_ref());
+        self.bus.rdp.step_dma(&self.bus.rdram, self.bus.rsp.mem());
+
+        self.set_access_source(AccessSource::AiDma, cpu_pc);
+        self.bus.ai.step(&self.bus.rdram, receiver);
+        self.set_access_source(AccessSource::PiDma, cpu_pc);
+        self.bus.pi.step(&mut self.bus.rdram, self.bus.cycles);
+        self.set_access_source(AccessSource::SiDma, cpu_pc);
+        self.bus.si.step(&mut self.bus.rdram, self.bus.cycles);
+
+        self.set_access_source(AccessSource::Vi, cpu_pc);
+        let frame_done = self.bus.vi.step(&self.bus.rdram, self.gfx.as_ref());
+
+        (frame_done, stopped || self.bus.rdram.watch().has_hit())
+    }
+
+    // Writes made by the debugger bypass the CPU, so stale instructions must
+    // be flushed from the instruction cache
+    fn write_memory(&mut self, paddr: u32, value: u8) -> Option<()> {
+        self.bus.write_memory(paddr, value)?;
+        self.cpu.invalidate_icache(paddr);
+        Some(())
+    }
+
+    fn set_access_source(&self, source: AccessSource, pc: u32) {
+        self.bus.rdram.watch().set_source(source, pc);
     }
 
     pub fn step(&mut self, receiver: &mut impl AudioReceiver) -> bool {
-        self.cycles += 1;
+        self.bus.cycles += 1;
 
         self.cpu.step(&mut self.bus);
 
-        if (self.cycles & 1) == 0 {
+        if (self.bus.cycles & 1) == 0 {
             self.cpu.step(&mut self.bus);
         }
 
         self.bus.rsp.step_core(self.bus.rdp.shared());
         self.bus.rsp.step_dma(&mut self.bus.rdram);
 
-        self.bus.rdp.step_core(&mut self.bus.rdram, &self.gfx);
+        self.bus
+            .rdp
+            .step_core(&mut self.bus.rdram, self.gfx.as_ref());
         self.bus.rdp.step_dma(&self.bus.rdram, self.bus.rsp.mem());
 
         self.bus.ai.step(&self.bus.rdram, receiver);
-        self.bus.pi.step(&mut self.bus.rdram);
-        self.bus.si.step(&mut self.bus.rdram);
+        self.bus.pi.step(&mut self.bus.rdram, self.bus.cycles);
+        self.bus.si.step(&mut self.bus.rdram, self.bus.cycles);
 
-        self.bus.vi.step(&self.bus.rdram, &self.gfx)
+        self.bus.vi.step(&self.bus.rdram, self.gfx.as_ref())
     }
 }
 
-impl cpu::Bus for Bus {
-    fn read_single<T: Size>(&self, address: u32) -> T {
-        match self.memory_map[address as usize >> 20] {
+impl Bus {
+    // Whether the debugger can safely access the given physical address.
+    // Registers are excluded, as accessing them may have side effects.
+    fn is_memory(&self, address: u32, write: bool) -> bool {
+        match self.memory_map.get(address as usize >> 20) {
+            Some(Mapping::RdramData) => true,
+            Some(Mapping::Rsp) => (address & 0x000f_ffff) < 0x0004_0000,
+            Some(Mapping::CartridgeRom) => !write,
+            _ => false,
+        }
+    }
+
+    // Accesses made by the debugger itself don't trigger watchpoints
+    fn read_memory(&self, address: u32) -> Option<u8> {
+        let hit = self.rdram.watch().take_hit();
+
+        let value = self
+            .is_memory(address, false)
+            .then(|| cpu::Bus::read_single(self, address));
+
+        self.rdram.watch().restore_hit(hit);
+        value
+    }
+
+    fn write_memory(&mut self, address: u32, value: u8) -> Option<()> {
+        let hit = self.rdram.watch().take_hit();
+
+        let result = self
+            .is_memory(address, true)
+            .then(|| cpu::Bus::write_single(self, address, value));
+
+        self.rdram.watch().restore_hit(hit);
+        result
+    }
+
+    fn read_mapped<T: Size>(&self, mapping: Mapping, address: u32) -> T {
+        match mapping {
             Mapping::RdramData => self.rdram.read_single(address as usize),
             Mapping::RdramRegister => self.rdram.read_register(&self.mi, address & 0x000f_ffff),
             Mapping::Rsp => self.rsp.read(address & 0x000f_ffff),
@@ -240,7 +758,11 @@ impl cpu::Bus for Bus {
             Mapping::RdramInterface => self.rdram.read_interface(address & 0x000f_ffff),
             Mapping::SerialInterface => self.si.read(address & 0x000f_ffff),
             Mapping::DDRegisters => T::max_value(),
-            Mapping::CartridgeRom => self.pi.read_rom(address & 0x0fff_ffff),
+            Mapping::CartridgeRam => self.pi.read_ram(address & 0x07ff_ffff, self.cycles),
+            Mapping::CartridgeRom => match address {
+                isviewer::BASE_ADDRESS..=0x13ff_ffff => self.isviewer.read(address & 0xffff),
+                _ => self.pi.read_rom(address & 0x0fff_ffff),
+            },
             Mapping::Pif => self.si.read_pif(address & 0x000f_ffff),
             Mapping::None => {
                 warn!("Unmapped read: {:08X}", address);
@@ -249,8 +771,8 @@ impl cpu::Bus for Bus {
         }
     }
 
-    fn write_single<T: Size>(&mut self, address: u32, value: T) {
-        match self.memory_map[address as usize >> 20] {
+    fn write_mapped<T: Size>(&mut self, mapping: Mapping, address: u32, value: T) {
+        match mapping {
             Mapping::RdramData => self.rdram.write_single(address as usize, value),
             Mapping::RdramRegister => {
                 self.rdram
@@ -266,21 +788,47 @@ impl cpu::Bus for Bus {
             Mapping::RdramInterface => self.rdram.write_interface(address & 0x000f_ffff, value),
             Mapping::SerialInterface => self.si.write(address & 0x000f_ffff, value),
             Mapping::DDRegisters => (), // Ignore
+            Mapping::CartridgeRam => self.pi.write_ram(address & 0x07ff_ffff, value, self.cycles),
             Mapping::CartridgeRom => match address {
-                0x13ff_0020..=0x13ff_0220 => {
-                    self.systest_buffer
-                        .write(address as usize - 0x13ff_0020, value);
+                isviewer::BASE_ADDRESS..=0x13ff_ffff => {
+                    let frame = self.vi.frame_counter();
+                    self.isviewer
+                        .write(address & 0xffff, value, frame, self.cycles);
                 }
-                0x13ff_0014 => println!(
-                    "{}",
-                    String::from_utf8_lossy(&self.systest_buffer[0..value.to_usize().unwrap()])
-                ),
                 _ => warn!("Write to Cartridge ROM: {:08X}", address),
             },
             Mapping::Pif => self.si.write_pif(address & 0x000f_ffff, value),
             Mapping::None => warn!("Unmapped write: {:08X}", address),
         }
     }
+}
+
+impl cpu::Bus for Bus {
+    fn read_single<T: Size>(&self, address: u32) -> T {
+        let mapping = self.memory_map[address as usize >> 20];
+        let value = self.read_mapped(mapping, address);
+
+        // RDRAM checks its own watchpoints
+        if mapping != Mapping::RdramData && self.rdram.watch().is_active() {
+            self.rdram
+                .watch()
+                .check(address, bytemuck::bytes_of(&T::to_be(value)), false);
+        }
+
+        value
+    }
+
+    fn write_single<T: Size>(&mut self, address: u32, value: T) {
+        let mapping = self.memory_map[address as usize >> 20];
+
+        if mapping != Mapping::RdramData && self.rdram.watch().is_active() {
+            self.rdram
+                .watch()
+                .check(address, bytemuck::bytes_of(&T::to_be(value)), true);
+        }
+
+        self.write_mapped(mapping, address, value);
+    }
 
     fn read_block<T: Size>(&self, address: u32, data: &mut [T]) {
         if self.memory_map[address as usize >> 20] != Mapping::RdramData {
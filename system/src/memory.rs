@@ -23,6 +23,7 @@ pub enum Mapping {
     RdramInterface,
     SerialInterface,
     DDRegisters,
+    CartridgeRam,
     CartridgeRom,
     Pif,
 }
@@ -1,6 +1,8 @@
 use crate::interrupt::{RcpIntType, RcpInterrupt};
 use crate::memory::{Size, WriteMask};
+use crate::snapshot::{Reader, Writer};
 use regs::{Mode, Regs};
+use std::error::Error;
 use tracing::debug;
 
 mod regs;
@@ -18,6 +20,19 @@ impl MipsInterface {
         }
     }
 
+    // The MI owns the RCP interrupt status and mask, so they are saved here
+    pub fn save_state(&self, writer: &mut Writer) {
+        writer.tag(b"MI  ");
+        writer.reg(self.regs.mode);
+        self.rcp_int.save_state(writer);
+    }
+
+    pub fn load_state(&mut self, reader: &mut Reader) -> Result<(), Box<dyn Error>> {
+        reader.tag(b"MI  ")?;
+        self.regs.mode = reader.reg()?;
+        self.rcp_int.load_state(reader)
+    }
+
     pub fn is_upper(&self) -> bool {
         self.regs.mode.upper()
     }
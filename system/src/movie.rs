@@ -0,0 +1,328 @@
+use crate::snapshot::{Reader, Writer};
+use std::error::Error;
+use tracing::warn;
+
+const MAGIC: &[u8; 8] = b"REALMOV\x1a";
+
+const VERSION: u32 = 2;
+
+pub enum MovieMode {
+    Record,
+    Playback(Vec<u8>),
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum PlaybackStatus {
+    Playing,
+    Finished,
+    // Emulation no longer matches the recording, as of the given frame
+    Desynced(u64),
+}
+
+// Startup options that affect emulation. Playback requires these to match
+// the options used when the movie was recorded.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Settings {
+    pub rom_hash: u32,
+    pub pif_hash: Option<u32>,
+    pub granularity: u64,
+    pub rdp_backend: u8,
+    // Resolved from the ROM header, game settings and command line
+    pub save_type: u8,
+    pub cic_type: u8,
+    pub accessories: [u8; 4],
+    pub expansion_pak: bool,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+struct FrameCheck {
+    polls: u64,
+    checksum: u64,
+}
+
+// Input is recorded once per Joybus poll rather than once per frame, as games
+// do not necessarily poll controllers exactly once per frame. The poll count
+// and a CPU register checksum are recorded at the end of each frame so that
+// playback can detect when it has drifted out of sync.
+pub struct Movie {
+    settings: Settings,
+    // Time the RTC started from, so that playback sees the same time
+    rtc_start: i64,
+    playback: bool,
+    polls: Vec<[[u8; 4]; 4]>,
+    frames: Vec<FrameCheck>,
+    poll_index: usize,
+    frame_index: usize,
+    status: PlaybackStatus,
+}
+
+impl Movie {
+    pub fn record(settings: Settings, rtc_start: i64) -> Self {
+        Self {
+            settings,
+            rtc_start,
+            playback: false,
+            polls: Vec::new(),
+            frames: Vec::new(),
+            poll_index: 0,
+            frame_index: 0,
+            status: PlaybackStatus::Playing,
+        }
+    }
+
+    pub fn play(settings: Settings, data: &[u8]) -> Result<Self, Box<dyn Error>> {
+        let mut reader = Reader::with_header(data, MAGIC, VERSION, "movie")?;
+
+        reader.tag(b"HEAD")?;
+
+        if reader.u32()? != settings.rom_hash {
+            return Err("Movie was recorded with a different ROM".into());
+        }
+
+        let pif_hash = if reader.bool()? {
+            Some(reader.u32()?)
+        } else {
+            None
+        };
+
+        if pif_hash != settings.pif_hash {
+            return Err("Movie was recorded with a different PIF ROM setting".into());
+        }
+
+        let granularity = reader.u64()?;
+
+        if granularity != settings.granularity {
+            return Err(format!("Movie was recorded with a granularity of {}", granularity).into());
+        }
+
+        if reader.u8()? != settings.rdp_backend {
+            return Err("Movie was recorded with a different RDP backend".into());
+        }
+
+        if reader.u8()? != settings.save_type {
+            return Err("Movie was recorded with a different save type".into());
+        }
+
+        if reader.u8()? != settings.cic_type {
+            return Err("Movie was recorded with a different CIC type".into());
+        }
+
+        for &accessory in &settings.accessories {
+            if reader.u8()? != accessory {
+                return Err("Movie was recorded with different controller accessories".into());
+            }
+        }
+
+        if reader.bool()? != settings.expansion_pak {
+            return Err("Movie was recorded with a different Expansion Pak setting".into());
+        }
+
+        let rtc_start = reader.u64()? as i64;
+
+        reader.tag(b"POLL")?;
+        let poll_count = reader.u32()?;
+        let mut polls = Vec::with_capacity(poll_count as usize);
+
+        for _ in 0..poll_count {
+            let mut joypads = [[0; 4]; 4];
+
+            for joypad in &mut joypads {
+                *joypad = reader.u32()?.to_le_bytes();
+            }
+
+            polls.push(joypads);
+        }
+
+        reader.tag(b"FRAM")?;
+        let frame_count = reader.u32()?;
+        let mut frames = Vec::with_capacity(frame_count as usize);
+
+        for _ in 0..frame_count {
+            frames.push(FrameCheck {
+                polls: reader.u64()?,
+                checksum: reader.u64()?,
+            });
+        }
+
+        reader.finish()?;
+
+        Ok(Self {
+            settings,
+            rtc_start,
+            playback: true,
+            polls,
+            frames,
+            poll_index: 0,
+            frame_index: 0,
+            status: PlaybackStatus::Playing,
+        })
+    }
+
+    pub fn encode(&self) -> Vec<u8> {
+        let mut writer = Writer::with_header(MAGIC, VERSION);
+
+        writer.tag(b"HEAD");
+        writer.u32(self.settings.rom_hash);
+        writer.bool(self.settings.pif_hash.is_some());
+
+        if let Some(pif_hash) = self.settings.pif_hash {
+            writer.u32(pif_hash);
+        }
+
+        writer.u64(self.settings.granularity);
+        writer.u8(self.settings.rdp_backend);
+        writer.u8(self.settings.save_type);
+        writer.u8(self.settings.cic_type);
+
+        for &accessory in &self.settings.accessories {
+            writer.u8(accessory);
+        }
+
+        writer.bool(self.settings.expansion_pak);
+        writer.u64(self.rtc_start as u64);
+
+        writer.tag(b"POLL");
+        writer.u32(self.polls.len() as u32);
+
+        for joypads in &self.polls {
+            for joypad in joypads {
+                writer.u32(u32::from_le_bytes(*joypad));
+            }
+        }
+
+        writer.tag(b"FRAM");
+        writer.u32(self.frames.len() as u32);
+
+        for frame in &self.frames {
+            writer.u64(frame.polls);
+            writer.u64(frame.checksum);
+        }
+
+        writer.finish()
+    }
+
+    pub fn rtc_start(&self) -> i64 {
+        self.rtc_start
+    }
+
+    pub fn is_playback(&self) -> bool {
+        self.playback
+    }
+
+    pub fn status(&self) -> PlaybackStatus {
+        self.status
+    }
+
+    // Called each time the Joybus is executed. When recording, stores the
+    // current controller state. When playing back, replaces it.
+    pub fn poll(&mut self, joypads: &mut [[u8; 4]; 4]) {
+        if !self.playback {
+            self.polls.push(*joypads);
+            self.poll_index += 1;
+            return;
+        }
+
+        if let Some(recorded) = self.polls.get(self.poll_index) {
+            *joypads = *recorded;
+        }
+
+        self.poll_index += 1;
+    }
+
+    pub fn end_frame(&mut self, checksum: u64) {
+        let check = FrameCheck {
+            polls: self.poll_index as u64,
+            checksum,
+        };
+
+        if !self.playback {
+            self.frames.push(check);
+            return;
+        }
+
+        if self.status != PlaybackStatus::Playing {
+            return;
+        }
+
+        match self.frames.get(self.frame_index) {
+            Some(expected) if *expected == check => (),
+            Some(expected) => {
+                warn!(
+                    "Movie desynced at frame {}: expected {:X?}, got {:X?}",
+                    self.frame_index, expected, check
+                );
+
+                self.status = PlaybackStatus::Desynced(self.frame_index as u64);
+            }
+            None => self.status = PlaybackStatus::Finished,
+        }
+
+        self.frame_index += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings() -> Settings {
+        Settings {
+            rom_hash: 0x1234_5678,
+            pif_hash: None,
+            granularity: 6250,
+            rdp_backend: 0,
+            save_type: 1,
+            cic_type: 2,
+            accessories: [1, 0, 0, 0],
+            expansion_pak: true,
+        }
+    }
+
+    #[test]
+    fn playback_matches_recording() {
+        let mut movie = Movie::record(settings(), 1_000_000_000);
+        let mut joypads = [[0; 4]; 4];
+
+        for frame in 0..4u8 {
+            joypads[0][0] = frame;
+            movie.poll(&mut joypads);
+            movie.end_frame(frame as u64);
+        }
+
+        let data = movie.encode();
+        let mut movie = Movie::play(settings(), &data).unwrap();
+        assert_eq!(movie.rtc_start(), 1_000_000_000);
+
+        for frame in 0..4u8 {
+            joypads[0][0] = 0xff;
+            movie.poll(&mut joypads);
+            assert_eq!(joypads[0][0], frame);
+            movie.end_frame(frame as u64);
+            assert_eq!(movie.status(), PlaybackStatus::Playing);
+        }
+
+        movie.end_frame(0);
+        assert_eq!(movie.status(), PlaybackStatus::Finished);
+
+        let mut movie = Movie::play(settings(), &data).unwrap();
+        movie.poll(&mut joypads);
+        movie.end_frame(1);
+        assert_eq!(movie.status(), PlaybackStatus::Desynced(0));
+
+        let mut other = settings();
+        other.granularity = 0;
+        assert!(Movie::play(other, &data).is_err());
+
+        let mut other = settings();
+        other.save_type = 0;
+        assert!(Movie::play(other, &data).is_err());
+
+        let mut other = settings();
+        other.accessories[1] = 1;
+        assert!(Movie::play(other, &data).is_err());
+
+        let mut other = settings();
+        other.expansion_pak = false;
+        assert!(Movie::play(other, &data).is_err());
+    }
+}
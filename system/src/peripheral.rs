@@ -1,10 +1,28 @@
+use crate::header::SaveType;
 use crate::interrupt::{RcpIntType, RcpInterrupt};
 use crate::memory::{Memory, Size, WriteMask};
 use crate::rdram::Rdram;
+use crate::save;
+use crate::snapshot::{Reader, Writer};
+use flash::Flash;
 use regs::Regs;
+use sram::Sram;
+use std::error::Error;
+use std::io;
+use std::path::Path;
 use tracing::{debug, warn};
 
+mod flash;
 mod regs;
+mod sram;
+
+// Cartridge save memory is mapped on domain 2 from here
+const CART_RAM_BASE: usize = 0x0800_0000;
+
+enum CartRam {
+    Sram(Sram),
+    Flash(Flash),
+}
 
 struct Dma {
     len: u32,
@@ -14,12 +32,19 @@ struct Dma {
 pub struct PeripheralInterface {
     regs: Regs,
     rom: Memory<u64>,
+    ram: Option<CartRam>,
     dma: Option<Dma>,
     rcp_int: RcpInterrupt,
 }
 
 impl PeripheralInterface {
-    pub fn new(rcp_int: RcpInterrupt, mut rom_data: Vec<u8>, skip_pif_rom: bool) -> Self {
+    pub fn new(
+        rcp_int: RcpInterrupt,
+        mut rom_data: Vec<u8>,
+        skip_pif_rom: bool,
+        save_type: SaveType,
+        save_path: Option<&Path>,
+    ) -> io::Result<Self> {
         // Ensure ROM length is a multiple of 8
         // TODO: Make it a multiple of memory map entry size and adjust memory map accordingly
         rom_data.resize((rom_data.len() + 7) & !7, 0);
@@ -33,24 +58,129 @@ impl PeripheralInterface {
             regs.bsd_dom[0].rls.set_rls(rom_data[1] as u32 >> 4);
         }
 
-        Self {
+        let ram = match save_type {
+            SaveType::Sram256K => Some(CartRam::Sram(Sram::new(
+                1,
+                save::save_path(save_path, ".sra"),
+            )?)),
+            SaveType::Sram768K => Some(CartRam::Sram(Sram::new(
+                3,
+                save::save_path(save_path, ".sra"),
+            )?)),
+            SaveType::Sram1M => Some(CartRam::Sram(Sram::new(
+                4,
+                save::save_path(save_path, ".sra"),
+            )?)),
+            SaveType::FlashRam => Some(CartRam::Flash(Flash::new(save::save_path(
+                save_path, ".fla",
+            ))?)),
+            _ => None,
+        };
+
+        Ok(Self {
             regs,
             rom: Memory::from_bytes(&rom_data),
+            ram,
             dma: None,
             rcp_int,
+        })
+    }
+
+    // Identifies the ROM a save state was taken with (the header CRCs)
+    pub fn rom_id(&self) -> u64 {
+        self.rom.read(0x10)
+    }
+
+    // Cartridge ROM contents are not saved, as they are (almost) never written
+    pub fn save_state(&self, writer: &mut Writer) {
+        writer.tag(b"PI  ");
+        writer.u32(self.regs.dram_addr);
+        writer.u32(self.regs.cart_addr);
+
+        for dom in &self.regs.bsd_dom {
+            writer.reg(dom.lat);
+            writer.reg(dom.pwd);
+            writer.reg(dom.pgs);
+            writer.reg(dom.rls);
+        }
+
+        writer.bool(self.dma.is_some());
+
+        if let Some(dma) = &self.dma {
+            writer.u32(dma.len);
+            writer.bool(dma.write);
+        }
+
+        match &self.ram {
+            Some(CartRam::Sram(sram)) => {
+                writer.u8(1);
+                sram.save_state(writer);
+            }
+            Some(CartRam::Flash(flash)) => {
+                writer.u8(2);
+                flash.save_state(writer);
+            }
+            None => writer.u8(0),
+        }
+    }
+
+    pub fn load_state(&mut self, reader: &mut Reader) -> Result<(), Box<dyn Error>> {
+        reader.tag(b"PI  ")?;
+        self.regs.dram_addr = reader.u32()?;
+        self.regs.cart_addr = reader.u32()?;
+
+        for dom in &mut self.regs.bsd_dom {
+            dom.lat = reader.reg()?;
+            dom.pwd = reader.reg()?;
+            dom.pgs = reader.reg()?;
+            dom.rls = reader.reg()?;
+        }
+
+        self.dma = if reader.bool()? {
+            Some(Dma {
+                len: reader.u32()?,
+                write: reader.bool()?,
+            })
+        } else {
+            None
+        };
+
+        match (reader.u8()?, &mut self.ram) {
+            (1, Some(CartRam::Sram(sram))) => sram.load_state(reader)?,
+            (2, Some(CartRam::Flash(flash))) => flash.load_state(reader)?,
+            (0, None) => (),
+            _ => return Err("Save state has a different save type".into()),
+        }
+
+        Ok(())
+    }
+
+    pub fn tick_saves(&mut self) {
+        match &mut self.ram {
+            Some(CartRam::Sram(sram)) => sram.tick(),
+            Some(CartRam::Flash(flash)) => flash.tick(),
+            None => (),
+        }
+    }
+
+    pub fn flush_saves(&mut self) {
+        match &mut self.ram {
+            Some(CartRam::Sram(sram)) => sram.flush(),
+            Some(CartRam::Flash(flash)) => flash.flush(),
+            None => (),
         }
     }
 
     #[inline(always)]
-    pub fn step(&mut self, rdram: &mut Rdram) {
+    pub fn step(&mut self, rdram: &mut Rdram, cycles: u64) {
         if self.dma.is_none() {
             return;
         }
 
-        self.step_inner(rdram);
+        self.step_inner(rdram, cycles);
     }
 
-    fn step_inner(&mut self, rdram: &mut Rdram) {
+    fn step_inner(&mut self, rdram: &mut Rdram, cycles: u64) {
         let dma = self.dma.as_mut().unwrap();
 
         let dram_addr = self.regs.dram_addr as usize & 0x00ff_fffe;
@@ -72,8 +202,29 @@ impl PeripheralInterface {
                     &mut self.rom[cart_addr..(cart_addr + block_len as usize)],
                 );
             }
+        } else if cart_addr >= CART_RAM_BASE && self.ram.is_some() {
+            // DMA to/from cartridge SRAM or FlashRAM
+            let cart_addr = (cart_addr - CART_RAM_BASE) as u32;
+            let mut buf = [0u8; 128];
+            let buf = &mut buf[0..block_len as usize];
+
+            if dma.write {
+                match self.ram.as_ref().unwrap() {
+                    CartRam::Sram(sram) => sram.read_block(cart_addr, buf),
+                    CartRam::Flash(flash) => flash.read_block(cart_addr, buf, cycles),
+                }
+
+                rdram.write_block(dram_addr, buf);
+            } else {
+                rdram.read_block(dram_addr, buf);
+
+                match self.ram.as_mut().unwrap() {
+                    CartRam::Sram(sram) => sram.write_block(cart_addr, buf),
+                    CartRam::Flash(flash) => flash.write_block(cart_addr, buf),
+                }
+            }
         } else {
-            // DMA to/from 64DD area or Flash RAM
+            // DMA to/from 64DD area or absent cartridge RAM
             // Just write zeroes and ignore reads
             if dma.write {
                 let buf: [u8; 128] = [0; 128];
@@ -184,4 +335,23 @@ impl PeripheralInterface {
             T::zeroed()
         }
     }
+
+    pub fn read_ram<T: Size>(&self, address: u32, cycles: u64) -> T {
+        match &self.ram {
+            Some(CartRam::Sram(sram)) => sram.read(address),
+            Some(CartRam::Flash(flash)) => flash.read(address, cycles),
+            None => {
+                warn!("Unmapped Cartridge RAM Read: {:08X}", address);
+                T::zeroed()
+            }
+        }
+    }
+
+    pub fn write_ram<T: Size>(&mut self, address: u32, value: T, cycles: u64) {
+        match &mut self.ram {
+            Some(CartRam::Sram(sram)) => sram.write(address, value),
+            Some(CartRam::Flash(flash)) => flash.write(address, value, cycles),
+            None => warn!("Unmapped Cartridge RAM Write: {:08X}", address),
+        }
+    }
 }
@@ -0,0 +1,290 @@
+use crate::memory::Size;
+use crate::save::SaveFile;
+use crate::snapshot::{Reader, Writer};
+use std::error::Error;
+use std::io;
+use std::path::PathBuf;
+use tracing::{debug, warn};
+
+const SIZE: usize = 131072;
+const PAGE_SIZE: usize = 128;
+const SECTOR_SIZE: usize = 16384;
+
+// Offset of the command register from the start of the flash
+pub const COMMAND_REG: u32 = 0x0001_0000;
+
+// Macronix MX29L1100
+const SILICON_ID: [u8; 8] = [0x11, 0x11, 0x80, 0x01, 0x00, 0xc2, 0x00, 0x1e];
+
+const STATUS_WRITE_BUSY: u8 = 0x01;
+const STATUS_ERASE_BUSY: u8 = 0x02;
+const STATUS_WRITE_OK: u8 = 0x04;
+const STATUS_ERASE_OK: u8 = 0x08;
+
+// How long operations take, in RCP cycles
+const PROGRAM_TIME: u64 = 62_500;
+const SECTOR_ERASE_TIME: u64 = 625_000;
+const CHIP_ERASE_TIME: u64 = 3_125_000;
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+enum Mode {
+    ReadArray,
+    Status,
+    Id,
+    PageBuffer,
+}
+
+pub struct Flash {
+    save: SaveFile,
+    mode: Mode,
+    status: u8,
+    page_buffer: [u8; PAGE_SIZE],
+    // Area selected for erasing by the last erase command
+    erase_area: Option<(usize, usize)>,
+    // Cycle at which the current program or erase finishes
+    busy_until: u64,
+}
+
+impl Flash {
+    pub fn new(path: Option<PathBuf>) -> io::Result<Self> {
+        Ok(Self {
+            save: SaveFile::open(path, SIZE, 0xff)?,
+            mode: Mode::ReadArray,
+            status: 0,
+            page_buffer: [0xff; PAGE_SIZE],
+            erase_area: None,
+            busy_until: 0,
+        })
+    }
+
+    pub fn save_state(&self, writer: &mut Writer) {
+        self.save.save_state(writer);
+        writer.u8(self.mode as u8);
+        writer.u8(self.status);
+        writer.bytes(&self.page_buffer);
+        writer.bool(self.erase_area.is_some());
+
+        if let Some((offset, len)) = self.erase_area {
+            writer.u32(offset as u32);
+            writer.u32(len as u32);
+        }
+
+        writer.u64(self.busy_until);
+    }
+
+    pub fn load_state(&mut self, reader: &mut Reader) -> Result<(), Box<dyn Error>> {
+        self.save.load_state(reader)?;
+
+        self.mode = match reader.u8()? {
+            0 => Mode::ReadArray,
+            1 => Mode::Status,
+            2 => Mode::Id,
+            3 => Mode::PageBuffer,
+            mode => return Err(format!("Unknown FlashRAM mode: {}", mode).into()),
+        };
+
+        self.status = reader.u8()?;
+        reader.bytes_into(&mut self.page_buffer)?;
+
+        self.erase_area = if reader.bool()? {
+            let offset = reader.u32()? as usize;
+            let len = reader.u32()? as usize;
+
+            if offset + len > SIZE {
+                return Err("FlashRAM erase area is out of range".into());
+            }
+
+            Some((offset, len))
+        } else {
+            None
+        };
+
+        self.busy_until = reader.u64()?;
+        Ok(())
+    }
+
+    pub fn tick(&mut self) {
+        self.save.tick();
+    }
+
+    pub fn flush(&mut self) {
+        self.save.flush();
+    }
+
+    pub fn read<T: Size>(&self, address: u32, cycles: u64) -> T {
+        if address != 0 {
+            warn!("Unexpected FlashRAM read: {:08X}", address);
+            return T::zeroed();
+        }
+
+        T::truncate_u32(self.status(cycles) as u32)
+    }
+
+    pub fn write<T: Size>(&mut self, address: u32, value: T, cycles: u64) {
+        let value = value.to_u32().unwrap();
+
+        match address {
+            // Writing to the status register clears it
+            0 => self.status = 0,
+            COMMAND_REG => self.command(value, cycles),
+            _ => warn!(
+                "Unexpected FlashRAM write: {:08X} <= {:08X}",
+                address, value
+            ),
+        }
+    }
+
+    // PI DMA from flash to RDRAM
+    pub fn read_block(&self, address: u32, data: &mut [u8], cycles: u64) {
+        match self.mode {
+            Mode::ReadArray => {
+                // The flash has a 16-bit bus, so PI addresses are halved
+                let offset = ((address as usize & 0xffff) * 2) % SIZE;
+                let len = data.len().min(SIZE - offset);
+                data[0..len].copy_from_slice(&self.save.data()[offset..(offset + len)]);
+            }
+            Mode::Status => data.fill(self.status(cycles)),
+            Mode::Id => {
+                for (index, byte) in data.iter_mut().enumerate() {
+                    *byte = SILICON_ID[index % SILICON_ID.len()];
+                }
+            }
+            Mode::PageBuffer => warn!("FlashRAM read while loading the page buffer"),
+        }
+    }
+
+    // PI DMA from RDRAM to flash
+    pub fn write_block(&mut self, address: u32, data: &[u8]) {
+        if self.mode != Mode::PageBuffer {
+            warn!("FlashRAM write in {:?} mode ignored", self.mode);
+            return;
+        }
+
+        for (index, &byte) in data.iter().enumerate() {
+            self.page_buffer[(address as usize + index) % PAGE_SIZE] = byte;
+        }
+    }
+
+    fn status(&self, cycles: u64) -> u8 {
+        if cycles < self.busy_until {
+            return self.status;
+        }
+
+        // Operation complete: busy flags become success flags
+        let mut status = self.status & !(STATUS_WRITE_BUSY | STATUS_ERASE_BUSY);
+
+        if (self.status & STATUS_WRITE_BUSY) != 0 {
+            status |= STATUS_WRITE_OK;
+        }
+
+        if (self.status & STATUS_ERASE_BUSY) != 0 {
+            status |= STATUS_ERASE_OK;
+        }
+
+        status
+    }
+
+    fn command(&mut self, value: u32, cycles: u64) {
+        debug!("FlashRAM Command: {:08X}", value);
+
+        if cycles < self.busy_until {
+            warn!("FlashRAM command {:08X} while busy", value);
+        }
+
+        let page = value as usize & 0x03ff;
+
+        match value >> 24 {
+            // Chip erase (select)
+            0x3c => self.erase_area = Some((0, SIZE)),
+            // Sector erase (select)
+            0x4b => {
+                let offset = (page * PAGE_SIZE) & !(SECTOR_SIZE - 1);
+                self.erase_area = Some((offset, SECTOR_SIZE));
+            }
+            // Execute erase
+            0x78 => {
+                let Some((offset, len)) = self.erase_area.take() else {
+                    warn!("FlashRAM erase executed without selecting an area");
+                    return;
+                };
+
+                self.save.data_mut()[offset..(offset + len)].fill(0xff);
+                self.status = STATUS_ERASE_BUSY;
+
+                self.busy_until = cycles
+                    + if len == SIZE {
+                        CHIP_ERASE_TIME
+                    } else {
+                        SECTOR_ERASE_TIME
+                    };
+            }
+            // Program page
+            0xa5 => {
+                let offset = page * PAGE_SIZE;
+                self.save.data_mut()[offset..(offset + PAGE_SIZE)]
+                    .copy_from_slice(&self.page_buffer);
+                self.status = STATUS_WRITE_BUSY;
+                self.busy_until = cycles + PROGRAM_TIME;
+            }
+            // Load page buffer
+            0xb4 => self.mode = Mode::PageBuffer,
+            0xd2 => self.mode = Mode::Status,
+            0xe1 => self.mode = Mode::Id,
+            0xf0 => self.mode = Mode::ReadArray,
+            _ => warn!("Unknown FlashRAM command: {:08X}", value),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn programs_pages_after_erasing() {
+        let mut flash = Flash::new(None).unwrap();
+
+        flash.write(COMMAND_REG, 0x4b00_0085u32, 0);
+        flash.write(COMMAND_REG, 0x7800_0000u32, 0);
+        flash.write(COMMAND_REG, 0xd200_0000u32, 0);
+        assert_eq!(flash.read::<u32>(0, 1), STATUS_ERASE_BUSY as u32);
+        assert_eq!(
+            flash.read::<u32>(0, SECTOR_ERASE_TIME),
+            STATUS_ERASE_OK as u32
+        );
+
+        flash.write(0, 0u32, SECTOR_ERASE_TIME);
+        flash.write(COMMAND_REG, 0xb400_0000u32, SECTOR_ERASE_TIME);
+        flash.write_block(0, &[0x5a; PAGE_SIZE]);
+        flash.write(COMMAND_REG, 0xa500_0085u32, SECTOR_ERASE_TIME);
+        assert_eq!(flash.status(SECTOR_ERASE_TIME), STATUS_WRITE_BUSY);
+
+        flash.write(COMMAND_REG, 0xf000_0000u32, SECTOR_ERASE_TIME);
+        let mut data = [0; 4];
+        flash.read_block(0x85 * PAGE_SIZE as u32 / 2 + 0x3e, &mut data, u64::MAX);
+        assert_eq!(data, [0x5a, 0x5a, 0x5a, 0x5a]);
+        flash.read_block(0x86 * PAGE_SIZE as u32 / 2, &mut data, u64::MAX);
+        assert_eq!(data, [0xff; 4]);
+    }
+
+    #[test]
+    fn restores_state_mid_operation() {
+        let mut flash = Flash::new(None).unwrap();
+        flash.write(COMMAND_REG, 0x3c00_0000u32, 0);
+        flash.write(COMMAND_REG, 0xb400_0000u32, 0);
+        flash.write_block(0, &[0x5a; 4]);
+
+        let mut writer = Writer::new();
+        flash.save_state(&mut writer);
+        let data = writer.finish();
+
+        let mut restored = Flash::new(None).unwrap();
+        let mut reader = Reader::new(&data).unwrap();
+        restored.load_state(&mut reader).unwrap();
+        reader.finish().unwrap();
+
+        assert_eq!(restored.mode, Mode::PageBuffer);
+        assert_eq!(restored.erase_area, Some((0, SIZE)));
+        assert_eq!(restored.page_buffer[0..5], [0x5a, 0x5a, 0x5a, 0x5a, 0xff]);
+    }
+}
@@ -0,0 +1,90 @@
+use crate::memory::Size;
+use crate::save::SaveFile;
+use crate::snapshot::{Reader, Writer};
+use std::error::Error;
+use std::io;
+use std::mem;
+use std::path::PathBuf;
+
+const BANK_SIZE: usize = 32768;
+
+pub struct Sram {
+    save: SaveFile,
+}
+
+impl Sram {
+    pub fn new(banks: usize, path: Option<PathBuf>) -> io::Result<Self> {
+        Ok(Self {
+            save: SaveFile::open(path, banks * BANK_SIZE, 0xff)?,
+        })
+    }
+
+    pub fn save_state(&self, writer: &mut Writer) {
+        self.save.save_state(writer);
+    }
+
+    pub fn load_state(&mut self, reader: &mut Reader) -> Result<(), Box<dyn Error>> {
+        self.save.load_state(reader)
+    }
+
+    pub fn tick(&mut self) {
+        self.save.tick();
+    }
+
+    pub fn flush(&mut self) {
+        self.save.flush();
+    }
+
+    pub fn read<T: Size>(&self, address: u32) -> T {
+        let offset = self.offset(address);
+        let data = &self.save.data()[offset..(offset + mem::size_of::<T>())];
+        T::from_be(bytemuck::pod_read_unaligned(data))
+    }
+
+    pub fn write<T: Size>(&mut self, address: u32, value: T) {
+        let offset = self.offset(address);
+        let data = &mut self.save.data_mut()[offset..(offset + mem::size_of::<T>())];
+        data.copy_from_slice(bytemuck::bytes_of(&value.to_be()));
+    }
+
+    pub fn read_block(&self, address: u32, data: &mut [u8]) {
+        for (index, byte) in data.iter_mut().enumerate() {
+            *byte = self.save.data()[self.offset(address + index as u32)];
+        }
+    }
+
+    pub fn write_block(&mut self, address: u32, data: &[u8]) {
+        for (index, &byte) in data.iter().enumerate() {
+            let offset = self.offset(address + index as u32);
+            self.save.data_mut()[offset] = byte;
+        }
+    }
+
+    // Banks are 256 KiB apart in the address space, but only 32 KiB in size.
+    // Banks that aren't present mirror those that are.
+    fn offset(&self, address: u32) -> usize {
+        let bank = (address as usize >> 18) & 3;
+        (bank * BANK_SIZE + (address as usize & (BANK_SIZE - 1))) % self.save.data().len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn selects_banks_by_address() {
+        let mut sram = Sram::new(3, None).unwrap();
+        sram.write(0x0008_0010, 0x1234_5678u32);
+        assert_eq!(sram.read::<u32>(0x0008_0010), 0x1234_5678);
+        assert_eq!(sram.read::<u32>(0x0000_0010), 0xffff_ffff);
+
+        let mut data = [0; 4];
+        sram.read_block(0x0008_0012, &mut data);
+        assert_eq!(data, [0x56, 0x78, 0xff, 0xff]);
+
+        // There's no fourth bank, so it mirrors the first
+        sram.write_block(0x000c_0000, &[0xab]);
+        assert_eq!(sram.read::<u8>(0x0000_0000), 0xab);
+    }
+}
@@ -2,14 +2,24 @@ use crate::gfx::GfxContext;
 use crate::interrupt::{RcpIntType, RcpInterrupt};
 use crate::memory::{Memory, Size, WriteMask};
 use crate::rdram::Rdram;
+use crate::snapshot::{Reader, Writer};
+use backend::Backend;
+use capture::FrameCapture;
 use decoder::{Context, Decoder};
 use regs::{Regs, Status};
-use renderer::Renderer;
+use std::error::Error;
 use tracing::{debug, error_span};
 
+pub use backend::RenderBackend;
+pub use capture::{replay, Image};
+pub use decoder::disassemble;
+
+mod backend;
+mod capture;
 mod decoder;
 mod regs;
 mod renderer;
+mod software;
 
 #[derive(Debug)]
 struct Dma {
@@ -17,44 +27,150 @@ struct Dma {
     end: u32,
 }
 
+impl Dma {
+    fn save_state(&self, writer: &mut Writer) {
+        writer.u32(self.start);
+        writer.u32(self.end);
+    }
+
+    fn load_state(reader: &mut Reader) -> Result<Self, Box<dyn Error>> {
+        Ok(Self {
+            start: reader.u32()?,
+            end: reader.u32()?,
+        })
+    }
+}
+
 pub struct RdpShared {
     regs: Regs,
     dma_active: Dma,
     dma_pending: Option<Dma>,
+    // Set when DP_END is written, so that frame captures can record what has
+    // changed in RDRAM before the new commands run
+    end_written: bool,
 }
 
 pub struct Rdp {
     shared: RdpShared,
     decoder: Decoder,
-    renderer: Renderer,
+    renderer: Backend,
+    capture: FrameCapture,
     rcp_int: RcpInterrupt,
 }
 
 impl Rdp {
-    pub fn new(rcp_int: RcpInterrupt, gfx: &GfxContext) -> Self {
-        Self {
+    pub fn new(
+        rcp_int: RcpInterrupt,
+        gfx: Option<&GfxContext>,
+        backend: RenderBackend,
+    ) -> Result<Self, Box<dyn Error>> {
+        Ok(Self {
             shared: RdpShared {
                 regs: Regs::default(),
                 dma_active: Dma { start: 0, end: 0 },
                 dma_pending: None,
+                end_written: false,
             },
             decoder: Decoder::new(),
-            renderer: Renderer::new(gfx),
+            renderer: Backend::new(backend, gfx)?,
+            capture: FrameCapture::default(),
             rcp_int,
+        })
+    }
+
+    // The renderer should be synced beforehand, so that anything it has drawn
+    // is in RDRAM
+    pub fn save_state(&self, writer: &mut Writer) {
+        let shared = &self.shared;
+
+        writer.tag(b"RDP ");
+        writer.u32(shared.regs.start);
+        writer.u32(shared.regs.end);
+        writer.reg(shared.regs.status);
+        writer.u32(shared.regs.clock);
+        shared.dma_active.save_state(writer);
+        writer.bool(shared.dma_pending.is_some());
+
+        if let Some(dma_pending) = &shared.dma_pending {
+            dma_pending.save_state(writer);
         }
+
+        self.decoder.save_state(writer);
+        self.renderer.save_state(writer);
+    }
+
+    // RDRAM must already have been restored, as replaying state commands may
+    // cause the hardware renderer to read from it
+    pub fn load_state(
+        &mut self,
+        reader: &mut Reader,
+        rdram: &mut Rdram,
+        gfx: Option<&GfxContext>,
+    ) -> Result<(), Box<dyn Error>> {
+        let shared = &mut self.shared;
+
+        reader.tag(b"RDP ")?;
+        shared.regs.start = reader.u32()?;
+        shared.regs.end = reader.u32()?;
+        shared.regs.status = reader.reg()?;
+        shared.regs.clock = reader.u32()?;
+        shared.dma_active = Dma::load_state(reader)?;
+
+        shared.dma_pending = if reader.bool()? {
+            Some(Dma::load_state(reader)?)
+        } else {
+            None
+        };
+
+        self.decoder.load_state(reader)?;
+
+        // Rebuild the renderer from scratch, then restore TMEM on top
+        self.renderer = Backend::new(self.renderer.kind(), gfx)?;
+        self.decoder.restore(&mut self.renderer, rdram, gfx);
+        self.renderer.load_state(reader)
     }
 
     pub fn shared(&mut self) -> &mut RdpShared {
         &mut self.shared
     }
 
-    pub fn sync(&mut self, gfx: &GfxContext, rdram: &mut Rdram) {
+    pub fn is_running(&self) -> bool {
+        self.decoder.running()
+    }
+
+    pub fn pending_commands(&self) -> usize {
+        self.decoder.pending_commands()
+    }
+
+    pub fn state_words(&self) -> Vec<u64> {
+        self.decoder.state_words()
+    }
+
+    pub fn start_capture(&mut self) {
+        self.decoder.start_capture();
+    }
+
+    pub fn take_capture(&mut self) -> Option<Vec<u64>> {
+        self.decoder.take_capture()
+    }
+
+    // Records everything needed to replay the RDP's work between the next two
+    // SYNC_FULL commands
+    pub fn capture_frame(&mut self) {
+        self.capture.arm();
+    }
+
+    pub fn take_frame_capture(&mut self) -> Option<Vec<u8>> {
+        self.capture.take()
+    }
+
+    pub fn sync(&mut self, gfx: Option<&GfxContext>, rdram: &mut Rdram) {
         let _span = error_span!("rdp").entered();
         self.renderer.sync(gfx, rdram);
     }
 
     #[inline(always)]
-    pub fn step_core(&mut self, rdram: &mut Rdram, gfx: &GfxContext) {
+    pub fn step_core(&mut self, rdram: &mut Rdram, gfx: Option<&GfxContext>) {
         self.shared.regs.clock = self.shared.regs.clock.wrapping_add(1);
 
         if !self.decoder.running() || self.shared.regs.status.freeze() {
@@ -64,7 +180,7 @@ impl Rdp {
         self.step_core_inner(rdram, gfx);
     }
 
-    fn step_core_inner(&mut self, rdram: &mut Rdram, gfx: &GfxContext) {
+    fn step_core_inner(&mut self, rdram: &mut Rdram, gfx: Option<&GfxContext>) {
         let sync_full = {
             let _span = error_span!("rdp").entered();
 
@@ -77,6 +193,7 @@ impl Rdp {
 
         if sync_full {
             self.sync(gfx, rdram);
+            self.capture.sync_full(&self.decoder, &self.renderer, rdram);
             self.rcp_int.raise(RcpIntType::DP);
 
             let status = &mut self.shared.regs.status;
@@ -96,6 +213,10 @@ impl Rdp {
     }
 
     fn step_dma_inner(&mut self, rdram: &Rdram, rsp_mem: &Memory<u128>) {
+        if std::mem::take(&mut self.shared.end_written) {
+            self.capture.dp_end(rdram);
+        }
+
         let dma = &mut self.shared.dma_active;
 
         assert!((dma.start & 7) == 0);
@@ -108,6 +229,7 @@ impl Rdp {
             for _ in 0..block_len {
                 let command: u64 = rsp_mem.read(current as usize & 0xfff);
                 self.decoder.write_command(command);
+                self.capture.record(command);
                 current = current.wrapping_add(8) & 0x00ff_fff8;
             }
 
@@ -120,6 +242,7 @@ impl Rdp {
             for _ in 0..block_len {
                 let command: u64 = rdram.read_single(current as usize);
                 self.decoder.write_command(command);
+                self.capture.record(command);
                 current = current.wrapping_add(8) & 0x00ff_fff8;
             }
 
@@ -204,6 +327,7 @@ impl RdpShared {
             1 => {
                 mask.write_partial(&mut self.regs.end, 0x00ff_fff8);
                 debug!("DPC_END: {:08X}", self.regs.end);
+                self.end_written = true;
 
                 let status = &mut self.regs.status;
 
@@ -0,0 +1,287 @@
+use super::renderer::{
+    ColorImage, CombineModeRaw, FixedColor, KeyParams, OtherModes, Rect, RectangleCoefficients,
+    Renderer, TextureImage, TileDescriptor, TileSize, TriangleCoefficients,
+};
+use super::software::SoftwareRenderer;
+use crate::gfx::GfxContext;
+use crate::rdram::Rdram;
+use crate::snapshot::{Reader, Writer};
+use std::error::Error;
+use tracing::trace;
+
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum RenderBackend {
+    // Renders using the GPU, via wgpu
+    #[default]
+    Hardware,
+    // Rasterizes directly into RDRAM on the CPU. Much slower, but aims to
+    // reproduce the RDP's own fixed-point pipeline, and needs no GPU (unless
+    // there is a window to present to).
+    Software,
+}
+
+pub enum Backend {
+    Hardware(Box<Renderer>),
+    Software(Box<SoftwareRenderer>),
+}
+
+impl Backend {
+    pub fn new(backend: RenderBackend, gfx: Option<&GfxContext>) -> Result<Self, Box<dyn Error>> {
+        Ok(match backend {
+            RenderBackend::Hardware => {
+                let gfx = gfx.ok_or("The hardware RDP backend needs a GPU")?;
+                Self::Hardware(Box::new(Renderer::new(gfx)))
+            }
+            RenderBackend::Software => Self::Software(Box::new(SoftwareRenderer::new())),
+        })
+    }
+
+    pub fn kind(&self) -> RenderBackend {
+        match self {
+            Self::Hardware(_) => RenderBackend::Hardware,
+            Self::Software(_) => RenderBackend::Software,
+        }
+    }
+
+    pub fn save_state(&self, writer: &mut Writer) {
+        writer.tag(b"RDPR");
+        writer.u8(self.kind() as u8);
+
+        match self {
+            Self::Hardware(renderer) => renderer.save_state(writer),
+            Self::Software(renderer) => renderer.save_state(writer),
+        }
+    }
+
+    // TMEM layouts differ between the two renderers, so a save state can only
+    // be loaded into the same kind of renderer that created it
+    pub fn load_state(&mut self, reader: &mut Reader) -> Result<(), Box<dyn Error>> {
+        reader.tag(b"RDPR")?;
+
+        if reader.u8()? != self.kind() as u8 {
+            return Err(format!(
+                "Save state was not created with the {:?} RDP backend",
+                self.kind()
+            )
+            .into());
+        }
+
+        match self {
+            Self::Hardware(renderer) => renderer.load_state(reader),
+            Self::Software(renderer) => renderer.load_state(reader),
+        }
+    }
+
+    pub fn set_color_image(
+        &mut self,
+        gfx: Option<&GfxContext>,
+        rdram: &mut Rdram,
+        color_image: ColorImage,
+    ) {
+        match self {
+            Self::Hardware(renderer) => renderer.set_color_image(gpu(gfx), rdram, color_image),
+            Self::Software(renderer) => renderer.set_color_image(color_image),
+        }
+    }
+
+    pub fn set_z_image(&mut self, dram_addr: u32) {
+        match self {
+            // Hardware renderer keeps its own depth buffer on the GPU
+            Self::Hardware(_) => trace!("  Z Image: {:08X} (ignored)", dram_addr),
+            Self::Software(renderer) => renderer.set_z_image(dram_addr),
+        }
+    }
+
+    pub fn set_scissor(&mut self, gfx: Option<&GfxContext>, rdram: &mut Rdram, scissor: Rect) {
+        match self {
+            Self::Hardware(renderer) => renderer.set_scissor(gpu(gfx), rdram, scissor),
+            Self::Software(renderer) => renderer.set_scissor(scissor),
+        }
+    }
+
+    pub fn set_fill_color(&mut self, gfx: Option<&GfxContext>, rdram: &mut Rdram, value: u32) {
+        match self {
+            Self::Hardware(renderer) => renderer.set_fill_color(gpu(gfx), rdram, value),
+            Self::Software(renderer) => renderer.set_fill_color(value),
+        }
+    }
+
+    pub fn set_combine_mode(&mut self, combine_mode: CombineModeRaw) {
+        match self {
+            Self::Hardware(renderer) => renderer.set_combine_mode(combine_mode),
+            Self::Software(renderer) => renderer.set_combine_mode(combine_mode),
+        }
+    }
+
+    pub fn set_other_modes(
+        &mut self,
+        gfx: Option<&GfxContext>,
+        rdram: &mut Rdram,
+        mode: OtherModes,
+    ) {
+        match self {
+            Self::Hardware(renderer) => renderer.set_other_modes(gpu(gfx), rdram, mode),
+            Self::Software(renderer) => renderer.set_other_modes(mode),
+        }
+    }
+
+    pub fn set_texture_image(&mut self, texture_image: TextureImage) {
+        match self {
+            Self::Hardware(renderer) => renderer.set_texture_image(texture_image),
+            Self::Software(renderer) => renderer.set_texture_image(texture_image),
+        }
+    }
+
+    pub fn set_tile(&mut self, index: usize, tile: TileDescriptor, hash_value: u64) {
+        match self {
+            Self::Hardware(renderer) => renderer.set_tile(index, tile, hash_value),
+            Self::Software(renderer) => renderer.set_tile(index, tile),
+        }
+    }
+
+    pub fn set_tile_size(&mut self, index: usize, size: TileSize, hash_value: u64) {
+        match self {
+            Self::Hardware(renderer) => renderer.set_tile_size(index, size, hash_value),
+            Self::Software(renderer) => renderer.set_tile_size(index, size),
+        }
+    }
+
+    pub fn load_tile(
+        &mut self,
+        gfx: Option<&GfxContext>,
+        rdram: &mut Rdram,
+        index: usize,
+        size: TileSize,
+        hash_value: u64,
+    ) {
+        match self {
+            Self::Hardware(renderer) => {
+                renderer.load_tile(gpu(gfx), rdram, index, size, hash_value)
+            }
+            Self::Software(renderer) => renderer.load_tile(rdram, index, size),
+        }
+    }
+
+    pub fn load_tlut(
+        &mut self,
+        gfx: Option<&GfxContext>,
+        rdram: &mut Rdram,
+        index: usize,
+        size: TileSize,
+    ) {
+        match self {
+            Self::Hardware(renderer) => renderer.load_tlut(gpu(gfx), rdram, index, size),
+            Self::Software(renderer) => renderer.load_tlut(rdram, index, size),
+        }
+    }
+
+    pub fn load_block(
+        &mut self,
+        gfx: Option<&GfxContext>,
+        rdram: &mut Rdram,
+        index: usize,
+        size: TileSize,
+        hash_value: u64,
+    ) {
+        match self {
+            Self::Hardware(renderer) => {
+                renderer.load_block(gpu(gfx), rdram, index, size, hash_value)
+            }
+            Self::Software(renderer) => renderer.load_block(rdram, index, size),
+        }
+    }
+
+    // Applies only the tile size from a LoadBlock command
+    pub fn set_block_size(&mut self, index: usize, size: TileSize, hash_value: u64) {
+        match self {
+            Self::Hardware(renderer) => renderer.set_block_size(index, size, hash_value),
+            Self::Software(renderer) => renderer.set_tile_size(index, size),
+        }
+    }
+
+    // Applies only the tile size from a LoadTlut command
+    pub fn set_tlut_size(&mut self, index: usize, size: TileSize) {
+        match self {
+            // Hardware renderer doesn't track tile size for TLUT loads
+            Self::Hardware(_) => (),
+            Self::Software(renderer) => renderer.set_tile_size(index, size),
+        }
+    }
+
+    pub fn set_fixed_color(&mut self, color: FixedColor, value: u32) {
+        match self {
+            Self::Hardware(renderer) => renderer.set_fixed_color(color, value),
+            Self::Software(renderer) => renderer.set_fixed_color(color, value),
+        }
+    }
+
+    pub fn set_prim_lod(&mut self, min_level: u32, lod_frac: u32) {
+        match self {
+            // TODO: LOD params
+            Self::Hardware(_) => (),
+            Self::Software(renderer) => renderer.set_prim_lod(min_level, lod_frac),
+        }
+    }
+
+    pub fn set_prim_depth(&mut self, z: i16, delta_z: i16) {
+        match self {
+            Self::Hardware(renderer) => renderer.set_prim_depth(z, delta_z),
+            Self::Software(renderer) => renderer.set_prim_depth(z, delta_z),
+        }
+    }
+
+    pub fn set_key(&mut self, index: usize, key: KeyParams) {
+        match self {
+            // TODO: Chroma key
+            Self::Hardware(_) => (),
+            Self::Software(renderer) => renderer.set_key(index, key),
+        }
+    }
+
+    pub fn set_convert(&mut self, coeffs: [i32; 6]) {
+        match self {
+            // TODO: YUV conversion
+            Self::Hardware(_) => (),
+            Self::Software(renderer) => renderer.set_convert(coeffs),
+        }
+    }
+
+    pub fn draw_triangle(
+        &mut self,
+        gfx: Option<&GfxContext>,
+        rdram: &mut Rdram,
+        triangle: &TriangleCoefficients,
+    ) {
+        match self {
+            Self::Hardware(renderer) => renderer.draw_triangle(gpu(gfx), rdram, triangle),
+            Self::Software(renderer) => renderer.draw_triangle(rdram, triangle),
+        }
+    }
+
+    pub fn draw_rectangle(
+        &mut self,
+        gfx: Option<&GfxContext>,
+        rdram: &mut Rdram,
+        rectangle: &RectangleCoefficients,
+    ) {
+        match self {
+            Self::Hardware(renderer) => renderer.draw_rectangle(gpu(gfx), rdram, rectangle),
+            Self::Software(renderer) => renderer.draw_rectangle(rdram, rectangle),
+        }
+    }
+
+    pub fn sync(&mut self, gfx: Option<&GfxContext>, rdram: &mut Rdram) {
+        match self {
+            Self::Hardware(renderer) => renderer.sync(gpu(gfx), rdram),
+            // Software renderer writes straight to RDRAM, so there is nothing
+            // left to do here
+            Self::Software(_) => (),
+        }
+    }
+}
+
+// The hardware renderer is only ever created with a GPU (see 'Backend::new'),
+// so it can always rely on having one
+fn gpu(gfx: Option<&GfxContext>) -> &GfxContext {
+    gfx.expect("Hardware RDP backend used without a GPU")
+}
@@ -0,0 +1,268 @@
+use super::backend::{Backend, RenderBackend};
+use super::decoder::{Context, Decoder};
+use super::renderer::Format;
+use crate::gfx::{self, DisplayTarget, GfxContext};
+use crate::header::CicType;
+use crate::rdram::Rdram;
+use crate::rewind;
+use crate::snapshot::{Reader, Writer};
+use std::error::Error;
+use tracing::info;
+
+// Capture format: the usual header, the renderer kind, the recorded state
+// commands, RDRAM and renderer state (as in a save state) as they were at the
+// SYNC_FULL that began the capture, then every command word run up to and
+// including the next SYNC_FULL. Commands are split into segments at each
+// DP_END write, each starting with the changes made to RDRAM since the last.
+const MAGIC: &[u8; 8] = b"REALRDP\x1a";
+
+const VERSION: u32 = 2;
+
+#[derive(Default)]
+struct Segment {
+    // RDRAM changes since the previous segment, as a rewind delta
+    rdram_delta: Vec<u8>,
+    commands: Vec<u64>,
+}
+
+// The color image drawn by replaying a capture, as RGBA8 pixels
+pub struct Image {
+    pub width: u32,
+    pub height: u32,
+    pub pixels: Vec<u8>,
+}
+
+#[derive(Default)]
+enum State {
+    #[default]
+    Idle,
+    // Waiting for a SYNC_FULL to start from
+    Armed,
+    Recording {
+        writer: Writer,
+        // RDRAM as of the latest segment
+        rdram: Vec<u8>,
+        segments: Vec<Segment>,
+    },
+    Finished(Vec<u8>),
+}
+
+#[derive(Default)]
+pub struct FrameCapture {
+    state: State,
+}
+
+impl FrameCapture {
+    pub fn arm(&mut self) {
+        self.state = State::Armed;
+    }
+
+    pub fn take(&mut self) -> Option<Vec<u8>> {
+        match std::mem::take(&mut self.state) {
+            State::Finished(data) => Some(data),
+            state => {
+                self.state = state;
+                None
+            }
+        }
+    }
+
+    #[inline(always)]
+    pub fn record(&mut self, word: u64) {
+        if let State::Recording { segments, .. } = &mut self.state {
+            segments.last_mut().unwrap().commands.push(word);
+        }
+    }
+
+    // Should be called when DP_END is written, before any of the new commands
+    // are recorded
+    pub fn dp_end(&mut self, rdram: &Rdram) {
+        let State::Recording {
+            rdram: latest,
+            segments,
+            ..
+        } = &mut self.state
+        else {
+            return;
+        };
+
+        let current = rdram.as_bytes();
+
+        segments.push(Segment {
+            rdram_delta: rewind::encode(latest, current),
+            commands: Vec::new(),
+        });
+
+        latest.copy_from_slice(current);
+    }
+
+    // Should be called once a SYNC_FULL has run and the renderer has been
+    // synced, so that RDRAM holds everything drawn so far
+    pub fn sync_full(&mut self, decoder: &Decoder, renderer: &Backend, rdram: &Rdram) {
+        match std::mem::take(&mut self.state) {
+            State::Armed => {
+                let mut writer = Writer::with_header(MAGIC, VERSION);
+                writer.tag(b"RDPC");
+                writer.u8(renderer.kind() as u8);
+
+                let state_words = decoder.state_words();
+                writer.u32(state_words.len() as u32);
+
+                for word in state_words {
+                    writer.u64(word);
+                }
+
+                rdram.save_state(&mut writer);
+                renderer.save_state(&mut writer);
+
+                info!("RDP frame capture started");
+
+                // Anything already submitted will run as part of this frame
+                self.state = State::Recording {
+                    writer,
+                    rdram: rdram.as_bytes().to_vec(),
+                    segments: vec![Segment {
+                        rdram_delta: Vec::new(),
+                        commands: decoder.pending_words(),
+                    }],
+                };
+            }
+            State::Recording {
+                mut writer,
+                mut segments,
+                ..
+            } => {
+                // Commands submitted after the SYNC_FULL belong to the next frame
+                let mut excess = decoder.pending_commands();
+
+                while let Some(segment) = segments.last_mut() {
+                    if excess < segment.commands.len() {
+                        segment.commands.truncate(segment.commands.len() - excess);
+                        break;
+                    }
+
+                    excess -= segment.commands.len();
+                    segments.pop();
+                }
+
+                writer.tag(b"CMDS");
+                writer.u32(segments.len() as u32);
+
+                for segment in segments {
+                    writer.bytes(&segment.rdram_delta);
+                    writer.u32(segment.commands.len() as u32);
+
+                    for word in segment.commands {
+                        writer.u64(word);
+                    }
+                }
+
+                info!("RDP frame capture finished");
+
+                self.state = State::Finished(writer.finish());
+            }
+            state => self.state = state,
+        }
+    }
+}
+
+// Only captures from the hardware renderer need a GPU to replay
+pub fn replay(data: &[u8]) -> Result<Image, Box<dyn Error>> {
+    let mut reader = Reader::with_header(data, MAGIC, VERSION, "RDP capture")?;
+    reader.tag(b"RDPC")?;
+
+    let backend = match reader.u8()? {
+        0 => RenderBackend::Hardware,
+        1 => RenderBackend::Software,
+        kind => return Err(format!("Unknown RDP backend: {}", kind).into()),
+    };
+
+    let state_words = (0..reader.u32()?)
+        .map(|_| reader.u64())
+        .collect::<Result<Vec<u64>, _>>()?;
+
+    let mut rdram = Rdram::new(CicType::Unknown, true);
+    rdram.load_state(&mut reader)?;
+
+    let gfx = match backend {
+        RenderBackend::Hardware => Some(GfxContext::new(DisplayTarget {
+            window: None,
+            width: 640,
+            height: 480,
+        })?),
+        RenderBackend::Software => None,
+    };
+
+    let gfx = gfx.as_ref();
+    let mut renderer = Backend::new(backend, gfx)?;
+    let mut decoder = Decoder::new();
+    decoder.restore_words(&state_words, &mut renderer, &mut rdram, gfx);
+    renderer.load_state(&mut reader)?;
+
+    reader.tag(b"CMDS")?;
+
+    for _ in 0..reader.u32()? {
+        let rdram_delta = reader.bytes()?;
+
+        if !rdram_delta.is_empty() {
+            let data = rewind::decode(rdram.as_bytes(), rdram_delta)?;
+
+            if data.len() != rdram.as_bytes().len() {
+                return Err("RDRAM size in capture does not match".into());
+            }
+
+            rdram.as_bytes_mut().copy_from_slice(&data);
+        }
+
+        for _ in 0..reader.u32()? {
+            decoder.write_command(reader.u64()?);
+        }
+
+        decoder.restart();
+
+        while decoder.running() {
+            decoder.step(Context {
+                renderer: &mut renderer,
+                rdram: &mut rdram,
+                gfx,
+            });
+        }
+    }
+
+    reader.finish()?;
+
+    renderer.sync(gfx, &mut rdram);
+
+    let (color_image, height) = decoder
+        .color_image()
+        .ok_or("RDP capture does not set a color image")?;
+
+    let width = color_image.width;
+    let mut pixels = vec![0; width as usize * height as usize * 4];
+
+    match color_image.format {
+        (Format::Rgba, 2) => gfx::copy_image_rgba16(
+            &rdram,
+            &mut pixels,
+            color_image.dram_addr,
+            width,
+            width,
+            height,
+        ),
+        (Format::Rgba, 3) => gfx::copy_image_rgba32(
+            &rdram,
+            &mut pixels,
+            color_image.dram_addr,
+            width,
+            width,
+            height,
+        ),
+        format => return Err(format!("Unsupported color image format: {:?}", format).into()),
+    }
+
+    Ok(Image {
+        width,
+        height,
+        pixels,
+    })
+}
@@ -1,10 +1,16 @@
+use super::backend::Backend;
 use super::renderer;
-use super::renderer::{Format, Renderer};
+use super::renderer::{ColorImage, Format};
 use crate::gfx::GfxContext;
 use crate::rdram::Rdram;
+use crate::snapshot::{Reader, Writer};
 use std::collections::VecDeque;
+use std::error::Error;
 use tracing::debug;
 
+pub use disassembler::disassemble;
+
+mod disassembler;
 mod mode;
 mod param;
 mod rect;
@@ -14,14 +20,24 @@ mod tmem;
 mod triangle;
 
 pub struct Context<'a> {
-    pub renderer: &'a mut Renderer,
+    pub renderer: &'a mut Backend,
     pub rdram: &'a mut Rdram,
-    pub gfx: &'a GfxContext,
+    pub gfx: Option<&'a GfxContext>,
 }
 
+// One slot per opcode, plus one per tile for each of the tile commands
+const STATE_SLOTS: usize = 64 + 8 * 6;
+
 pub struct Decoder {
     running: bool,
     commands: VecDeque<u64>,
+    // The most recent word of each command that changes renderer state,
+    // tagged with a sequence number so the order can be recovered. Replaying
+    // these is enough to rebuild either renderer when loading a save state.
+    state: Vec<(u64, u64)>,
+    sequence: u64,
+    // Every word submitted while a capture is in progress
+    capture: Option<Vec<u64>>,
 }
 
 impl Decoder {
@@ -29,7 +45,114 @@ impl Decoder {
         Self {
             running: false,
             commands: VecDeque::new(),
+            state: vec![(0, 0); STATE_SLOTS],
+            sequence: 0,
+            capture: None,
+        }
+    }
+
+    pub fn save_state(&self, writer: &mut Writer) {
+        writer.tag(b"RDPD");
+        writer.bool(self.running);
+        writer.u32(self.commands.len() as u32);
+
+        for &word in &self.commands {
+            writer.u64(word);
+        }
+
+        for &(sequence, word) in &self.state {
+            writer.u64(sequence);
+            writer.u64(word);
+        }
+
+        writer.u64(self.sequence);
+    }
+
+    pub fn load_state(&mut self, reader: &mut Reader) -> Result<(), Box<dyn Error>> {
+        reader.tag(b"RDPD")?;
+        self.running = reader.bool()?;
+
+        let len = reader.u32()?;
+        self.commands.clear();
+
+        for _ in 0..len {
+            self.commands.push_back(reader.u64()?);
         }
+
+        for (sequence, word) in &mut self.state {
+            *sequence = reader.u64()?;
+            *word = reader.u64()?;
+        }
+
+        self.sequence = reader.u64()?;
+        Ok(())
+    }
+
+    // Replays the recorded state commands into a freshly created renderer.
+    // Texture loads only have their tile size applied, as TMEM contents are
+    // restored separately.
+    pub fn restore(&mut self, renderer: &mut Backend, rdram: &mut Rdram, gfx: Option<&GfxContext>) {
+        self.restore_words(&self.state_words(), renderer, rdram, gfx);
+    }
+
+    // As above, but for state commands recorded elsewhere (such as in a frame
+    // capture). These become the decoder's own recorded state.
+    pub fn restore_words(
+        &mut self,
+        words: &[u64],
+        renderer: &mut Backend,
+        rdram: &mut Rdram,
+        gfx: Option<&GfxContext>,
+    ) {
+        for &word in words {
+            if let Some(slot) = state_slot((word >> 56) & 0x3f, word) {
+                self.sequence += 1;
+                self.state[slot] = (self.sequence, word);
+            }
+
+            let ctx = Context {
+                renderer,
+                rdram,
+                gfx,
+            };
+
+            match (word >> 56) & 0x3f {
+                0x30 => tmem::restore_tlut_size(self, ctx, word),
+                0x33 => tmem::restore_block_size(self, ctx, word),
+                // LoadTile has the same layout as SetTileSize
+                0x34 => tmem::set_tile_size(self, ctx, word),
+                _ => self.execute(ctx, word),
+            }
+        }
+    }
+
+    // Returns the recorded state commands in the order they were submitted
+    pub fn state_words(&self) -> Vec<u64> {
+        let mut words: Vec<(u64, u64)> = self
+            .state
+            .iter()
+            .copied()
+            .filter(|&(sequence, _)| sequence != 0)
+            .collect();
+
+        words.sort_unstable();
+        words.into_iter().map(|(_, word)| word).collect()
+    }
+
+    // The color image and the number of lines it has been drawn to, if one
+    // has been set
+    pub fn color_image(&self) -> Option<(ColorImage, u32)> {
+        let (sequence, color_image) = self.state[0x3f];
+        let (_, scissor) = self.state[0x2d];
+        (sequence != 0).then(|| target::color_image_area(color_image, scissor))
+    }
+
+    pub fn pending_commands(&self) -> usize {
+        self.commands.len()
+    }
+
+    pub fn pending_words(&self) -> Vec<u64> {
+        self.commands.iter().copied().collect()
     }
 
     pub fn running(&self) -> bool {
@@ -40,7 +163,19 @@ impl Decoder {
         self.running = true;
     }
 
+    pub fn start_capture(&mut self) {
+        self.capture = Some(Vec::new());
+    }
+
+    pub fn take_capture(&mut self) -> Option<Vec<u64>> {
+        self.capture.take()
+    }
+
     pub fn write_command(&mut self, value: u64) {
+        if let Some(capture) = &mut self.capture {
+            capture.push(value);
+        }
+
         self.commands.push_back(value)
     }
 
@@ -52,6 +187,20 @@ impl Decoder {
 
         let opcode = (word >> 56) & 0x3f;
 
+        if let Some(slot) = state_slot(opcode, word) {
+            self.sequence += 1;
+            self.state[slot] = (self.sequence, word);
+        }
+
+        self.execute(bus, word);
+
+        // If SYNC_FULL was run, let the caller know
+        opcode == 0x29
+    }
+
+    fn execute(&mut self, bus: Context, word: u64) {
+        let opcode = (word >> 56) & 0x3f;
+
         match opcode {
             0x00 => (), // NOP
             0x08 => triangle::triangle::<false, false, false>(self, bus, word),
@@ -68,6 +217,9 @@ impl Decoder {
             0x27 => sync::sync_pipe(self, bus, word),
             0x28 => sync::sync_tile(self, bus, word),
             0x29 => sync::sync_full(self, bus, word),
+            0x2a => param::set_key_gb(self, bus, word),
+            0x2b => param::set_key_r(self, bus, word),
+            0x2c => param::set_convert(self, bus, word),
             0x2d => target::set_scissor(self, bus, word),
             0x2e => param::set_prim_depth(self, bus, word),
             0x2f => mode::set_other_modes(self, bus, word),
@@ -84,12 +236,20 @@ impl Decoder {
             0x3b => param::set_env_color(self, bus, word),
             0x3c => mode::set_combine_mode(self, bus, word),
             0x3d => tmem::set_texture_image(self, bus, word),
+            0x3e => target::set_z_image(self, bus, word),
             0x3f => target::set_color_image(self, bus, word),
             _ => debug!("TODO: RDP Command: {:#02X}", opcode),
         }
+    }
+}
 
-        // If SYNC_FULL was run, let the caller know
-        opcode == 0x29
+fn state_slot(opcode: u64, word: u64) -> Option<usize> {
+    let tile = ((word >> 24) & 7) as usize;
+
+    match opcode {
+        0x30 | 0x32..=0x35 => Some(64 + (opcode as usize - 0x30) * 8 + tile),
+        0x2a..=0x2f | 0x37..=0x3f => Some(opcode as usize),
+        _ => None,
     }
 }
 
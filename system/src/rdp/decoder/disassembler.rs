@@ -0,0 +1,231 @@
+use super::mode::{SetCombineMode, SetOtherModes};
+use super::param::{
+    SetBlendColor, SetConvert, SetEnvColor, SetFogColor, SetKeyGB, SetKeyR, SetPrimColor,
+    SetPrimDepth,
+};
+use super::rect::{Rectangle, TexCoords};
+use super::target::{SetColorImage, SetFillColor, SetScissor, SetZImage};
+use super::tmem::{LoadBlock, LoadTile, LoadTlut, SetTextureImage, SetTile, SetTileSize};
+use super::triangle::{Color, Edge, TexCoord, Triangle};
+use std::fmt::Write;
+
+const COLOR_PARAMS: [&str; 8] = [
+    "Shade",
+    "Shade DX",
+    "Shade Frac",
+    "Shade Frac DX",
+    "Shade DE",
+    "Shade DY",
+    "Shade Frac DE",
+    "Shade Frac DY",
+];
+
+const TEX_COORD_PARAMS: [&str; 8] = [
+    "Texture",
+    "Texture DX",
+    "Texture Frac",
+    "Texture Frac DX",
+    "Texture DE",
+    "Texture DY",
+    "Texture Frac DE",
+    "Texture Frac DY",
+];
+
+pub fn command_name(opcode: u64) -> &'static str {
+    match opcode {
+        0x00 => "No_Op",
+        0x08 => "Fill_Triangle",
+        0x09 => "Fill_ZBuffer_Triangle",
+        0x0a => "Texture_Triangle",
+        0x0b => "Texture_ZBuffer_Triangle",
+        0x0c => "Shade_Triangle",
+        0x0d => "Shade_ZBuffer_Triangle",
+        0x0e => "Shade_Texture_Triangle",
+        0x0f => "Shade_Texture_ZBuffer_Triangle",
+        0x24 => "Texture_Rectangle",
+        0x25 => "Texture_Rectangle_Flip",
+        0x26 => "Sync_Load",
+        0x27 => "Sync_Pipe",
+        0x28 => "Sync_Tile",
+        0x29 => "Sync_Full",
+        0x2a => "Set_Key_GB",
+        0x2b => "Set_Key_R",
+        0x2c => "Set_Convert",
+        0x2d => "Set_Scissor",
+        0x2e => "Set_Prim_Depth",
+        0x2f => "Set_Other_Modes",
+        0x30 => "Load_TLUT",
+        0x32 => "Set_Tile_Size",
+        0x33 => "Load_Block",
+        0x34 => "Load_Tile",
+        0x35 => "Set_Tile",
+        0x36 => "Fill_Rectangle",
+        0x37 => "Set_Fill_Color",
+        0x38 => "Set_Fog_Color",
+        0x39 => "Set_Blend_Color",
+        0x3a => "Set_Prim_Color",
+        0x3b => "Set_Env_Color",
+        0x3c => "Set_Combine_Mode",
+        0x3d => "Set_Texture_Image",
+        0x3e => "Set_Z_Image",
+        0x3f => "Set_Color_Image",
+        _ => "Unknown",
+    }
+}
+
+// Number of words taken up by the command starting with 'word', including
+// any parameters that follow it
+pub fn command_len(word: u64) -> usize {
+    let opcode = (word >> 56) & 0x3f;
+
+    match opcode {
+        0x08..=0x0f => {
+            let shade = (opcode & 0x04) != 0;
+            let texture = (opcode & 0x02) != 0;
+            let z_buffer = (opcode & 0x01) != 0;
+            4 + (shade as usize * 8) + (texture as usize * 8) + (z_buffer as usize * 2)
+        }
+        0x24 | 0x25 => 2,
+        _ => 1,
+    }
+}
+
+// Describes a stream of command words, one command per line. Parameters of
+// triangle commands are listed on the lines that follow.
+pub fn disassemble(words: &[u64]) -> String {
+    let mut output = String::new();
+    let mut index = 0;
+
+    while index < words.len() {
+        let word = words[index];
+        let len = command_len(word);
+        let end = (index + len).min(words.len());
+
+        describe(&mut output, &words[index..end]);
+
+        if end - index < len {
+            writeln!(output, "  (incomplete: {} of {} words)", end - index, len).unwrap();
+        }
+
+        index = end;
+    }
+
+    output
+}
+
+fn describe(output: &mut String, words: &[u64]) {
+    let word = words[0];
+    let opcode = (word >> 56) & 0x3f;
+    let params = &words[1..];
+
+    write!(output, "{:016X} {}", word, command_name(opcode)).unwrap();
+
+    let fields = match opcode {
+        0x08..=0x0f => Triangle::from(word).to_string(),
+        0x24 | 0x25 | 0x36 => Rectangle::from(word).to_string(),
+        0x2a => SetKeyGB::from(word).to_string(),
+        0x2b => SetKeyR::from(word).to_string(),
+        0x2c => SetConvert::from(word).to_string(),
+        0x2d => SetScissor::from(word).to_string(),
+        0x2e => SetPrimDepth::from(word).to_string(),
+        0x2f => SetOtherModes::from(word).to_string(),
+        0x30 => LoadTlut::from(word).to_string(),
+        0x32 => SetTileSize::from(word).to_string(),
+        0x33 => LoadBlock::from(word).to_string(),
+        0x34 => LoadTile::from(word).to_string(),
+        0x35 => SetTile::from(word).to_string(),
+        0x37 => SetFillColor::from(word).to_string(),
+        0x38 => SetFogColor::from(word).to_string(),
+        0x39 => SetBlendColor::from(word).to_string(),
+        0x3a => SetPrimColor::from(word).to_string(),
+        0x3b => SetEnvColor::from(word).to_string(),
+        0x3c => SetCombineMode::from(word).to_string(),
+        0x3d => SetTextureImage::from(word).to_string(),
+        0x3e => SetZImage::from(word).to_string(),
+        0x3f => SetColorImage::from(word).to_string(),
+        _ => String::new(),
+    };
+
+    if fields.is_empty() {
+        writeln!(output).unwrap();
+    } else {
+        writeln!(output, " {}", fields).unwrap();
+    }
+
+    if let 0x24 | 0x25 = opcode {
+        if let Some(&param) = params.first() {
+            writeln!(output, "  {}", TexCoords::from(param)).unwrap();
+        }
+    }
+
+    if !(0x08..=0x0f).contains(&opcode) {
+        return;
+    }
+
+    let mut params = params.iter().copied();
+
+    for name in ["L", "H", "M"] {
+        if let Some(param) = params.next() {
+            writeln!(output, "  {}: {}", name, Edge::from(param)).unwrap();
+        }
+    }
+
+    if (opcode & 0x04) != 0 {
+        for name in COLOR_PARAMS {
+            if let Some(param) = params.next() {
+                writeln!(output, "  {}: {}", name, Color::from(param)).unwrap();
+            }
+        }
+    }
+
+    if (opcode & 0x02) != 0 {
+        for name in TEX_COORD_PARAMS {
+            if let Some(param) = params.next() {
+                writeln!(output, "  {}: {}", name, TexCoord::from(param)).unwrap();
+            }
+        }
+    }
+
+    if (opcode & 0x01) != 0 {
+        for names in [("Z", "DZDX"), ("DZDE", "DZDY")] {
+            if let Some(param) = params.next() {
+                writeln!(
+                    output,
+                    "  {}: {:08X}, {}: {:08X}",
+                    names.0,
+                    (param >> 32) as u32,
+                    names.1,
+                    param as u32
+                )
+                .unwrap();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn describes_multi_word_commands() {
+        let output = disassemble(&[
+            0x3f10_013f_0010_0000,
+            0x2400_0000_0000_0000,
+            0x0000_0000_0400_0400,
+            0x0800_0000_0000_0000,
+        ]);
+
+        let lines: Vec<&str> = output.lines().collect();
+
+        assert_eq!(
+            lines[0],
+            "3F10013F00100000 Set_Color_Image format=Rgba size=16bpp width=320 dram_addr=100000"
+        );
+
+        assert!(lines[1].contains("Texture_Rectangle tile=0"));
+        assert_eq!(lines[2], "  s=0.00000 t=0.00000 dsdx=1.00000 dtdy=1.00000");
+        assert!(lines[3].contains("Fill_Triangle"));
+        assert_eq!(lines[4], "  (incomplete: 1 of 4 words)");
+    }
+}
@@ -1,12 +1,77 @@
 use super::renderer::{
-    BlendModeRaw, BlendModeRawParams, CombineModeRaw, CombineModeRawParams, CycleType, OtherModes,
-    SampleType, ZSource,
+    AlphaDitherSelect, BlendModeRaw, BlendModeRawParams, CombineModeRaw, CombineModeRawParams,
+    CvgDest, CycleType, OtherModes, RgbDitherSelect, SampleType, TlutType, ZMode, ZSource,
 };
 use super::{Context, Decoder};
 use bitfield_struct::bitfield;
+use std::fmt::{self, Display};
 use std::mem;
 use tracing::trace;
 
+const RGB_SUB_A: [&str; 8] = [
+    "COMBINED",
+    "TEXEL0",
+    "TEXEL1",
+    "PRIMITIVE",
+    "SHADE",
+    "ENVIRONMENT",
+    "1",
+    "NOISE",
+];
+
+const RGB_SUB_B: [&str; 8] = [
+    "COMBINED",
+    "TEXEL0",
+    "TEXEL1",
+    "PRIMITIVE",
+    "SHADE",
+    "ENVIRONMENT",
+    "CENTER",
+    "K4",
+];
+
+const RGB_MUL: [&str; 16] = [
+    "COMBINED",
+    "TEXEL0",
+    "TEXEL1",
+    "PRIMITIVE",
+    "SHADE",
+    "ENVIRONMENT",
+    "SCALE",
+    "COMBINED_ALPHA",
+    "TEXEL0_ALPHA",
+    "TEXEL1_ALPHA",
+    "PRIMITIVE_ALPHA",
+    "SHADE_ALPHA",
+    "ENV_ALPHA",
+    "LOD_FRACTION",
+    "PRIM_LOD_FRAC",
+    "K5",
+];
+
+// Also used for the alpha subtract and add inputs
+const RGB_ADD: [&str; 8] = [
+    "COMBINED",
+    "TEXEL0",
+    "TEXEL1",
+    "PRIMITIVE",
+    "SHADE",
+    "ENVIRONMENT",
+    "1",
+    "0",
+];
+
+const ALPHA_MUL: [&str; 8] = [
+    "LOD_FRACTION",
+    "TEXEL0",
+    "TEXEL1",
+    "PRIMITIVE",
+    "SHADE",
+    "ENVIRONMENT",
+    "PRIM_LOD_FRAC",
+    "0",
+];
+
 pub fn set_combine_mode(_decoder: &mut Decoder, ctx: Context, word: u64) {
     let cmd = SetCombineMode::from(word);
 
@@ -59,6 +124,7 @@ pub fn set_other_modes(_decoder: &mut Decoder, ctx: Context, word: u64) {
             z_compare_en: cmd.z_compare_en(),
             z_update_en: cmd.z_update_en(),
             z_source: cmd.z_source_sel(),
+            z_mode: cmd.z_mode(),
             blend_mode: BlendModeRaw {
                 mode: [
                     BlendModeRawParams {
@@ -75,12 +141,31 @@ pub fn set_other_modes(_decoder: &mut Decoder, ctx: Context, word: u64) {
                     },
                 ],
             },
+            alpha_compare_en: cmd.alpha_compare_en(),
+            dither_alpha_en: cmd.dither_alpha_en(),
+            antialias_en: cmd.antialias_en(),
+            image_read_en: cmd.image_read_en(),
+            color_on_cvg: cmd.color_on_cvg(),
+            cvg_dest: cmd.cvg_dest(),
+            cvg_times_alpha: cmd.cvg_times_alpha(),
+            alpha_cvg_select: cmd.alpha_cvg_select(),
+            force_blend: cmd.force_blend(),
+            alpha_dither_sel: cmd.alpha_dither_sel(),
+            rgb_dither_sel: cmd.rgb_dither_sel(),
+            key_en: cmd.key_en(),
+            bi_lerp: [cmd.bi_lerp_0(), cmd.bi_lerp_1()],
+            mid_texel: cmd.mid_texel(),
+            en_tlut: cmd.en_tlut(),
+            tlut_type: cmd.tlut_type(),
+            tex_lod_en: cmd.tex_lod_en(),
+            sharpen_tex_en: cmd.sharpen_tex_en(),
+            detail_tex_en: cmd.detail_tex_en(),
         },
     );
 }
 
 #[bitfield(u64)]
-struct SetCombineMode {
+pub struct SetCombineMode {
     #[bits(3)]
     add_a_1: u32,
     #[bits(3)]
@@ -118,7 +203,7 @@ struct SetCombineMode {
 }
 
 #[bitfield(u64)]
-struct SetOtherModes {
+pub struct SetOtherModes {
     alpha_compare_en: bool,
     dither_alpha_en: bool,
     #[bits(1)]
@@ -160,12 +245,13 @@ struct SetOtherModes {
     rgb_dither_sel: RgbDitherSelect,
     key_en: bool,
     convert_one: bool,
-    bi_lerp_0: bool,
     bi_lerp_1: bool,
+    bi_lerp_0: bool,
     mid_texel: bool,
     #[bits(1)]
     sample_type: SampleType,
-    tlut_type: bool,
+    #[bits(1)]
+    tlut_type: TlutType,
     en_tlut: bool,
     tex_lod_en: bool,
     sharpen_tex_en: bool,
@@ -179,13 +265,91 @@ struct SetOtherModes {
     __: u64,
 }
 
-#[repr(u32)]
-#[derive(Copy, Clone, Debug, Eq, PartialEq)]
-enum CvgDest {
-    Clamp = 0,
-    Wrap = 1,
-    Zap = 2,
-    Save = 3,
+impl Display for SetCombineMode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let input = |table: &[&'static str], value: u32| -> &'static str {
+            table.get(value as usize).copied().unwrap_or("0")
+        };
+
+        write!(
+            f,
+            "rgb0=({} - {}) * {} + {} alpha0=({} - {}) * {} + {} \
+            rgb1=({} - {}) * {} + {} alpha1=({} - {}) * {} + {}",
+            input(&RGB_SUB_A, self.sub_a_r_0()),
+            input(&RGB_SUB_B, self.sub_b_r_0()),
+            input(&RGB_MUL, self.mul_r_0()),
+            input(&RGB_ADD, self.add_r_0()),
+            input(&RGB_ADD, self.sub_a_a_0()),
+            input(&RGB_ADD, self.sub_b_a_0()),
+            input(&ALPHA_MUL, self.mul_a_0()),
+            input(&RGB_ADD, self.add_a_0()),
+            input(&RGB_SUB_A, self.sub_a_r_1()),
+            input(&RGB_SUB_B, self.sub_b_r_1()),
+            input(&RGB_MUL, self.mul_r_1()),
+            input(&RGB_ADD, self.add_r_1()),
+            input(&RGB_ADD, self.sub_a_a_1()),
+            input(&RGB_ADD, self.sub_b_a_1()),
+            input(&ALPHA_MUL, self.mul_a_1()),
+            input(&RGB_ADD, self.add_a_1()),
+        )
+    }
+}
+
+impl Display for SetOtherModes {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "cycle_type={:?} sample_type={:?} tlut_type={:?} z_source={:?} z_mode={:?} \
+            cvg_dest={:?} rgb_dither={:?} alpha_dither={:?} \
+            blend0=({}, {}, {}, {}) blend1=({}, {}, {}, {})",
+            self.cycle_type(),
+            self.sample_type(),
+            self.tlut_type(),
+            self.z_source_sel(),
+            self.z_mode(),
+            self.cvg_dest(),
+            self.rgb_dither_sel(),
+            self.alpha_dither_sel(),
+            self.b_m1a_0(),
+            self.b_m1b_0(),
+            self.b_m2a_0(),
+            self.b_m2b_0(),
+            self.b_m1a_1(),
+            self.b_m1b_1(),
+            self.b_m2a_1(),
+            self.b_m2b_1(),
+        )?;
+
+        let flags = [
+            (self.atomic_prim(), "atomic_prim"),
+            (self.persp_tex_en(), "persp_tex_en"),
+            (self.detail_tex_en(), "detail_tex_en"),
+            (self.sharpen_tex_en(), "sharpen_tex_en"),
+            (self.tex_lod_en(), "tex_lod_en"),
+            (self.en_tlut(), "en_tlut"),
+            (self.mid_texel(), "mid_texel"),
+            (self.bi_lerp_0(), "bi_lerp_0"),
+            (self.bi_lerp_1(), "bi_lerp_1"),
+            (self.convert_one(), "convert_one"),
+            (self.key_en(), "key_en"),
+            (self.force_blend(), "force_blend"),
+            (self.alpha_cvg_select(), "alpha_cvg_select"),
+            (self.cvg_times_alpha(), "cvg_times_alpha"),
+            (self.color_on_cvg(), "color_on_cvg"),
+            (self.image_read_en(), "image_read_en"),
+            (self.z_update_en(), "z_update_en"),
+            (self.z_compare_en(), "z_compare_en"),
+            (self.antialias_en(), "antialias_en"),
+            (self.dither_alpha_en(), "dither_alpha_en"),
+            (self.alpha_compare_en(), "alpha_compare_en"),
+        ];
+
+        for (_, name) in flags.iter().filter(|(enabled, _)| *enabled) {
+            write!(f, " {}", name)?;
+        }
+
+        Ok(())
+    }
 }
 
 impl CvgDest {
@@ -203,15 +367,6 @@ impl CvgDest {
     }
 }
 
-#[repr(u32)]
-#[derive(Copy, Clone, Debug, Eq, PartialEq)]
-enum ZMode {
-    Opaque = 0,
-    Interpenetrating = 1,
-    Transparent = 2,
-    Decal = 3,
-}
-
 impl ZMode {
     const fn into_bits(self) -> u32 {
         self as u32
@@ -227,15 +382,6 @@ impl ZMode {
     }
 }
 
-#[repr(u32)]
-#[derive(Copy, Clone, Debug, Eq, PartialEq)]
-enum AlphaDitherSelect {
-    Pattern = 0,
-    PatternInverted = 1,
-    Noise = 2,
-    NoDither = 3,
-}
-
 impl AlphaDitherSelect {
     const fn into_bits(self) -> u32 {
         self as u32
@@ -251,15 +397,6 @@ impl AlphaDitherSelect {
     }
 }
 
-#[repr(u32)]
-#[derive(Copy, Clone, Debug, Eq, PartialEq)]
-enum RgbDitherSelect {
-    MagicSquare = 0,
-    Bayer = 1,
-    Noise = 2,
-    NoDither = 3,
-}
-
 impl RgbDitherSelect {
     const fn into_bits(self) -> u32 {
         self as u32
@@ -275,6 +412,19 @@ impl RgbDitherSelect {
     }
 }
 
+impl TlutType {
+    const fn into_bits(self) -> u32 {
+        self as u32
+    }
+
+    const fn from_bits(value: u32) -> Self {
+        match value & 1 {
+            0 => Self::Rgba16,
+            _ => Self::Ia16,
+        }
+    }
+}
+
 impl CycleType {
     const fn into_bits(self) -> u32 {
         unsafe { mem::transmute(self) }
@@ -1,6 +1,7 @@
-use super::renderer::FixedColor;
+use super::renderer::{FixedColor, KeyParams};
 use super::{Context, Decoder};
 use bitfield_struct::bitfield;
+use std::fmt::{self, Display};
 use tracing::trace;
 
 pub fn set_fog_color(_decoder: &mut Decoder, ctx: Context, word: u64) {
@@ -18,9 +19,10 @@ pub fn set_blend_color(_decoder: &mut Decoder, ctx: Context, word: u64) {
 pub fn set_prim_color(_decoder: &mut Decoder, ctx: Context, word: u64) {
     let cmd = SetPrimColor::from(word);
     trace!("{:?}", cmd);
-    // TODO: LOD params
     ctx.renderer
         .set_fixed_color(FixedColor::Primitive, cmd.color());
+    ctx.renderer
+        .set_prim_lod(cmd.prim_min_level(), cmd.prim_lod_frac());
 }
 
 pub fn set_env_color(_decoder: &mut Decoder, ctx: Context, word: u64) {
@@ -33,23 +35,68 @@ pub fn set_env_color(_decoder: &mut Decoder, ctx: Context, word: u64) {
 pub fn set_prim_depth(_decoder: &mut Decoder, ctx: Context, word: u64) {
     let cmd = SetPrimDepth::from(word);
     trace!("{:?}", cmd);
-    ctx.renderer.set_prim_depth(cmd.z() as i32 as f32 / 32768.0);
+    ctx.renderer.set_prim_depth(cmd.z(), cmd.delta_z());
+}
+
+pub fn set_key_gb(_decoder: &mut Decoder, ctx: Context, word: u64) {
+    let cmd = SetKeyGB::from(word);
+    trace!("{:?}", cmd);
+
+    ctx.renderer.set_key(
+        1,
+        KeyParams {
+            width: cmd.width_g(),
+            center: cmd.center_g(),
+            scale: cmd.scale_g(),
+        },
+    );
+
+    ctx.renderer.set_key(
+        2,
+        KeyParams {
+            width: cmd.width_b(),
+            center: cmd.center_b(),
+            scale: cmd.scale_b(),
+        },
+    );
+}
+
+pub fn set_key_r(_decoder: &mut Decoder, ctx: Context, word: u64) {
+    let cmd = SetKeyR::from(word);
+    trace!("{:?}", cmd);
+
+    ctx.renderer.set_key(
+        0,
+        KeyParams {
+            width: cmd.width_r(),
+            center: cmd.center_r(),
+            scale: cmd.scale_r(),
+        },
+    );
+}
+
+pub fn set_convert(_decoder: &mut Decoder, ctx: Context, word: u64) {
+    let cmd = SetConvert::from(word);
+    trace!("{:?}", cmd);
+
+    ctx.renderer
+        .set_convert([cmd.k0(), cmd.k1(), cmd.k2(), cmd.k3(), cmd.k4(), cmd.k5()]);
 }
 
 #[bitfield(u64)]
-struct SetFogColor {
+pub struct SetFogColor {
     color: u32,
     __: u32,
 }
 
 #[bitfield(u64)]
-struct SetBlendColor {
+pub struct SetBlendColor {
     color: u32,
     __: u32,
 }
 
 #[bitfield(u64)]
-struct SetPrimColor {
+pub struct SetPrimColor {
     color: u32,
     #[bits(8)]
     prim_lod_frac: u32,
@@ -60,14 +107,140 @@ struct SetPrimColor {
 }
 
 #[bitfield(u64)]
-struct SetEnvColor {
+pub struct SetEnvColor {
     color: u32,
     __: u32,
 }
 
 #[bitfield(u64)]
-struct SetPrimDepth {
+pub struct SetPrimDepth {
     delta_z: i16,
     z: i16,
     __: u32,
 }
+
+#[bitfield(u64)]
+pub struct SetKeyGB {
+    #[bits(8)]
+    scale_b: u32,
+    #[bits(8)]
+    center_b: u32,
+    #[bits(8)]
+    scale_g: u32,
+    #[bits(8)]
+    center_g: u32,
+    #[bits(12)]
+    width_b: u32,
+    #[bits(12)]
+    width_g: u32,
+    #[bits(8)]
+    __: u64,
+}
+
+#[bitfield(u64)]
+pub struct SetKeyR {
+    #[bits(8)]
+    scale_r: u32,
+    #[bits(8)]
+    center_r: u32,
+    #[bits(12)]
+    width_r: u32,
+    #[bits(36)]
+    __: u64,
+}
+
+#[bitfield(u64)]
+pub struct SetConvert {
+    #[bits(9)]
+    k5: i32,
+    #[bits(9)]
+    k4: i32,
+    #[bits(9)]
+    k3: i32,
+    #[bits(9)]
+    k2: i32,
+    #[bits(9)]
+    k1: i32,
+    #[bits(9)]
+    k0: i32,
+    #[bits(10)]
+    __: u64,
+}
+
+impl Display for SetFogColor {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "color={:08X}", self.color())
+    }
+}
+
+impl Display for SetBlendColor {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "color={:08X}", self.color())
+    }
+}
+
+impl Display for SetEnvColor {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "color={:08X}", self.color())
+    }
+}
+
+impl Display for SetPrimColor {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "color={:08X} min_level={} lod_frac={}",
+            self.color(),
+            self.prim_min_level(),
+            self.prim_lod_frac()
+        )
+    }
+}
+
+impl Display for SetPrimDepth {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "z={} delta_z={}", self.z(), self.delta_z())
+    }
+}
+
+impl Display for SetKeyGB {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "width_g={} center_g={} scale_g={} width_b={} center_b={} scale_b={}",
+            self.width_g(),
+            self.center_g(),
+            self.scale_g(),
+            self.width_b(),
+            self.center_b(),
+            self.scale_b()
+        )
+    }
+}
+
+impl Display for SetKeyR {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "width_r={} center_r={} scale_r={}",
+            self.width_r(),
+            self.center_r(),
+            self.scale_r()
+        )
+    }
+}
+
+impl Display for SetConvert {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "k0={} k1={} k2={} k3={} k4={} k5={}",
+            self.k0(),
+            self.k1(),
+            self.k2(),
+            self.k3(),
+            self.k4(),
+            self.k5()
+        )
+    }
+}
@@ -1,6 +1,7 @@
-use super::renderer::Rect;
+use super::renderer::{RectangleCoefficients, TexRectCoefficients};
 use super::{Context, Decoder};
 use bitfield_struct::bitfield;
+use std::fmt::{self, Display};
 use tracing::trace;
 
 pub fn rectangle<const TEXTURE: bool, const FLIP: bool>(
@@ -12,15 +13,6 @@ pub fn rectangle<const TEXTURE: bool, const FLIP: bool>(
 
     trace!("{:?}", cmd);
 
-    let rect = Rect {
-        left: cmd.xh() as f32 / 4.0,
-        right: (cmd.xl() as f32 / 4.0),
-        top: cmd.yh() as f32 / 4.0,
-        bottom: (cmd.yl() as f32 / 4.0),
-    };
-
-    trace!("  = {:?}", rect);
-
     let texture = if TEXTURE {
         let Some(arg) = decoder.commands.pop_front() else {
             decoder.commands.push_front(word);
@@ -31,42 +23,33 @@ pub fn rectangle<const TEXTURE: bool, const FLIP: bool>(
         let coords = TexCoords::from(arg);
         trace!("{:?}", coords);
 
-        let sh = coords.s() as i16 as f32 / 32.0;
-        let th = coords.t() as i16 as f32 / 32.0;
-        let dsdx = coords.dsdx() as i16 as f32 / 1024.0;
-        let dtdy = coords.dtdy() as f32 / 1024.0;
-
-        trace!(
-            "SH = {}, TH = {}, DSDX = {}, DTDY={}, FLIP={}",
-            sh,
-            th,
-            dsdx,
-            dtdy,
-            FLIP
-        );
-
-        let sl = sh + (rect.right - rect.left) * dsdx;
-        let tl = th + (rect.bottom - rect.top) * dtdy;
-
-        let tex_rect = Rect {
-            left: sh,
-            right: sl,
-            top: th,
-            bottom: tl,
-        };
-
-        trace!("  = {:?}", tex_rect);
-        Some((cmd.tile() as usize, tex_rect, FLIP))
+        Some(TexRectCoefficients {
+            tile: cmd.tile() as usize,
+            s: coords.s() as i16,
+            t: coords.t() as i16,
+            dsdx: coords.dsdx() as i16,
+            dtdy: coords.dtdy() as i16,
+            flip: FLIP,
+        })
     } else {
         None
     };
 
-    ctx.renderer
-        .draw_rectangle(ctx.gfx, ctx.rdram, rect, texture);
+    ctx.renderer.draw_rectangle(
+        ctx.gfx,
+        ctx.rdram,
+        &RectangleCoefficients {
+            xh: cmd.xh(),
+            yh: cmd.yh(),
+            xl: cmd.xl(),
+            yl: cmd.yl(),
+            texture,
+        },
+    );
 }
 
 #[bitfield(u64)]
-struct Rectangle {
+pub struct Rectangle {
     #[bits(12)]
     yh: u32,
     #[bits(12)]
@@ -84,9 +67,36 @@ struct Rectangle {
 }
 
 #[bitfield(u64)]
-struct TexCoords {
+pub struct TexCoords {
     dtdy: u16,
     dsdx: u16,
     t: u16,
     s: u16,
 }
+
+impl Display for Rectangle {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "tile={} xl={:.2} yl={:.2} xh={:.2} yh={:.2}",
+            self.tile(),
+            self.xl() as f32 / 4.0,
+            self.yl() as f32 / 4.0,
+            self.xh() as f32 / 4.0,
+            self.yh() as f32 / 4.0,
+        )
+    }
+}
+
+impl Display for TexCoords {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "s={:.5} t={:.5} dsdx={:.5} dtdy={:.5}",
+            self.s() as i16 as f32 / 32.0,
+            self.t() as i16 as f32 / 32.0,
+            self.dsdx() as i16 as f32 / 1024.0,
+            self.dtdy() as i16 as f32 / 1024.0,
+        )
+    }
+}
@@ -1,6 +1,7 @@
 use super::renderer::{ColorImage, Format, Rect};
 use super::{Context, Decoder};
 use bitfield_struct::bitfield;
+use std::fmt::{self, Display};
 use tracing::{trace, warn};
 
 pub fn set_scissor(_decoder: &mut Decoder, ctx: Context, word: u64) {
@@ -40,6 +41,12 @@ pub fn set_color_image(_decoder: &mut Decoder, ctx: Context, word: u64) {
     );
 }
 
+pub fn set_z_image(_decoder: &mut Decoder, ctx: Context, word: u64) {
+    let cmd = SetZImage::from(word);
+    trace!("{:?}", cmd);
+    ctx.renderer.set_z_image(cmd.dram_addr());
+}
+
 pub fn set_fill_color(_decoder: &mut Decoder, ctx: Context, word: u64) {
     let cmd = SetFillColor::from(word);
     trace!("{:?}", cmd);
@@ -47,8 +54,23 @@ pub fn set_fill_color(_decoder: &mut Decoder, ctx: Context, word: u64) {
         .set_fill_color(ctx.gfx, ctx.rdram, cmd.packed_color());
 }
 
+// The color image being drawn to, along with the number of lines covered by
+// the scissor region
+pub fn color_image_area(color_image: u64, scissor: u64) -> (ColorImage, u32) {
+    let cmd = SetColorImage::from(color_image);
+    let scissor = SetScissor::from(scissor);
+
+    let color_image = ColorImage {
+        dram_addr: cmd.dram_addr(),
+        width: cmd.width() + 1,
+        format: (cmd.format(), cmd.size()),
+    };
+
+    (color_image, scissor.yl().div_ceil(4))
+}
+
 #[bitfield(u64)]
-struct SetScissor {
+pub struct SetScissor {
     #[bits(12)]
     yl: u32,
     #[bits(12)]
@@ -66,7 +88,7 @@ struct SetScissor {
 }
 
 #[bitfield(u64)]
-struct SetColorImage {
+pub struct SetColorImage {
     #[bits(26)]
     dram_addr: u32,
     #[bits(6)]
@@ -84,7 +106,55 @@ struct SetColorImage {
 }
 
 #[bitfield(u64)]
-struct SetFillColor {
+pub struct SetZImage {
+    #[bits(26)]
+    dram_addr: u32,
+    #[bits(38)]
+    __: u64,
+}
+
+#[bitfield(u64)]
+pub struct SetFillColor {
     packed_color: u32,
     __: u32,
 }
+
+impl Display for SetScissor {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "xh={:.2} yh={:.2} xl={:.2} yl={:.2} field={} odd_line={}",
+            self.xh() as f32 / 4.0,
+            self.yh() as f32 / 4.0,
+            self.xl() as f32 / 4.0,
+            self.yl() as f32 / 4.0,
+            self.field(),
+            self.odd_line(),
+        )
+    }
+}
+
+impl Display for SetColorImage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "format={:?} size={}bpp width={} dram_addr={:06X}",
+            self.format(),
+            4 << self.size(),
+            self.width() + 1,
+            self.dram_addr(),
+        )
+    }
+}
+
+impl Display for SetZImage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "dram_addr={:06X}", self.dram_addr())
+    }
+}
+
+impl Display for SetFillColor {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "color={:08X}", self.packed_color())
+    }
+}
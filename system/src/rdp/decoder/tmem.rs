@@ -1,6 +1,7 @@
-use super::renderer::{Format, Rect, TextureImage, TileAddressMode, TileDescriptor};
+use super::renderer::{Format, TextureImage, TileAddressMode, TileDescriptor, TileSize};
 use super::{Context, Decoder};
 use bitfield_struct::bitfield;
+use std::fmt::{self, Display};
 use tracing::trace;
 
 pub fn set_texture_image(_decoder: &mut Decoder, ctx: Context, word: u64) {
@@ -49,15 +50,16 @@ pub fn set_tile_size(_decoder: &mut Decoder, ctx: Context, word: u64) {
 
     trace!("{:?}", cmd);
 
-    let rect = Rect {
-        left: cmd.sl() as f32 / 4.0,
-        right: cmd.sh() as f32 / 4.0 + 1.0,
-        top: cmd.tl() as f32 / 4.0,
-        bottom: cmd.th() as f32 / 4.0 + 1.0,
-    };
-
-    ctx.renderer
-        .set_tile_size(cmd.tile(), rect, word & 0x00ff_ffff_00ff_ffff);
+    ctx.renderer.set_tile_size(
+        cmd.tile(),
+        TileSize {
+            sl: cmd.sl(),
+            tl: cmd.tl(),
+            sh: cmd.sh(),
+            th: cmd.th(),
+        },
+        word & 0x00ff_ffff_00ff_ffff,
+    );
 }
 
 pub fn load_tile(_decoder: &mut Decoder, ctx: Context, word: u64) {
@@ -65,24 +67,17 @@ pub fn load_tile(_decoder: &mut Decoder, ctx: Context, word: u64) {
 
     trace!("{:?}", cmd);
 
-    let rect = Rect {
-        left: cmd.sl() as f32 / 4.0,
-        right: cmd.sh() as f32 / 4.0 + 1.0,
-        top: cmd.tl() as f32 / 4.0,
-        bottom: cmd.th() as f32 / 4.0 + 1.0,
-    };
-
-    ctx.renderer
-        .set_tile_size(cmd.tile(), rect, word & 0x00ff_ffff_00ff_ffff);
-
     ctx.renderer.load_tile(
         ctx.gfx,
         ctx.rdram,
         cmd.tile(),
-        cmd.sl() / 4,
-        ((cmd.sh() - cmd.sl()) / 4) + 1,
-        cmd.tl() / 4,
-        ((cmd.th() - cmd.tl()) / 4) + 1,
+        TileSize {
+            sl: cmd.sl(),
+            tl: cmd.tl(),
+            sh: cmd.sh(),
+            th: cmd.th(),
+        },
+        word & 0x00ff_ffff_00ff_ffff,
     );
 }
 
@@ -95,10 +90,26 @@ pub fn load_tlut(_decoder: &mut Decoder, ctx: Context, word: u64) {
         ctx.gfx,
         ctx.rdram,
         cmd.tile(),
-        cmd.sl() / 4,
-        ((cmd.sh() - cmd.sl()) / 4) + 1,
-        cmd.tl() / 4,
-        ((cmd.th() - cmd.tl()) / 4) + 1,
+        TileSize {
+            sl: cmd.sl(),
+            tl: cmd.tl(),
+            sh: cmd.sh(),
+            th: cmd.th(),
+        },
+    );
+}
+
+pub fn restore_tlut_size(_decoder: &mut Decoder, ctx: Context, word: u64) {
+    let cmd = LoadTlut::from(word);
+
+    ctx.renderer.set_tlut_size(
+        cmd.tile(),
+        TileSize {
+            sl: cmd.sl(),
+            tl: cmd.tl(),
+            sh: cmd.sh(),
+            th: cmd.th(),
+        },
     );
 }
 
@@ -107,34 +118,37 @@ pub fn load_block(_decoder: &mut Decoder, ctx: Context, word: u64) {
 
     trace!("{:?}", cmd);
 
-    let rect = Rect {
-        left: cmd.sl() as f32,
-        right: cmd.sh() as f32 + 1.0,
-        top: cmd.tl() as f32,
-        bottom: cmd.dxt() as f32 / 2048.0,
-    };
-
-    ctx.renderer.set_tile_size(
-        cmd.tile(),
-        rect,
-        // Add an extra bit to specify that tile size was set with LoadBlock
-        // command, due to its different parameter format
-        (word & 0x00ff_ffff_00ff_ffff) | 0x8000_0000_0000_0000,
-    );
-
     ctx.renderer.load_block(
         ctx.gfx,
         ctx.rdram,
         cmd.tile(),
-        cmd.sl(),
-        (cmd.sh() - cmd.sl()) + 1,
-        cmd.tl(),
-        cmd.dxt(),
+        TileSize {
+            sl: cmd.sl(),
+            tl: cmd.tl(),
+            sh: cmd.sh(),
+            th: cmd.dxt(),
+        },
+        word & 0x00ff_ffff_00ff_ffff,
+    );
+}
+
+pub fn restore_block_size(_decoder: &mut Decoder, ctx: Context, word: u64) {
+    let cmd = LoadBlock::from(word);
+
+    ctx.renderer.set_block_size(
+        cmd.tile(),
+        TileSize {
+            sl: cmd.sl(),
+            tl: cmd.tl(),
+            sh: cmd.sh(),
+            th: cmd.dxt(),
+        },
+        word & 0x00ff_ffff_00ff_ffff,
     );
 }
 
 #[bitfield(u64)]
-struct SetTextureImage {
+pub struct SetTextureImage {
     #[bits(26)]
     dram_addr: u32,
     #[bits(6)]
@@ -152,7 +166,7 @@ struct SetTextureImage {
 }
 
 #[bitfield(u64)]
-struct SetTile {
+pub struct SetTile {
     #[bits(4)]
     shift_s: u32,
     #[bits(4)]
@@ -185,7 +199,7 @@ struct SetTile {
 }
 
 #[bitfield(u64)]
-struct SetTileSize {
+pub struct SetTileSize {
     #[bits(12)]
     th: u32,
     #[bits(12)]
@@ -203,55 +217,148 @@ struct SetTileSize {
 }
 
 #[bitfield(u64)]
-struct LoadTile {
+pub struct LoadTile {
     #[bits(12)]
-    th: usize,
+    th: u32,
     #[bits(12)]
-    sh: usize,
+    sh: u32,
     #[bits(3)]
     tile: usize,
     #[bits(5)]
     __: u64,
     #[bits(12)]
-    tl: usize,
+    tl: u32,
     #[bits(12)]
-    sl: usize,
+    sl: u32,
     #[bits(8)]
     __: u64,
 }
 
 #[bitfield(u64)]
-struct LoadTlut {
+pub struct LoadTlut {
     #[bits(12)]
-    th: usize,
+    th: u32,
     #[bits(12)]
-    sh: usize,
+    sh: u32,
     #[bits(3)]
     tile: usize,
     #[bits(5)]
     __: u64,
     #[bits(12)]
-    tl: usize,
+    tl: u32,
     #[bits(12)]
-    sl: usize,
+    sl: u32,
     #[bits(8)]
     __: u64,
 }
 
 #[bitfield(u64)]
-struct LoadBlock {
+pub struct LoadBlock {
     #[bits(12)]
-    dxt: usize,
+    dxt: u32,
     #[bits(12)]
-    sh: usize,
+    sh: u32,
     #[bits(3)]
     tile: usize,
     #[bits(5)]
     __: u64,
     #[bits(12)]
-    tl: usize,
+    tl: u32,
     #[bits(12)]
-    sl: usize,
+    sl: u32,
     #[bits(8)]
     __: u64,
 }
+
+impl Display for SetTextureImage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "format={:?} size={}bpp width={} dram_addr={:06X}",
+            self.format(),
+            4 << self.size(),
+            self.width() + 1,
+            self.dram_addr(),
+        )
+    }
+}
+
+impl Display for SetTile {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "tile={} format={:?} size={}bpp line={} tmem_addr={:03X} palette={} \
+            s=(clamp={} mirror={} mask={} shift={}) t=(clamp={} mirror={} mask={} shift={})",
+            self.tile(),
+            self.format(),
+            4 << self.size(),
+            self.line(),
+            self.tmem_addr(),
+            self.palette(),
+            self.clamp_s(),
+            self.mirror_s(),
+            self.mask_s(),
+            self.shift_s(),
+            self.clamp_t(),
+            self.mirror_t(),
+            self.mask_t(),
+            self.shift_t(),
+        )
+    }
+}
+
+impl Display for LoadBlock {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "tile={} sl={} tl={} sh={} dxt={:03X}",
+            self.tile(),
+            self.sl(),
+            self.tl(),
+            self.sh(),
+            self.dxt(),
+        )
+    }
+}
+
+impl Display for SetTileSize {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "tile={} sl={:.2} tl={:.2} sh={:.2} th={:.2}",
+            self.tile(),
+            self.sl() as f32 / 4.0,
+            self.tl() as f32 / 4.0,
+            self.sh() as f32 / 4.0,
+            self.th() as f32 / 4.0,
+        )
+    }
+}
+
+impl Display for LoadTile {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "tile={} sl={:.2} tl={:.2} sh={:.2} th={:.2}",
+            self.tile(),
+            self.sl() as f32 / 4.0,
+            self.tl() as f32 / 4.0,
+            self.sh() as f32 / 4.0,
+            self.th() as f32 / 4.0,
+        )
+    }
+}
+
+impl Display for LoadTlut {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "tile={} sl={:.2} tl={:.2} sh={:.2} th={:.2}",
+            self.tile(),
+            self.sl() as f32 / 4.0,
+            self.tl() as f32 / 4.0,
+            self.sh() as f32 / 4.0,
+            self.th() as f32 / 4.0,
+        )
+    }
+}
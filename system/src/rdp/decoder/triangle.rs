@@ -1,6 +1,7 @@
+use super::renderer::{self, Coefficients, EdgeCoefficients, TriangleCoefficients};
 use super::{Context, Decoder};
 use bitfield_struct::bitfield;
-use std::array;
+use std::fmt::{self, Display};
 use tracing::trace;
 
 pub fn triangle<const SHADE: bool, const TEXTURE: bool, const Z_BUFFER: bool>(
@@ -28,27 +29,26 @@ pub fn triangle<const SHADE: bool, const TEXTURE: bool, const Z_BUFFER: bool>(
     trace!("H {:?}", edge_high);
     trace!("M {:?}", edge_mid);
 
-    let yh = cmd.yh() as f32 / 4.0;
-    let ym = cmd.ym() as f32 / 4.0;
-    let yl = cmd.yl() as f32 / 4.0;
-    let xh = edge_high.x() as f32 / 65536.0;
-    let xl = edge_low.x() as f32 / 65536.0;
-    let dxhdy = edge_high.dxdy() as f32 / 65536.0;
-
-    let high_y = yh - yh.floor();
-    let mid_y = ym - yh.floor();
-    let mid_x = xl - (xh + mid_y * dxhdy);
-    let low_y = yl - yh.floor();
-
-    let edges: [[f32; 2]; 3] = [
-        [xh + high_y * dxhdy, yh],
-        [xl, ym],
-        [xh + low_y * dxhdy, yl],
-    ];
-
-    trace!("  = {:?}", edges);
+    let edges = EdgeCoefficients {
+        left_major: cmd.right(),
+        yh: cmd.yh(),
+        ym: cmd.ym(),
+        yl: cmd.yl(),
+        high: renderer::Edge {
+            x: edge_high.x(),
+            dxdy: edge_high.dxdy(),
+        },
+        mid: renderer::Edge {
+            x: edge_mid.x(),
+            dxdy: edge_mid.dxdy(),
+        },
+        low: renderer::Edge {
+            x: edge_low.x(),
+            dxdy: edge_low.dxdy(),
+        },
+    };
 
-    let colors: [[f32; 4]; 3] = if SHADE {
+    let shade = SHADE.then(|| {
         let shade = Color::from(decoder.commands.pop_front().unwrap());
         let shade_dx = Color::from(decoder.commands.pop_front().unwrap());
         let shade_frac = Color::from(decoder.commands.pop_front().unwrap());
@@ -60,34 +60,21 @@ pub fn triangle<const SHADE: bool, const TEXTURE: bool, const Z_BUFFER: bool>(
         trace!("Shade: {:?}", shade);
         trace!("Shade DX: {:?}", shade_dx);
         trace!("Shade Frac: {:?}", shade_frac);
-        trace!("Shade Frac DY: {:?}", shade_frac_dx);
+        trace!("Shade Frac DX: {:?}", shade_frac_dx);
         trace!("Shade DE: {:?}", shade_de);
-        trace!("Shade DX: {:?}", shade_dy);
+        trace!("Shade DY: {:?}", shade_dy);
         trace!("Shade Frac DE: {:?}", shade_frac_de);
         trace!("Shade Frac DY: {:?}", shade_frac_dy);
 
-        let base_color = decode_color(shade, shade_frac);
-        let color_dx = decode_color(shade_dx, shade_frac_dx);
-        let color_de = decode_color(shade_de, shade_frac_de);
-        let color_dy = decode_color(shade_dy, shade_frac_dy);
-        trace!("Base Color: {:?}", base_color);
-        trace!("Color DX: {:?}", color_dx);
-        trace!("Color DE: {:?}", color_de);
-        trace!("Color DY: {:?}", color_dy);
-
-        let colors: [[f32; 4]; 3] = [
-            array::from_fn(|i| (base_color[i] + high_y * color_de[i]) / 255.0),
-            array::from_fn(|i| (base_color[i] + mid_y * color_de[i] + mid_x * color_dx[i]) / 255.0),
-            array::from_fn(|i| (base_color[i] + low_y * color_de[i]) / 255.0),
-        ];
-
-        trace!("  = {:?}", colors);
-        colors
-    } else {
-        [[0.0; 4]; 3]
-    };
+        Coefficients {
+            base: decode_color(shade, shade_frac),
+            dx: decode_color(shade_dx, shade_frac_dx),
+            de: decode_color(shade_de, shade_frac_de),
+            dy: decode_color(shade_dy, shade_frac_dy),
+        }
+    });
 
-    let texture = if TEXTURE {
+    let texture = TEXTURE.then(|| {
         let coord = TexCoord::from(decoder.commands.pop_front().unwrap());
         let coord_dx = TexCoord::from(decoder.commands.pop_front().unwrap());
         let coord_frac = TexCoord::from(decoder.commands.pop_front().unwrap());
@@ -99,77 +86,72 @@ pub fn triangle<const SHADE: bool, const TEXTURE: bool, const Z_BUFFER: bool>(
         trace!("Texture: {:?}", coord);
         trace!("Texture DX: {:?}", coord_dx);
         trace!("Texture Frac: {:?}", coord_frac);
-        trace!("Texture Frac DY: {:?}", coord_frac_dx);
+        trace!("Texture Frac DX: {:?}", coord_frac_dx);
         trace!("Texture DE: {:?}", coord_de);
-        trace!("Texture DX: {:?}", coord_dy);
+        trace!("Texture DY: {:?}", coord_dy);
         trace!("Texture Frac DE: {:?}", coord_frac_de);
         trace!("Texture Frac DY: {:?}", coord_frac_dy);
 
-        let base_texel = decode_tex_coord(coord, coord_frac);
-        let texel_dx = decode_tex_coord(coord_dx, coord_frac_dx);
-        let texel_de = decode_tex_coord(coord_de, coord_frac_de);
-        let texel_dy = decode_tex_coord(coord_dy, coord_frac_dy);
-        trace!("Base Texel: {:?}", base_texel);
-        trace!("Texel DX: {:?}", texel_dx);
-        trace!("Texel DE: {:?}", texel_de);
-        trace!("Texel DY: {:?}", texel_dy);
-
-        let tex_coords: [[f32; 3]; 3] = [
-            array::from_fn(|i| base_texel[i] + high_y * texel_de[i]),
-            array::from_fn(|i| base_texel[i] + mid_y * texel_de[i] + mid_x * texel_dx[i]),
-            array::from_fn(|i| base_texel[i] + low_y * texel_de[i]),
-        ];
-
-        trace!("  = {:?}", tex_coords);
-        Some((cmd.tile() as usize, tex_coords))
-    } else {
-        None
-    };
+        let coeffs = Coefficients {
+            base: decode_tex_coord(coord, coord_frac),
+            dx: decode_tex_coord(coord_dx, coord_frac_dx),
+            de: decode_tex_coord(coord_de, coord_frac_de),
+            dy: decode_tex_coord(coord_dy, coord_frac_dy),
+        };
 
-    let z_values: [f32; 3] = if Z_BUFFER {
+        (cmd.tile() as usize, coeffs)
+    });
+
+    let z = Z_BUFFER.then(|| {
         let z_dzdx_word = decoder.commands.pop_front().unwrap();
         let dzde_dzdy_word = decoder.commands.pop_front().unwrap();
 
-        let z = ((z_dzdx_word >> 32) as u32 as i32) as f32 / 65536.0;
-        let dzdx = (z_dzdx_word as u32 as i32) as f32 / 65536.0;
-        let dzde = ((dzde_dzdy_word >> 32) as u32 as i32) as f32 / 65536.0;
-        let dzdy = (dzde_dzdy_word as u32 as i32) as f32 / 65536.0;
+        let z = (z_dzdx_word >> 32) as u32 as i32;
+        let dzdx = z_dzdx_word as u32 as i32;
+        let dzde = (dzde_dzdy_word >> 32) as u32 as i32;
+        let dzdy = dzde_dzdy_word as u32 as i32;
         trace!("Z: {}, DZDX: {}, DZDE: {}, DZDY: {}", z, dzdx, dzde, dzdy);
 
-        let z_values = [
-            (z + high_y * dzde) / 32768.0,
-            (z + mid_y * dzde + mid_x * dzdx) / 32768.0,
-            (z + low_y * dzde) / 32768.0,
-        ];
-
-        trace!("  = {:?}", z_values);
-        z_values
-    } else {
-        [0.0; 3]
-    };
+        Coefficients {
+            base: [z],
+            dx: [dzdx],
+            de: [dzde],
+            dy: [dzdy],
+        }
+    });
 
-    ctx.renderer
-        .draw_triangle(ctx.gfx, ctx.rdram, edges, colors, texture, z_values);
+    ctx.renderer.draw_triangle(
+        ctx.gfx,
+        ctx.rdram,
+        &TriangleCoefficients {
+            edges,
+            shade,
+            texture,
+            z,
+            max_level: cmd.level(),
+        },
+    );
 }
 
-fn decode_color(integer: Color, fraction: Color) -> [f32; 4] {
+fn decode_color(integer: Color, fraction: Color) -> [i32; 4] {
     [
-        ((integer.r() << 16) | fraction.r()) as i32 as f32 / 65536.0,
-        ((integer.g() << 16) | fraction.g()) as i32 as f32 / 65536.0,
-        ((integer.b() << 16) | fraction.b()) as i32 as f32 / 65536.0,
-        ((integer.a() << 16) | fraction.a()) as i32 as f32 / 65536.0,
+        ((integer.r() << 16) | fraction.r()) as i32,
+        ((integer.g() << 16) | fraction.g()) as i32,
+        ((integer.b() << 16) | fraction.b()) as i32,
+        ((integer.a() << 16) | fraction.a()) as i32,
     ]
 }
 
-fn decode_tex_coord(integer: TexCoord, fraction: TexCoord) -> [f32; 3] {
+fn decode_tex_coord(integer: TexCoord, fraction: TexCoord) -> [i32; 3] {
     [
-        ((integer.s() << 16) | fraction.s()) as i32 as f32 / 65536.0 / 32.0,
-        ((integer.t() << 16) | fraction.t()) as i32 as f32 / 65536.0 / 32.0,
-        ((integer.w() << 16) | fraction.w()) as i32 as f32 / 65536.0 / 32.0,
+        ((integer.s() << 16) | fraction.s()) as i32,
+        ((integer.t() << 16) | fraction.t()) as i32,
+        ((integer.w() << 16) | fraction.w()) as i32,
     ]
 }
+
 #[bitfield(u64)]
-struct Triangle {
+pub struct Triangle {
     #[bits(14)]
     yh: i32,
     #[bits(2)]
@@ -193,7 +175,7 @@ struct Triangle {
 }
 
 #[bitfield(u64)]
-struct Edge {
+pub struct Edge {
     #[bits(30)]
     dxdy: i32,
     #[bits(2)]
@@ -205,7 +187,7 @@ struct Edge {
 }
 
 #[bitfield(u64)]
-struct Color {
+pub struct Color {
     #[bits(16)]
     a: u32,
     #[bits(16)]
@@ -217,7 +199,7 @@ struct Color {
 }
 
 #[bitfield(u64)]
-struct TexCoord {
+pub struct TexCoord {
     #[bits(16)]
     __: u32,
     #[bits(16)]
@@ -227,3 +209,54 @@ struct TexCoord {
     #[bits(16)]
     s: u32,
 }
+
+impl Display for Triangle {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "left_major={} tile={} level={} yl={:.2} ym={:.2} yh={:.2}",
+            self.right(),
+            self.tile(),
+            self.level(),
+            self.yl() as f32 / 4.0,
+            self.ym() as f32 / 4.0,
+            self.yh() as f32 / 4.0,
+        )
+    }
+}
+
+impl Display for Edge {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "x={:.4} dxdy={:.4}",
+            self.x() as f64 / 65536.0,
+            self.dxdy() as f64 / 65536.0,
+        )
+    }
+}
+
+impl Display for Color {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "r={:04X} g={:04X} b={:04X} a={:04X}",
+            self.r(),
+            self.g(),
+            self.b(),
+            self.a()
+        )
+    }
+}
+
+impl Display for TexCoord {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "s={:04X} t={:04X} w={:04X}",
+            self.s(),
+            self.t(),
+            self.w()
+        )
+    }
+}
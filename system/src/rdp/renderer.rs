@@ -6,10 +6,13 @@ pub use tmem::{TextureImage, TileAddressMode, TileDescriptor};
 
 use crate::gfx::GfxContext;
 use crate::rdram::Rdram;
+use crate::snapshot::{Reader, Writer};
 use blender::BlendMode;
 use combiner::CombineMode;
 use display_list::DisplayList;
 use pipeline::{Pipeline, PipelineSpec};
+use std::array;
+use std::error::Error;
 use target::Target;
 use tmem::Tmem;
 use tracing::trace;
@@ -60,7 +63,55 @@ pub enum SampleType {
     Bilinear = 1,
 }
 
-#[derive(Debug, Default, Eq, PartialEq)]
+#[repr(u32)]
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum CvgDest {
+    #[default]
+    Clamp = 0,
+    Wrap = 1,
+    Zap = 2,
+    Save = 3,
+}
+
+#[repr(u32)]
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum ZMode {
+    #[default]
+    Opaque = 0,
+    Interpenetrating = 1,
+    Transparent = 2,
+    Decal = 3,
+}
+
+#[repr(u32)]
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum AlphaDitherSelect {
+    #[default]
+    Pattern = 0,
+    PatternInverted = 1,
+    Noise = 2,
+    NoDither = 3,
+}
+
+#[repr(u32)]
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum RgbDitherSelect {
+    #[default]
+    MagicSquare = 0,
+    Bayer = 1,
+    Noise = 2,
+    NoDither = 3,
+}
+
+#[repr(u32)]
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum TlutType {
+    #[default]
+    Rgba16 = 0,
+    Ia16 = 1,
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
 pub struct OtherModes {
     pub cycle_type: CycleType,
     pub sample_type: SampleType,
@@ -68,7 +119,112 @@ pub struct OtherModes {
     pub z_compare_en: bool,
     pub z_update_en: bool,
     pub z_source: ZSource,
+    pub z_mode: ZMode,
     pub blend_mode: BlendModeRaw,
+    pub alpha_compare_en: bool,
+    pub dither_alpha_en: bool,
+    pub antialias_en: bool,
+    pub image_read_en: bool,
+    pub color_on_cvg: bool,
+    pub cvg_dest: CvgDest,
+    pub cvg_times_alpha: bool,
+    pub alpha_cvg_select: bool,
+    pub force_blend: bool,
+    pub alpha_dither_sel: AlphaDitherSelect,
+    pub rgb_dither_sel: RgbDitherSelect,
+    pub key_en: bool,
+    // Per cycle: whether the texture filter filters texels (rather than
+    // converting them from YUV)
+    pub bi_lerp: [bool; 2],
+    pub mid_texel: bool,
+    pub en_tlut: bool,
+    pub tlut_type: TlutType,
+    pub tex_lod_en: bool,
+    pub sharpen_tex_en: bool,
+    pub detail_tex_en: bool,
+}
+
+// Edge, shade, texture and Z values are passed to the renderers in the same
+// fixed-point formats used by the RDP triangle commands, so that each renderer
+// can decide for itself how precisely to interpret them
+
+#[derive(Clone, Debug, Default)]
+pub struct Edge {
+    // s15.16
+    pub x: i32,
+    pub dxdy: i32,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct EdgeCoefficients {
+    pub left_major: bool,
+    // s11.2
+    pub yh: i32,
+    pub ym: i32,
+    pub yl: i32,
+    pub high: Edge,
+    pub mid: Edge,
+    pub low: Edge,
+}
+
+#[derive(Clone, Debug)]
+pub struct Coefficients<const N: usize> {
+    pub base: [i32; N],
+    pub dx: [i32; N],
+    pub de: [i32; N],
+    pub dy: [i32; N],
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct TriangleCoefficients {
+    pub edges: EdgeCoefficients,
+    // s15.16 (RGBA)
+    pub shade: Option<Coefficients<4>>,
+    // s10.21 (STW), plus tile index
+    pub texture: Option<(usize, Coefficients<3>)>,
+    // s15.16
+    pub z: Option<Coefficients<1>>,
+    // Number of mipmap levels after the first
+    pub max_level: u32,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct TexRectCoefficients {
+    pub tile: usize,
+    // s10.5
+    pub s: i16,
+    pub t: i16,
+    // s5.10
+    pub dsdx: i16,
+    pub dtdy: i16,
+    pub flip: bool,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct RectangleCoefficients {
+    // u10.2
+    pub xh: u32,
+    pub yh: u32,
+    pub xl: u32,
+    pub yl: u32,
+    pub texture: Option<TexRectCoefficients>,
+}
+
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct KeyParams {
+    // u4.8
+    pub width: u32,
+    pub center: u32,
+    pub scale: u32,
+}
+
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct TileSize {
+    // u10.2 (for LoadBlock, SL/SH/TL are in texels and TH is DXT)
+    pub sl: u32,
+    pub tl: u32,
+    pub sh: u32,
+    pub th: u32,
 }
 
 pub struct Renderer {
@@ -124,6 +280,16 @@ impl Renderer {
         }
     }
 
+    // Only TMEM contents need saving. Everything else is rebuilt by replaying
+    // the decoder's state commands, and the target is synced to RDRAM.
+    pub fn save_state(&self, writer: &mut Writer) {
+        writer.bytes(bytemuck::cast_slice(self.tmem.data()));
+    }
+
+    pub fn load_state(&mut self, reader: &mut Reader) -> Result<(), Box<dyn Error>> {
+        reader.bytes_into(bytemuck::cast_slice_mut(self.tmem.data_mut()))
+    }
+
     pub fn set_color_image(
         &mut self,
         gfx: &GfxContext,
@@ -190,64 +356,106 @@ impl Renderer {
         self.tmem.set_tile(index, tile, hash_value);
     }
 
-    pub fn set_tile_size(&mut self, index: usize, rect: Rect, hash_value: u64) {
+    pub fn set_tile_size(&mut self, index: usize, size: TileSize, hash_value: u64) {
+        let rect = Rect {
+            left: size.sl as f32 / 4.0,
+            right: size.sh as f32 / 4.0 + 1.0,
+            top: size.tl as f32 / 4.0,
+            bottom: size.th as f32 / 4.0 + 1.0,
+        };
+
         self.tmem.set_tile_size(index, rect, hash_value);
     }
 
-    #[allow(clippy::too_many_arguments)]
     pub fn load_tile(
         &mut self,
         gfx: &GfxContext,
         rdram: &mut Rdram,
         index: usize,
-        x_offset: usize,
-        x_size: usize,
-        y_offset: usize,
-        y_size: usize,
+        size: TileSize,
+        hash_value: u64,
     ) {
+        self.set_tile_size(index, size, hash_value);
         self.flush(gfx, rdram);
-        self.tmem
-            .load_tile(rdram, index, x_offset, x_size, y_offset, y_size);
+
+        let (sl, tl, sh, th) = (
+            size.sl as usize,
+            size.tl as usize,
+            size.sh as usize,
+            size.th as usize,
+        );
+
+        self.tmem.load_tile(
+            rdram,
+            index,
+            sl / 4,
+            ((sh - sl) / 4) + 1,
+            tl / 4,
+            ((th - tl) / 4) + 1,
+        );
     }
 
-    #[allow(clippy::too_many_arguments)]
-    pub fn load_tlut(
-        &mut self,
-        gfx: &GfxContext,
-        rdram: &mut Rdram,
-        index: usize,
-        x_offset: usize,
-        x_size: usize,
-        y_offset: usize,
-        y_size: usize,
-    ) {
+    pub fn load_tlut(&mut self, gfx: &GfxContext, rdram: &mut Rdram, index: usize, size: TileSize) {
         self.flush(gfx, rdram);
-        self.tmem
-            .load_tlut(rdram, index, x_offset, x_size, y_offset, y_size);
+
+        let (sl, tl, sh, th) = (
+            size.sl as usize,
+            size.tl as usize,
+            size.sh as usize,
+            size.th as usize,
+        );
+
+        self.tmem.load_tlut(
+            rdram,
+            index,
+            sl / 4,
+            ((sh - sl) / 4) + 1,
+            tl / 4,
+            ((th - tl) / 4) + 1,
+        );
     }
 
-    #[allow(clippy::too_many_arguments)]
     pub fn load_block(
         &mut self,
         gfx: &GfxContext,
         rdram: &mut Rdram,
         index: usize,
-        x_offset: usize,
-        x_size: usize,
-        y_offset: usize,
-        y_delta: usize,
+        size: TileSize,
+        hash_value: u64,
     ) {
+        self.set_block_size(index, size, hash_value);
         self.flush(gfx, rdram);
+
+        self.tmem.load_block(
+            rdram,
+            index,
+            size.sl as usize,
+            (size.sh - size.sl) as usize + 1,
+            size.tl as usize,
+            size.th as usize,
+        );
+    }
+
+    pub fn set_block_size(&mut self, index: usize, size: TileSize, hash_value: u64) {
+        let rect = Rect {
+            left: size.sl as f32,
+            right: size.sh as f32 + 1.0,
+            top: size.tl as f32,
+            bottom: size.th as f32 / 2048.0,
+        };
+
+        // Add an extra bit to specify that tile size was set with LoadBlock
+        // command, due to its different parameter format
         self.tmem
-            .load_block(rdram, index, x_offset, x_size, y_offset, y_delta);
+            .set_tile_size(index, rect, hash_value | 0x8000_0000_0000_0000);
     }
 
     pub fn set_fixed_color(&mut self, color: FixedColor, value: u32) {
         self.display_list.set_fixed_color(color, value);
     }
 
-    pub fn set_prim_depth(&mut self, prim_depth: f32) {
-        self.prim_depth = prim_depth;
+    pub fn set_prim_depth(&mut self, z: i16, _delta_z: i16) {
+        self.prim_depth = z as i32 as f32 / 32768.0;
         trace!("  Prim Depth: {}", self.prim_depth);
     }
 
@@ -255,11 +463,86 @@ impl Renderer {
         &mut self,
         gfx: &GfxContext,
         rdram: &mut Rdram,
-        edges: [[f32; 2]; 3],
-        colors: [[f32; 4]; 3],
-        texture: Option<(usize, [[f32; 3]; 3])>,
-        z_values: [f32; 3],
+        triangle: &TriangleCoefficients,
     ) {
+        let edge_coeffs = &triangle.edges;
+        let yh = edge_coeffs.yh as f32 / 4.0;
+        let ym = edge_coeffs.ym as f32 / 4.0;
+        let yl = edge_coeffs.yl as f32 / 4.0;
+        let xh = edge_coeffs.high.x as f32 / 65536.0;
+        let xl = edge_coeffs.low.x as f32 / 65536.0;
+        let dxhdy = edge_coeffs.high.dxdy as f32 / 65536.0;
+
+        let high_y = yh - yh.floor();
+        let mid_y = ym - yh.floor();
+        let mid_x = xl - (xh + mid_y * dxhdy);
+        let low_y = yl - yh.floor();
+
+        let edges: [[f32; 2]; 3] = [
+            [xh + high_y * dxhdy, yh],
+            [xl, ym],
+            [xh + low_y * dxhdy, yl],
+        ];
+
+        trace!("  = {:?}", edges);
+
+        let colors: [[f32; 4]; 3] = if let Some(shade) = &triangle.shade {
+            let base_color = shade.base.map(|value| value as f32 / 65536.0);
+            let color_dx = shade.dx.map(|value| value as f32 / 65536.0);
+            let color_de = shade.de.map(|value| value as f32 / 65536.0);
+            trace!("Base Color: {:?}", base_color);
+            trace!("Color DX: {:?}", color_dx);
+            trace!("Color DE: {:?}", color_de);
+
+            let colors: [[f32; 4]; 3] = [
+                array::from_fn(|i| (base_color[i] + high_y * color_de[i]) / 255.0),
+                array::from_fn(|i| {
+                    (base_color[i] + mid_y * color_de[i] + mid_x * color_dx[i]) / 255.0
+                }),
+                array::from_fn(|i| (base_color[i] + low_y * color_de[i]) / 255.0),
+            ];
+
+            trace!("  = {:?}", colors);
+            colors
+        } else {
+            [[0.0; 4]; 3]
+        };
+
+        let texture = triangle.texture.as_ref().map(|(tile_id, coords)| {
+            let base_texel = coords.base.map(|value| value as f32 / 65536.0 / 32.0);
+            let texel_dx = coords.dx.map(|value| value as f32 / 65536.0 / 32.0);
+            let texel_de = coords.de.map(|value| value as f32 / 65536.0 / 32.0);
+            trace!("Base Texel: {:?}", base_texel);
+            trace!("Texel DX: {:?}", texel_dx);
+            trace!("Texel DE: {:?}", texel_de);
+
+            let tex_coords: [[f32; 3]; 3] = [
+                array::from_fn(|i| base_texel[i] + high_y * texel_de[i]),
+                array::from_fn(|i| base_texel[i] + mid_y * texel_de[i] + mid_x * texel_dx[i]),
+                array::from_fn(|i| base_texel[i] + low_y * texel_de[i]),
+            ];
+
+            trace!("  = {:?}", tex_coords);
+            (*tile_id, tex_coords)
+        });
+
+        let z_values: [f32; 3] = if let Some(z_coeffs) = &triangle.z {
+            let z = z_coeffs.base[0] as f32 / 65536.0;
+            let dzdx = z_coeffs.dx[0] as f32 / 65536.0;
+            let dzde = z_coeffs.de[0] as f32 / 65536.0;
+
+            let z_values = [
+                (z + high_y * dzde) / 32768.0,
+                (z + mid_y * dzde + mid_x * dzdx) / 32768.0,
+                (z + low_y * dzde) / 32768.0,
+            ];
+
+            trace!("  = {:?}", z_values);
+            z_values
+        } else {
+            [0.0; 3]
+        };
+
         let texture = texture.and_then(|(tile_id, mut coords)| {
             // Make texture coordinates relative to tile origin
             let tile_size = self.tmem.tile_size(tile_id);
@@ -298,9 +581,37 @@ impl Renderer {
         &mut self,
         gfx: &GfxContext,
         rdram: &mut Rdram,
-        mut rect: Rect,
-        texture: Option<(usize, Rect, bool)>,
+        rectangle: &RectangleCoefficients,
     ) {
+        let mut rect = Rect {
+            left: rectangle.xh as f32 / 4.0,
+            right: rectangle.xl as f32 / 4.0,
+            top: rectangle.yh as f32 / 4.0,
+            bottom: rectangle.yl as f32 / 4.0,
+        };
+
+        trace!("  = {:?}", rect);
+
+        let texture = rectangle.texture.as_ref().map(|coords| {
+            let sh = coords.s as f32 / 32.0;
+            let th = coords.t as f32 / 32.0;
+            let dsdx = coords.dsdx as f32 / 1024.0;
+            let dtdy = coords.dtdy as u16 as f32 / 1024.0;
+
+            let sl = sh + (rect.right - rect.left) * dsdx;
+            let tl = th + (rect.bottom - rect.top) * dtdy;
+
+            let tex_rect = Rect {
+                left: sh,
+                right: sl,
+                top: th,
+                bottom: tl,
+            };
+
+            trace!("  = {:?}", tex_rect);
+            (coords.tile, tex_rect, coords.flip)
+        });
+
         // TODO: Proper blending
         let color = self.blend_color;
 
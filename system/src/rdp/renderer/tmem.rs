@@ -136,6 +136,17 @@ impl Tmem {
         trace!("  Tile {} Hash Value: {:032X}", tile_id, tile.hash_value);
     }
 
+    pub fn data(&self) -> &[u64] {
+        &self.tmem_data
+    }
+
+    pub fn data_mut(&mut self) -> &mut [u64] {
+        // Cached textures may no longer match TMEM contents
+        self.texture_cache.clear();
+        self.tile_view_cache.clear();
+        &mut self.tmem_data
+    }
+
     pub fn tile_size(&self, tile_id: usize) -> &Rect {
         &self.tiles[tile_id].size
     }
@@ -0,0 +1,749 @@
+use super::renderer::{
+    BlendModeRawParams, ColorImage, CombineModeRaw, CvgDest, CycleType, FixedColor, Format,
+    KeyParams, OtherModes, Rect, RectangleCoefficients, RgbDitherSelect, SampleType, TextureImage,
+    TileAddressMode, TileDescriptor, TileSize, TlutType, TriangleCoefficients, ZMode, ZSource,
+};
+use crate::rdram::Rdram;
+use crate::snapshot::{Reader, Writer};
+use blender::BlendInputs;
+use combiner::CombineInputs;
+use raster::{Fragment, Scissor};
+use std::error::Error;
+use target::{MemoryPixel, ZValue};
+use tmem::Tmem;
+use tracing::trace;
+
+mod blender;
+mod combiner;
+mod raster;
+mod target;
+mod tmem;
+
+const MAGIC_SQUARE_MATRIX: [u32; 16] = [0, 6, 1, 7, 4, 2, 5, 3, 3, 5, 2, 4, 7, 1, 6, 0];
+const BAYER_MATRIX: [u32; 16] = [0, 4, 1, 5, 4, 0, 5, 1, 3, 7, 2, 6, 7, 3, 6, 2];
+
+pub struct SoftwareRenderer {
+    color_image: ColorImage,
+    z_image: u32,
+    scissor: Scissor,
+    fill_color: u32,
+    combine_mode: CombineModeRaw,
+    other_modes: OtherModes,
+    fixed_colors: [[i32; 4]; 4],
+    prim_min_level: i32,
+    prim_lod_frac: i32,
+    prim_depth: (u32, u32),
+    key: [KeyParams; 3],
+    convert: [i32; 6],
+    tmem: Tmem,
+    noise: u32,
+}
+
+impl SoftwareRenderer {
+    pub fn new() -> Self {
+        Self {
+            color_image: ColorImage::default(),
+            z_image: 0,
+            scissor: Scissor::default(),
+            fill_color: 0,
+            combine_mode: CombineModeRaw::default(),
+            other_modes: OtherModes::default(),
+            fixed_colors: [[0; 4]; 4],
+            prim_min_level: 0,
+            prim_lod_frac: 0,
+            prim_depth: (0, 0),
+            key: Default::default(),
+            convert: [0; 6],
+            tmem: Tmem::new(),
+            noise: 1,
+        }
+    }
+
+    // As with the hardware renderer, everything other than TMEM contents and
+    // the noise generator is rebuilt from the decoder's state commands
+    pub fn save_state(&self, writer: &mut Writer) {
+        writer.bytes(self.tmem.data());
+        writer.u32(self.noise);
+    }
+
+    pub fn load_state(&mut self, reader: &mut Reader) -> Result<(), Box<dyn Error>> {
+        reader.bytes_into(self.tmem.data_mut())?;
+        self.noise = reader.u32()?;
+        Ok(())
+    }
+
+    pub fn set_color_image(&mut self, color_image: ColorImage) {
+        self.color_image = color_image;
+        trace!("  Color Image: {:?}", self.color_image);
+    }
+
+    pub fn set_z_image(&mut self, dram_addr: u32) {
+        self.z_image = dram_addr;
+        trace!("  Z Image: {:08X}", self.z_image);
+    }
+
+    pub fn set_scissor(&mut self, scissor: Rect) {
+        self.scissor = Scissor {
+            xh: (scissor.left * 4.0) as i32,
+            yh: (scissor.top * 4.0) as i32,
+            xl: (scissor.right * 4.0) as i32,
+            yl: (scissor.bottom * 4.0) as i32,
+        };
+
+        trace!("  Scissor: {:?}", self.scissor);
+    }
+
+    pub fn set_fill_color(&mut self, value: u32) {
+        self.fill_color = value;
+        trace!("  Fill Color: {:08X}", self.fill_color);
+    }
+
+    pub fn set_combine_mode(&mut self, combine_mode: CombineModeRaw) {
+        self.combine_mode = combine_mode;
+        trace!("  Combine Mode: {:?}", self.combine_mode);
+    }
+
+    pub fn set_other_modes(&mut self, other_modes: OtherModes) {
+        self.other_modes = other_modes;
+        trace!("  Other Modes: {:?}", self.other_modes);
+    }
+
+    pub fn set_texture_image(&mut self, texture_image: TextureImage) {
+        self.tmem.set_texture_image(texture_image);
+    }
+
+    pub fn set_tile(&mut self, index: usize, descriptor: TileDescriptor) {
+        self.tmem.set_tile(index, descriptor);
+    }
+
+    pub fn set_tile_size(&mut self, index: usize, size: TileSize) {
+        self.tmem.set_tile_size(index, size);
+    }
+
+    pub fn load_tile(&mut self, rdram: &Rdram, index: usize, size: TileSize) {
+        self.tmem.set_tile_size(index, size);
+        self.tmem.load_tile(rdram, index);
+    }
+
+    pub fn load_tlut(&mut self, rdram: &Rdram, index: usize, size: TileSize) {
+        self.tmem.set_tile_size(index, size);
+        self.tmem.load_tlut(rdram, index);
+    }
+
+    pub fn load_block(&mut self, rdram: &Rdram, index: usize, size: TileSize) {
+        self.tmem.set_tile_size(index, size);
+        self.tmem.load_block(rdram, index);
+    }
+
+    pub fn set_fixed_color(&mut self, color: FixedColor, value: u32) {
+        self.fixed_colors[color as usize] = [
+            (value >> 24) as i32,
+            ((value >> 16) & 0xff) as i32,
+            ((value >> 8) & 0xff) as i32,
+            (value & 0xff) as i32,
+        ];
+
+        trace!("  {:?} Color: {:08X}", color, value);
+    }
+
+    pub fn set_prim_lod(&mut self, min_level: u32, lod_frac: u32) {
+        self.prim_min_level = min_level as i32;
+        self.prim_lod_frac = lod_frac as i32;
+        trace!("  Prim Min Level: {}", self.prim_min_level);
+        trace!("  Prim LOD Frac: {}", self.prim_lod_frac);
+    }
+
+    pub fn set_prim_depth(&mut self, z: i16, delta_z: i16) {
+        self.prim_depth = ((z as u32 & 0x7fff) << 3, delta_z as u16 as u32);
+        trace!("  Prim Depth: {:?}", self.prim_depth);
+    }
+
+    pub fn set_key(&mut self, index: usize, key: KeyParams) {
+        self.key[index] = key;
+        trace!("  Key {}: {:?}", index, key);
+    }
+
+    pub fn set_convert(&mut self, coeffs: [i32; 6]) {
+        self.convert = coeffs;
+        trace!("  Convert: {:?}", self.convert);
+    }
+
+    pub fn draw_triangle(&mut self, rdram: &mut Rdram, triangle: &TriangleCoefficients) {
+        raster::triangle(self, rdram, triangle);
+    }
+
+    pub fn draw_rectangle(&mut self, rdram: &mut Rdram, rectangle: &RectangleCoefficients) {
+        raster::rectangle(self, rdram, rectangle);
+    }
+
+    // Whether the level of detail has to be calculated for each pixel, either
+    // to pick mipmap tiles or for the combiner's LOD fraction input
+    fn needs_lod(&self) -> bool {
+        let first_cycle = if self.other_modes.cycle_type == CycleType::TwoCycle {
+            0
+        } else {
+            1
+        };
+
+        self.other_modes.tex_lod_en
+            || (first_cycle..2).any(|cycle| {
+                self.combine_mode.rgb[cycle].mul == 13 || self.combine_mode.alpha[cycle].mul == 0
+            })
+    }
+
+    fn fill_pixel(&mut self, rdram: &mut Rdram, x: u32, y: u32) {
+        target::write_fill(rdram, &self.color_image, x, y, self.fill_color);
+    }
+
+    fn copy_pixel(&mut self, rdram: &mut Rdram, x: u32, y: u32, tile: usize, s: i32, t: i32) {
+        let (raw, alpha) = self.tmem.fetch_raw(tile, s, t, self.other_modes.en_tlut);
+
+        if self.other_modes.alpha_compare_en && !alpha {
+            return;
+        }
+
+        target::write_raw(rdram, &self.color_image, x, y, raw);
+    }
+
+    fn shade_pixel(&mut self, rdram: &mut Rdram, fragment: &Fragment, tile: usize) {
+        let noise = self.next_noise();
+        let modes = &self.other_modes;
+        let two_cycle = modes.cycle_type == CycleType::TwoCycle;
+
+        // Coverage is stored as 'count - 1', as it is in the frame buffer
+        if fragment.cvg == 0 || !(modes.antialias_en || fragment.cvbit) {
+            return;
+        }
+
+        let cvg = fragment.cvg - 1;
+
+        let memory = if modes.image_read_en {
+            target::read_color(rdram, &self.color_image, fragment.x, fragment.y)
+        } else {
+            MemoryPixel {
+                color: [0; 3],
+                cvg: 7,
+            }
+        };
+
+        let overflow = (cvg + memory.cvg) >= 8;
+
+        let z = if modes.z_source == ZSource::Primitive {
+            ZValue {
+                z: self.prim_depth.0,
+                dz: self.prim_depth.1,
+            }
+        } else {
+            fragment.z
+        };
+
+        if modes.z_compare_en {
+            let stored = target::read_z(
+                rdram,
+                self.z_image,
+                &self.color_image,
+                fragment.x,
+                fragment.y,
+            );
+
+            if !target::z_compare(modes.z_mode, z, stored, overflow) {
+                return;
+            }
+        }
+
+        let (tile0, tile1, lod_frac) = self.lod_tiles(tile, fragment);
+        let texel0 = self.texel(tile0, fragment.s, fragment.t, 0);
+
+        // In one-cycle mode, there is no second texture fetch
+        let texel1 = if two_cycle {
+            self.texel(tile1, fragment.s, fragment.t, 1)
+        } else {
+            texel0
+        };
+
+        let mut inputs = CombineInputs {
+            combined: [0; 4],
+            texel0,
+            texel1,
+            prim: self.fixed_colors[FixedColor::Primitive as usize],
+            shade: fragment.shade,
+            env: self.fixed_colors[FixedColor::Environment as usize],
+            key_center: self.key.map(|key| key.center as i32),
+            key_scale: self.key.map(|key| key.scale as i32),
+            key_width: self.key.map(|key| key.width as i32),
+            lod_frac,
+            prim_lod_frac: self.prim_lod_frac,
+            noise: (((noise & 7) << 6) | 0x20) as i32,
+            k4: self.convert[4],
+            k5: self.convert[5],
+        };
+
+        // In one-cycle mode, the combiner uses the second cycle's settings
+        if two_cycle {
+            inputs.combined = combiner::combine(&self.combine_mode, 0, &inputs);
+        }
+
+        let mut color = if modes.key_en {
+            // The chroma key replaces the combined alpha
+            let (combined, key_alpha) = combiner::combine_keyed(&self.combine_mode, 1, &inputs);
+            let mut color = combined.map(combiner::clamp);
+            color[3] = key_alpha;
+            color
+        } else {
+            combiner::combine(&self.combine_mode, 1, &inputs).map(combiner::clamp)
+        };
+
+        if modes.alpha_compare_en {
+            let threshold = if modes.dither_alpha_en {
+                (noise >> 3) as i32 & 0xff
+            } else {
+                self.fixed_colors[FixedColor::Blend as usize][3]
+            };
+
+            if color[3] < threshold {
+                return;
+            }
+        }
+
+        if modes.cvg_times_alpha {
+            color[3] = (color[3] * (cvg as i32 + 1) + 4) >> 3;
+        }
+
+        if modes.alpha_cvg_select {
+            color[3] = ((cvg as i32 + 1) << 5).min(255);
+        }
+
+        let blend_inputs = BlendInputs {
+            memory: memory.color,
+            memory_alpha: (memory.cvg << 5) as i32,
+            blend: self.fixed_colors[FixedColor::Blend as usize],
+            fog: self.fixed_colors[FixedColor::Fog as usize],
+            shade_alpha: fragment.shade[3],
+        };
+
+        let mut pixel = [color[0], color[1], color[2]];
+
+        if two_cycle {
+            pixel = blender::blend(
+                &modes.blend_mode.mode[0],
+                pixel,
+                color[3],
+                &blend_inputs,
+                true,
+            );
+        }
+
+        let mode = &modes.blend_mode.mode[two_cycle as usize];
+
+        let output = if modes.color_on_cvg && !overflow {
+            memory.color
+        } else if (modes.force_blend || !overflow) && !is_partial_reject(mode, color[3]) {
+            blender::blend(mode, pixel, color[3], &blend_inputs, modes.force_blend)
+        } else {
+            pixel
+        };
+
+        let new_cvg = match modes.cvg_dest {
+            CvgDest::Clamp => (cvg + memory.cvg).min(7),
+            CvgDest::Wrap => (cvg + memory.cvg) & 7,
+            CvgDest::Zap => 7,
+            CvgDest::Save => memory.cvg,
+        };
+
+        let dither = self.rgb_dither(fragment.x, fragment.y, noise);
+
+        target::write_color(
+            rdram,
+            &self.color_image,
+            fragment.x,
+            fragment.y,
+            output,
+            new_cvg,
+            dither,
+        );
+
+        if self.other_modes.z_update_en {
+            target::write_z(
+                rdram,
+                self.z_image,
+                &self.color_image,
+                fragment.x,
+                fragment.y,
+                z,
+            );
+        }
+    }
+
+    // For YUV textures, the texture filter converts texels to RGB instead of
+    // filtering them, unless bilinear filtering is selected for the cycle
+    fn texel(&self, tile: usize, s: i32, t: i32, cycle: usize) -> [i32; 4] {
+        let modes = &self.other_modes;
+        let convert = !modes.bi_lerp[cycle] && self.tmem.format(tile) == Format::Yuv;
+        let texel = self.tmem.sample(tile, s, t, modes, !convert);
+
+        if !convert {
+            return texel;
+        }
+
+        let [u, v, y, _] = texel;
+        let k = &self.convert;
+
+        [
+            y + ((k[0] * v + 0x80) >> 8),
+            y + ((k[1] * u + k[2] * v + 0x80) >> 8),
+            y + ((k[3] * u + 0x80) >> 8),
+            y,
+        ]
+        .map(|value| value & 0x1ff)
+    }
+
+    // Picks the tiles for the two texture fetches from the pixel's level of
+    // detail, along with the combiner's LOD fraction
+    fn lod_tiles(&self, tile: usize, fragment: &Fragment) -> (usize, usize, i32) {
+        let modes = &self.other_modes;
+        let max_level = fragment.max_level as usize;
+        let sharpen = modes.sharpen_tex_en;
+        let detail = modes.detail_tex_en;
+
+        let (level, magnify, distant, lod_frac) = if fragment.lod >= 0x4000 {
+            (max_level, false, true, 0xff)
+        } else if fragment.lod < 32 {
+            // Texels are larger than pixels
+            let distant = max_level == 0;
+
+            let lod_frac = if sharpen || detail {
+                let lod = fragment.lod.max(self.prim_min_level);
+                (lod << 3) | if sharpen { 0x100 } else { 0 }
+            } else if distant {
+                0xff
+            } else {
+                0
+            };
+
+            (0, true, distant, lod_frac)
+        } else {
+            let level = (31 - (fragment.lod >> 5).leading_zeros()) as usize;
+            let distant = level >= max_level;
+
+            let lod_frac = if distant && !sharpen && !detail {
+                0xff
+            } else {
+                ((fragment.lod << 3) >> level) & 0xff
+            };
+
+            (level, false, distant, lod_frac)
+        };
+
+        if !modes.tex_lod_en {
+            return (tile, (tile + 1) & 7, lod_frac);
+        }
+
+        let level = if distant { max_level } else { level };
+
+        let tiles = if !detail {
+            let tile0 = tile + level;
+
+            if distant || (magnify && !sharpen) {
+                (tile0, tile0)
+            } else {
+                (tile0, tile0 + 1)
+            }
+        } else if distant || magnify {
+            // Detail textures sit in the tile before the first mipmap
+            (tile + level + !magnify as usize, tile + level + 1)
+        } else {
+            (tile + level + 1, tile + level + 2)
+        };
+
+        (tiles.0 & 7, tiles.1 & 7, lod_frac)
+    }
+
+    fn rgb_dither(&self, x: u32, y: u32, noise: u32) -> u32 {
+        let index = (((y & 3) << 2) | (x & 3)) as usize;
+
+        match self.other_modes.rgb_dither_sel {
+            RgbDitherSelect::MagicSquare => MAGIC_SQUARE_MATRIX[index],
+            RgbDitherSelect::Bayer => BAYER_MATRIX[index],
+            RgbDitherSelect::Noise => noise & 7,
+            RgbDitherSelect::NoDither => 7,
+        }
+    }
+
+    fn next_noise(&mut self) -> u32 {
+        // Hardware noise is not reproducible anyway, so any cheap generator
+        // will do, as long as it is deterministic
+        self.noise ^= self.noise << 13;
+        self.noise ^= self.noise >> 17;
+        self.noise ^= self.noise << 5;
+        self.noise
+    }
+}
+
+fn is_partial_reject(mode: &BlendModeRawParams, alpha: i32) -> bool {
+    // If the blender is set up as a standard 'A * P + (1 - A) * M' blend,
+    // fully opaque pixels skip blending entirely
+    mode.a == 0 && mode.b == 0 && alpha >= 0xff
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::backend::{Backend, RenderBackend};
+    use super::super::decoder::{Context, Decoder};
+    use crate::header::CicType;
+    use crate::rdram::Rdram;
+
+    // Images are 8x8 pixels, 32 bits per pixel
+    const SIZE: u32 = 8;
+    const COLOR_IMAGE: u32 = 0x1000;
+    const Z_IMAGE: u32 = 0x2000;
+    const TEXTURE: u32 = 0x3000;
+    const TLUT: u32 = 0x3800;
+
+    const CYCLE_1: u64 = 0 << 52;
+    const CYCLE_FILL: u64 = 3 << 52;
+    const EN_TLUT: u64 = 1 << 47;
+    const Z_COMPARE: u64 = 1 << 4;
+    const Z_UPDATE: u64 = 1 << 5;
+    const Z_PRIMITIVE: u64 = 1 << 2;
+
+    fn run(rdram: &mut Rdram, commands: &[u64]) {
+        let mut renderer = Backend::new(RenderBackend::Software, None).unwrap();
+        let mut decoder = Decoder::new();
+
+        let setup = [
+            color_image(COLOR_IMAGE, 3),
+            0x3e << 56 | Z_IMAGE as u64,
+            // Scissor covers the whole image
+            0x2d << 56 | ((SIZE as u64 * 4) << 12) | (SIZE as u64 * 4),
+        ];
+
+        for &word in setup.iter().chain(commands) {
+            decoder.write_command(word);
+        }
+
+        decoder.restart();
+
+        while decoder.running() {
+            decoder.step(Context {
+                renderer: &mut renderer,
+                rdram,
+                gfx: None,
+            });
+        }
+    }
+
+    fn render(commands: &[u64]) -> Vec<u32> {
+        let mut rdram = Rdram::new(CicType::Unknown, false);
+        run(&mut rdram, commands);
+        pixels(&rdram)
+    }
+
+    fn pixels(rdram: &Rdram) -> Vec<u32> {
+        (0..(SIZE * SIZE))
+            .map(|index| rdram.read_single((COLOR_IMAGE + index * 4) as usize))
+            .collect()
+    }
+
+    fn color_image(address: u32, size: u64) -> u64 {
+        0x3f << 56 | size << 51 | ((SIZE as u64 - 1) << 32) | address as u64
+    }
+
+    fn other_modes(flags: u64) -> u64 {
+        0x2f << 56 | flags
+    }
+
+    // Sets both combiner cycles to '(0 - 0) * 0 + input', for both colour and
+    // alpha (where input is one of COMBINED, TEXEL0, etc.)
+    fn combine(input: u64) -> u64 {
+        let add = (input << 15) | (input << 9) | (input << 6) | input;
+        let zero_alpha = (7 << 44) | (7 << 41) | (7 << 21) | (7 << 18) | (7 << 12) | (7 << 3);
+        let zero_rgb = (15 << 52) | (31 << 47) | (15 << 37) | (31 << 32) | (15 << 28) | (15 << 24);
+        0x3c << 56 | zero_rgb | zero_alpha | add
+    }
+
+    // Coordinates are in whole pixels
+    fn rectangle(x0: u64, y0: u64, x1: u64, y1: u64) -> u64 {
+        0x36 << 56 | (x1 * 4) << 44 | (y1 * 4) << 32 | (x0 * 4) << 12 | (y0 * 4)
+    }
+
+    fn assert_image(pixels: &[u32], expected: &[u32]) {
+        for y in 0..SIZE as usize {
+            let row = &pixels[(y * SIZE as usize)..((y + 1) * SIZE as usize)];
+            let expected = &expected[(y * SIZE as usize)..((y + 1) * SIZE as usize)];
+            assert_eq!(row, expected, "row {}", y);
+        }
+    }
+
+    #[test]
+    fn fills_rectangle() {
+        let pixels = render(&[
+            other_modes(CYCLE_FILL),
+            0x37 << 56 | 0x1122_3344,
+            // Edges are inclusive in fill mode
+            rectangle(2, 1, 5, 3),
+        ]);
+
+        let f = 0x1122_3344;
+
+        #[rustfmt::skip]
+        let expected = [
+            0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, f, f, f, f, 0, 0,
+            0, 0, f, f, f, f, 0, 0,
+            0, 0, f, f, f, f, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0,
+        ];
+
+        assert_image(&pixels, &expected);
+    }
+
+    #[test]
+    fn draws_shaded_triangle() {
+        let pixels = render(&[
+            other_modes(CYCLE_1),
+            combine(4),
+            // Left major, with YH = 1, YM = YL = 7
+            0x0c << 56 | 1 << 55 | 28 << 32 | 28 << 16 | 4,
+            // Low, high and middle edges (X, then DXDY). The major edge is
+            // vertical at X = 1, and the minor edge moves right by a pixel
+            // on each line.
+            0,
+            0x0001_0000 << 32,
+            0x0002_0000 << 32 | 0x0001_0000,
+            // Red increases across, blue increases down
+            0x0010 << 48 | 0x0080 << 32 | 0x00ff,
+            0x0020 << 48,
+            0,
+            0,
+            0x0010 << 16,
+            0x0010 << 16,
+            0,
+            0,
+        ]);
+
+        #[rustfmt::skip]
+        let coverage = [
+            0, 0, 0, 0, 0, 0, 0, 0,
+            0, 1, 0, 0, 0, 0, 0, 0,
+            0, 1, 1, 0, 0, 0, 0, 0,
+            0, 1, 1, 1, 0, 0, 0, 0,
+            0, 1, 1, 1, 1, 0, 0, 0,
+            0, 1, 1, 1, 1, 1, 0, 0,
+            0, 1, 1, 1, 1, 1, 1, 0,
+            0, 0, 0, 0, 0, 0, 0, 0,
+        ];
+
+        let expected: Vec<u32> = coverage
+            .iter()
+            .enumerate()
+            .map(|(index, &covered)| {
+                let (x, y) = (index as u32 % SIZE, index as u32 / SIZE);
+                let red = 0x10 + 0x20 * x.saturating_sub(1);
+                let blue = 0x10 * y.saturating_sub(1);
+                covered * ((red << 24) | (0x80 << 16) | (blue << 8) | 0xe0)
+            })
+            .collect();
+
+        assert_image(&pixels, &expected);
+    }
+
+    #[test]
+    fn draws_textured_rectangle_with_tlut() {
+        let mut rdram = Rdram::new(CicType::Unknown, false);
+
+        // Palette 0 is all black, palette 1 starts red, green, blue, white
+        for (index, color) in [0xf801u16, 0x07c1, 0x003f, 0xffff].into_iter().enumerate() {
+            rdram.write_single((TLUT as usize) + (16 + index) * 2, (color >> 8) as u8);
+            rdram.write_single((TLUT as usize) + (16 + index) * 2 + 1, color as u8);
+        }
+
+        for index in 0..16 {
+            rdram.write_single((TLUT as usize) + index * 2 + 1, 1u8);
+        }
+
+        // 4x4 CI4 texture, with each line rotated by one texel
+        for (index, byte) in [0x01u8, 0x23, 0x12, 0x30, 0x23, 0x01, 0x30, 0x12]
+            .into_iter()
+            .enumerate()
+        {
+            rdram.write_single(TEXTURE as usize + index, byte);
+        }
+
+        run(
+            &mut rdram,
+            &[
+                // Load 32 TLUT entries into the upper half of TMEM
+                0x3d << 56 | 2 << 51 | 31 << 32 | TLUT as u64,
+                0x35 << 56 | 0x100 << 32 | 7 << 24,
+                0x30 << 56 | 7 << 24 | (31 << 2) << 12,
+                // Load the texture, and use palette 1 for it
+                0x3d << 56 | 2 << 53 | 3 << 32 | TEXTURE as u64,
+                0x35 << 56 | 2 << 53 | 1 << 41 | 1 << 20,
+                0x34 << 56 | (3 << 2) << 12 | 3 << 2,
+                other_modes(CYCLE_1 | EN_TLUT),
+                combine(1),
+                // Texture one texel per pixel, from (2, 2) to (6, 6)
+                0x24 << 56 | 24 << 44 | 24 << 32 | 8 << 12 | 8,
+                0x0400 << 16 | 0x0400,
+            ],
+        );
+
+        let (r, g, b, w) = (0xff00_00e0, 0x00ff_00e0, 0x0000_ffe0, 0xffff_ffe0);
+
+        #[rustfmt::skip]
+        let expected = [
+            0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, r, g, b, w, 0, 0,
+            0, 0, g, b, w, r, 0, 0,
+            0, 0, b, w, r, g, 0, 0,
+            0, 0, w, r, g, b, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0,
+        ];
+
+        assert_image(&pixels(&rdram), &expected);
+    }
+
+    #[test]
+    fn hides_overlapping_rectangles_behind_nearer_ones() {
+        // Each rectangle is a different colour at a different depth
+        let layer =
+            |color: u64, z: u64, rect: u64| [0x3a << 56 | color, 0x2e << 56 | z << 16, rect];
+
+        let mut commands = vec![
+            // Clear the Z buffer to the far plane
+            other_modes(CYCLE_FILL),
+            color_image(Z_IMAGE, 2),
+            0x37 << 56 | 0xfffc_fffc,
+            rectangle(0, 0, 7, 7),
+            color_image(COLOR_IMAGE, 3),
+            other_modes(CYCLE_1 | Z_COMPARE | Z_UPDATE | Z_PRIMITIVE),
+            combine(3),
+        ];
+
+        commands.extend(layer(0xff00_00ff, 0x4000, rectangle(1, 1, 5, 5)));
+        commands.extend(layer(0x00ff_00ff, 0x6000, rectangle(3, 3, 7, 7)));
+        commands.extend(layer(0x0000_ffff, 0x2000, rectangle(0, 4, 4, 6)));
+
+        let pixels = render(&commands);
+        let (r, g, b) = (0xff00_00e0, 0x00ff_00e0, 0x0000_ffe0);
+
+        #[rustfmt::skip]
+        let expected = [
+            0, 0, 0, 0, 0, 0, 0, 0,
+            0, r, r, r, r, 0, 0, 0,
+            0, r, r, r, r, 0, 0, 0,
+            0, r, r, r, r, g, g, 0,
+            b, b, b, b, r, g, g, 0,
+            b, b, b, b, g, g, g, 0,
+            0, 0, 0, g, g, g, g, 0,
+            0, 0, 0, 0, 0, 0, 0, 0,
+        ];
+
+        assert_image(&pixels, &expected);
+    }
+}
@@ -0,0 +1,62 @@
+use super::BlendModeRawParams;
+
+pub struct BlendInputs {
+    pub memory: [i32; 3],
+    pub memory_alpha: i32,
+    pub blend: [i32; 4],
+    pub fog: [i32; 4],
+    pub shade_alpha: i32,
+}
+
+// 'pixel' is the combined colour for the first blender cycle, and the output
+// of the first blender cycle for the second
+pub fn blend(
+    mode: &BlendModeRawParams,
+    pixel: [i32; 3],
+    pixel_alpha: i32,
+    inputs: &BlendInputs,
+    shift_only: bool,
+) -> [i32; 3] {
+    let factor_a = match mode.a {
+        0 => pixel_alpha,
+        1 => inputs.fog[3],
+        2 => inputs.shade_alpha,
+        _ => 0,
+    } >> 3;
+
+    let factor_b = match mode.b {
+        0 => !(factor_a << 3) & 0xff,
+        1 => inputs.memory_alpha,
+        2 => 0xff,
+        _ => 0,
+    } >> 3;
+
+    let p = blend_input(mode.p, pixel, inputs);
+    let m = blend_input(mode.m, pixel, inputs);
+
+    let mut result = [0; 3];
+
+    for (channel, value) in result.iter_mut().enumerate() {
+        let sum = p[channel] * factor_a + m[channel] * (factor_b + 1);
+
+        *value = if shift_only {
+            (sum >> 5) & 0xff
+        } else {
+            // Without FORCE_BLEND, the result is normalised by the sum of the
+            // blend factors (with reduced precision)
+            let divisor = (factor_a & !3) + (factor_b & !3) + 4;
+            (sum / divisor).min(0xff)
+        };
+    }
+
+    result
+}
+
+fn blend_input(input: u32, pixel: [i32; 3], inputs: &BlendInputs) -> [i32; 3] {
+    match input {
+        0 => pixel,
+        1 => inputs.memory,
+        2 => [inputs.blend[0], inputs.blend[1], inputs.blend[2]],
+        _ => [inputs.fog[0], inputs.fog[1], inputs.fog[2]],
+    }
+}
@@ -0,0 +1,187 @@
+use super::CombineModeRaw;
+
+pub struct CombineInputs {
+    pub combined: [i32; 4],
+    pub texel0: [i32; 4],
+    pub texel1: [i32; 4],
+    pub prim: [i32; 4],
+    pub shade: [i32; 4],
+    pub env: [i32; 4],
+    pub key_center: [i32; 3],
+    pub key_scale: [i32; 3],
+    pub key_width: [i32; 3],
+    pub lod_frac: i32,
+    pub prim_lod_frac: i32,
+    pub noise: i32,
+    pub k4: i32,
+    pub k5: i32,
+}
+
+// Returns the raw 9-bit result of the given combiner cycle. Use 'clamp' to
+// convert it to a displayable 8-bit value.
+pub fn combine(mode: &CombineModeRaw, cycle: usize, inputs: &CombineInputs) -> [i32; 4] {
+    combine_unshifted(mode, cycle, inputs).map(|value| (value >> 8) & 0x1ff)
+}
+
+// As 'combine', but also returns the alpha produced by the chroma key, which
+// compares each colour channel's distance from the key center (as scaled by
+// the combiner) against the key width
+pub fn combine_keyed(
+    mode: &CombineModeRaw,
+    cycle: usize,
+    inputs: &CombineInputs,
+) -> ([i32; 4], i32) {
+    let result = combine_unshifted(mode, cycle, inputs);
+
+    let key_alpha = (0..3)
+        .map(|channel| {
+            // The key is applied before the result is truncated to 9 bits
+            let value = (result[channel] << 15) >> 15;
+            (inputs.key_width[channel] << 4) - value.abs()
+        })
+        .min()
+        .unwrap()
+        .clamp(0, 0xff);
+
+    (result.map(|value| (value >> 8) & 0x1ff), key_alpha)
+}
+
+// Returns the 17-bit result of each equation, before it is shifted down
+fn combine_unshifted(mode: &CombineModeRaw, cycle: usize, inputs: &CombineInputs) -> [i32; 4] {
+    let rgb = &mode.rgb[cycle];
+    let alpha = &mode.alpha[cycle];
+    let mut result = [0; 4];
+
+    for (channel, value) in result.iter_mut().take(3).enumerate() {
+        let sub_a = match rgb.sub_a {
+            6 => 0x100,
+            7 => inputs.noise,
+            input => color_input(input, channel, inputs),
+        };
+
+        let sub_b = match rgb.sub_b {
+            6 => inputs.key_center[channel],
+            7 => inputs.k4,
+            input => color_input(input, channel, inputs),
+        };
+
+        let mul = match rgb.mul {
+            6 => inputs.key_scale[channel],
+            7..=12 => alpha_input(rgb.mul - 7, inputs),
+            13 => inputs.lod_frac,
+            14 => inputs.prim_lod_frac,
+            15 => inputs.k5,
+            input => color_input(input, channel, inputs),
+        };
+
+        let add = match rgb.add {
+            6 => 0x100,
+            input => color_input(input, channel, inputs),
+        };
+
+        *value = equation(sub_a, sub_b, mul, add);
+    }
+
+    let alpha_add_sub = |input: u32| match input {
+        6 => 0x100,
+        input => alpha_input(input, inputs),
+    };
+
+    let alpha_mul = match alpha.mul {
+        0 => inputs.lod_frac,
+        6 => inputs.prim_lod_frac,
+        input => alpha_input(input, inputs),
+    };
+
+    result[3] = equation(
+        alpha_add_sub(alpha.sub_a),
+        alpha_add_sub(alpha.sub_b),
+        alpha_mul,
+        alpha_add_sub(alpha.add),
+    );
+
+    result
+}
+
+// Converts a 9-bit combiner value into an 8-bit colour component. Values
+// that have overflowed saturate, and values that have gone negative become 0.
+pub fn clamp(value: i32) -> i32 {
+    match value & 0x1ff {
+        value @ 0x000..=0x0ff => value,
+        0x100..=0x17f => 0xff,
+        _ => 0,
+    }
+}
+
+fn color_input(input: u32, channel: usize, inputs: &CombineInputs) -> i32 {
+    match input {
+        0 => inputs.combined[channel],
+        1 => inputs.texel0[channel],
+        2 => inputs.texel1[channel],
+        3 => inputs.prim[channel],
+        4 => inputs.shade[channel],
+        5 => inputs.env[channel],
+        _ => 0,
+    }
+}
+
+fn alpha_input(input: u32, inputs: &CombineInputs) -> i32 {
+    color_input(input, 3, inputs)
+}
+
+fn equation(sub_a: i32, sub_b: i32, mul: i32, add: i32) -> i32 {
+    // Multiplier is a signed 9-bit value
+    let mul = ((mul & 0x1ff) << 23) >> 23;
+    (extend(sub_a) - extend(sub_b)) * mul + (extend(add) << 8) + 0x80
+}
+
+fn extend(value: i32) -> i32 {
+    // Only the top quarter of the 9-bit range is treated as negative
+    match value & 0x1ff {
+        value @ 0x180..=0x1ff => value - 0x200,
+        value => value,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rdp::renderer::CombineModeRawParams;
+
+    #[test]
+    fn keys_colours_near_the_center() {
+        let mut mode = CombineModeRaw::default();
+
+        // (TEXEL0 - CENTER) * SCALE + 0
+        mode.rgb[1] = CombineModeRawParams {
+            sub_a: 1,
+            sub_b: 6,
+            mul: 6,
+            add: 7,
+        };
+
+        let mut inputs = CombineInputs {
+            combined: [0; 4],
+            texel0: [0x40, 0x80, 0xc0, 0xff],
+            texel1: [0; 4],
+            prim: [0; 4],
+            shade: [0; 4],
+            env: [0; 4],
+            key_center: [0x40, 0x80, 0xc0],
+            key_scale: [0x40; 3],
+            key_width: [0x010; 3],
+            lod_frac: 0,
+            prim_lod_frac: 0,
+            noise: 0,
+            k4: 0,
+            k5: 0,
+        };
+
+        let (color, key_alpha) = combine_keyed(&mode, 1, &inputs);
+        assert_eq!(color, combine(&mode, 1, &inputs));
+        assert_eq!(key_alpha, 0x80);
+
+        inputs.texel0[1] = 0x90;
+        assert_eq!(combine_keyed(&mode, 1, &inputs).1, 0);
+    }
+}
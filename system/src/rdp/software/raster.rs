@@ -0,0 +1,338 @@
+use super::combiner;
+use super::target::ZValue;
+use super::{CycleType, RectangleCoefficients, SoftwareRenderer, TriangleCoefficients};
+use crate::rdram::Rdram;
+
+// Sub-pixel X offsets (in quarter pixels) of the two coverage samples taken on
+// each even sub-scanline. Odd sub-scanlines are offset by an extra quarter.
+const SAMPLE_OFFSETS: [i64; 2] = [0, 2];
+
+#[derive(Clone, Debug, Default)]
+pub struct Scissor {
+    // u10.2
+    pub xh: i32,
+    pub yh: i32,
+    pub xl: i32,
+    pub yl: i32,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Fragment {
+    pub x: u32,
+    pub y: u32,
+    // Number of covered samples (0-8)
+    pub cvg: u32,
+    // Whether the sample used in non-antialiased mode is covered
+    pub cvbit: bool,
+    pub shade: [i32; 4],
+    // s10.5
+    pub s: i32,
+    pub t: i32,
+    // Texels per pixel (u10.5), for choosing mipmap levels. Saturates at
+    // 0x7fff.
+    pub lod: i32,
+    pub max_level: u32,
+    pub z: ZValue,
+}
+
+#[derive(Default)]
+struct SubScanlines {
+    valid: [bool; 4],
+    left: [i64; 4],
+    right: [i64; 4],
+}
+
+pub fn triangle(
+    renderer: &mut SoftwareRenderer,
+    rdram: &mut Rdram,
+    triangle: &TriangleCoefficients,
+) {
+    let edges = &triangle.edges;
+    let scissor = renderer.scissor.clone();
+    let cycle_type = renderer.other_modes.cycle_type;
+
+    let y_start = edges.yh.max(scissor.yh);
+    let y_end = edges.yl.min(scissor.yl);
+
+    if y_start >= y_end {
+        return;
+    }
+
+    let x_clip_start = (scissor.xh + 3) >> 2;
+    let x_clip_end = (scissor.xl + 3) >> 2;
+
+    // Edge X coordinates are given for the first scanline the triangle
+    // touches, except for XL which is given at YM
+    let y_top = edges.yh & !3;
+
+    // The RDP steps edges once per sub-scanline, dropping the lowest bit
+    let step = |dxdy: i32| ((dxdy >> 2) & !1) as i64;
+    let high_step = step(edges.high.dxdy);
+    let mid_step = step(edges.mid.dxdy);
+    let low_step = step(edges.low.dxdy);
+
+    let (tile, texture) = match &triangle.texture {
+        Some((tile, coeffs)) => (*tile, Some(coeffs)),
+        None => (0, None),
+    };
+
+    let perspective = renderer.other_modes.perspective_enable;
+    let needs_lod = renderer.needs_lod();
+
+    for y in (y_start >> 2)..=((y_end - 1) >> 2) {
+        let mut sub = SubScanlines::default();
+
+        for index in 0..4 {
+            let sub_y = (y << 2) + index as i32;
+
+            if sub_y < y_start || sub_y >= y_end {
+                continue;
+            }
+
+            let major = edges.high.x as i64 + high_step * (sub_y - y_top) as i64;
+
+            let minor = if sub_y < edges.ym {
+                edges.mid.x as i64 + mid_step * (sub_y - y_top) as i64
+            } else {
+                edges.low.x as i64 + low_step * (sub_y - edges.ym) as i64
+            };
+
+            let (left, right) = if edges.left_major {
+                (major, minor)
+            } else {
+                (minor, major)
+            };
+
+            if left < right {
+                sub.valid[index] = true;
+                sub.left[index] = left;
+                sub.right[index] = right;
+            }
+        }
+
+        let valid_left = (0..4)
+            .filter(|&index| sub.valid[index])
+            .map(|index| sub.left[index]);
+        let valid_right = (0..4)
+            .filter(|&index| sub.valid[index])
+            .map(|index| sub.right[index]);
+
+        let (Some(left), Some(right)) = (valid_left.min(), valid_right.max()) else {
+            continue;
+        };
+
+        let x_start = ((left >> 16) as i32).max(x_clip_start).max(0);
+        let x_end = (((right + 0xffff) >> 16) as i32).min(x_clip_end);
+
+        // Attributes are interpolated from the major edge of each scanline
+        let scanline = (y - (y_top >> 2)) as i64;
+        let major_x = edges.high.x as i64 + edges.high.dxdy as i64 * scanline;
+
+        for x in x_start..x_end {
+            let (cvg, cvbit) = sub.coverage(x);
+
+            if cvg == 0 {
+                continue;
+            }
+
+            let offset = ((x as i64) << 16) - major_x;
+
+            let interpolate = |base: i32, dx: i32, de: i32| {
+                base as i64 + de as i64 * scanline + ((dx as i64 * offset) >> 16)
+            };
+
+            let [s, t, lod] = texture
+                .map(|coeffs| {
+                    let [s_raw, t_raw, w] =
+                        [0, 1, 2].map(|i| interpolate(coeffs.base[i], coeffs.dx[i], coeffs.de[i]));
+
+                    let [s, t] = texture_coords(s_raw, t_raw, w, perspective);
+
+                    if !needs_lod {
+                        return [s, t, 0];
+                    }
+
+                    // LOD is taken from the texel distance to the next pixel
+                    // across and the next pixel down
+                    let delta = |step: &[i32; 3]| {
+                        let [s_next, t_next] = texture_coords(
+                            s_raw + step[0] as i64,
+                            t_raw + step[1] as i64,
+                            w + step[2] as i64,
+                            perspective,
+                        );
+
+                        (s_next - s).abs().max((t_next - t).abs())
+                    };
+
+                    let lod = delta(&coeffs.dx).max(delta(&coeffs.dy)).min(0x7fff);
+                    [s, t, lod]
+                })
+                .unwrap_or([0, 0, 0]);
+
+            if cycle_type == CycleType::Fill {
+                renderer.fill_pixel(rdram, x as u32, y as u32);
+            } else if cycle_type == CycleType::Copy {
+                renderer.copy_pixel(rdram, x as u32, y as u32, tile, s, t);
+            } else {
+                let shade = triangle
+                    .shade
+                    .as_ref()
+                    .map(|coeffs| {
+                        [0, 1, 2, 3].map(|i| {
+                            let value = interpolate(coeffs.base[i], coeffs.dx[i], coeffs.de[i]);
+                            combiner::clamp((value >> 16) as i32)
+                        })
+                    })
+                    .unwrap_or([0; 4]);
+
+                let z = triangle
+                    .z
+                    .as_ref()
+                    .map(|coeffs| {
+                        let value = interpolate(coeffs.base[0], coeffs.dx[0], coeffs.de[0]);
+                        let delta = (coeffs.dx[0] as i64).abs() + (coeffs.dy[0] as i64).abs();
+
+                        ZValue {
+                            z: (value >> 13).clamp(0, 0x3ffff) as u32,
+                            dz: (delta >> 16).clamp(0, 0xffff) as u32,
+                        }
+                    })
+                    .unwrap_or_default();
+
+                let fragment = Fragment {
+                    x: x as u32,
+                    y: y as u32,
+                    cvg,
+                    cvbit,
+                    shade,
+                    s,
+                    t,
+                    lod,
+                    max_level: triangle.max_level,
+                    z,
+                };
+
+                renderer.shade_pixel(rdram, &fragment, tile);
+            }
+        }
+    }
+}
+
+pub fn rectangle(
+    renderer: &mut SoftwareRenderer,
+    rdram: &mut Rdram,
+    rectangle: &RectangleCoefficients,
+) {
+    let scissor = renderer.scissor.clone();
+    let cycle_type = renderer.other_modes.cycle_type;
+
+    let (xh, yh, xl, yl) = (
+        rectangle.xh as i32,
+        rectangle.yh as i32,
+        rectangle.xl as i32,
+        rectangle.yl as i32,
+    );
+
+    // In fill and copy modes, the right and bottom edges are inclusive
+    let (x_start, x_end, y_start, y_end) =
+        if cycle_type == CycleType::Fill || cycle_type == CycleType::Copy {
+            (xh >> 2, (xl >> 2) + 1, yh >> 2, (yl >> 2) + 1)
+        } else {
+            ((xh + 3) >> 2, (xl + 3) >> 2, (yh + 3) >> 2, (yl + 3) >> 2)
+        };
+
+    let x_start = x_start.max((scissor.xh + 3) >> 2);
+    let x_end = x_end.min((scissor.xl + 3) >> 2);
+    let y_start = y_start.max((scissor.yh + 3) >> 2);
+    let y_end = y_end.min((scissor.yl + 3) >> 2);
+
+    // Texture coordinates are relative to the unclipped top-left corner
+    let origin_x = xh >> 2;
+    let origin_y = yh >> 2;
+
+    // In copy mode, four pixels are written for every DSDX step
+    let s_shift = if cycle_type == CycleType::Copy { 7 } else { 5 };
+
+    // DSDX and DTDY are s5.10, and constant across the whole rectangle
+    let lod = rectangle.texture.as_ref().map_or(0, |coords| {
+        ((coords.dsdx as i32).abs().max((coords.dtdy as i32).abs()) >> 5).min(0x7fff)
+    });
+
+    for y in y_start..y_end {
+        for x in x_start..x_end {
+            let (tile, s, t) = rectangle
+                .texture
+                .as_ref()
+                .map(|coords| {
+                    let (s_delta, t_delta) = if coords.flip {
+                        (y - origin_y, x - origin_x)
+                    } else {
+                        (x - origin_x, y - origin_y)
+                    };
+
+                    let s = coords.s as i32 + ((coords.dsdx as i32 * s_delta) >> s_shift);
+                    let t = coords.t as i32 + ((coords.dtdy as i32 * t_delta) >> 5);
+                    (coords.tile, s, t)
+                })
+                .unwrap_or((0, 0, 0));
+
+            if cycle_type == CycleType::Fill {
+                renderer.fill_pixel(rdram, x as u32, y as u32);
+            } else if cycle_type == CycleType::Copy {
+                renderer.copy_pixel(rdram, x as u32, y as u32, tile, s, t);
+            } else {
+                let fragment = Fragment {
+                    x: x as u32,
+                    y: y as u32,
+                    cvg: 8,
+                    cvbit: true,
+                    shade: [0; 4],
+                    s,
+                    t,
+                    lod,
+                    max_level: 0,
+                    z: ZValue::default(),
+                };
+
+                renderer.shade_pixel(rdram, &fragment, tile);
+            }
+        }
+    }
+}
+
+// Converts interpolated texture coordinates to s10.5, dividing by W if
+// perspective correction is enabled
+fn texture_coords(s: i64, t: i64, w: i64, perspective: bool) -> [i32; 2] {
+    if perspective {
+        let w = w.max(1);
+        [s, t].map(|value| ((value << 15) / w).clamp(i16::MIN as i64, i16::MAX as i64) as i32)
+    } else {
+        [s, t].map(|value| (value >> 16) as i32)
+    }
+}
+
+impl SubScanlines {
+    fn coverage(&self, x: i32) -> (u32, bool) {
+        let pixel_x = (x as i64) << 16;
+        let mut count = 0;
+        let mut cvbit = false;
+
+        for index in 0..4 {
+            if !self.valid[index] {
+                continue;
+            }
+
+            for (sample, offset) in SAMPLE_OFFSETS.iter().enumerate() {
+                let sample_x = pixel_x + ((offset + (index as i64 & 1)) << 14);
+
+                if sample_x >= self.left[index] && sample_x < self.right[index] {
+                    count += 1;
+                    cvbit |= index == 0 && sample == 0;
+                }
+            }
+        }
+
+        (count, cvbit)
+    }
+}
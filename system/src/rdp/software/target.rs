@@ -0,0 +1,197 @@
+use super::{ColorImage, ZMode};
+use crate::rdram::Rdram;
+
+const MAX_Z: u32 = 0x3ffff;
+
+// Z values are stored as a 3-bit exponent and 11-bit mantissa. Each exponent
+// halves the precision of the one before it.
+const Z_FORMAT: [(u32, u32); 8] = [
+    (0x00000, 6),
+    (0x20000, 5),
+    (0x30000, 4),
+    (0x38000, 3),
+    (0x3c000, 2),
+    (0x3e000, 1),
+    (0x3f000, 0),
+    (0x3f800, 0),
+];
+
+#[derive(Clone, Debug)]
+pub struct MemoryPixel {
+    pub color: [i32; 3],
+    pub cvg: u32,
+}
+
+#[derive(Copy, Clone, Debug, Default)]
+pub struct ZValue {
+    // u15.3
+    pub z: u32,
+    pub dz: u32,
+}
+
+pub fn read_color(rdram: &Rdram, image: &ColorImage, x: u32, y: u32) -> MemoryPixel {
+    let address = pixel_address(image.dram_addr, image, x, y);
+
+    match image.format.1 {
+        3 => {
+            let word: u32 = rdram.read_single(address);
+
+            MemoryPixel {
+                color: [
+                    (word >> 24) as i32,
+                    ((word >> 16) & 0xff) as i32,
+                    ((word >> 8) & 0xff) as i32,
+                ],
+                cvg: (word & 0xff) >> 5,
+            }
+        }
+        2 => {
+            let word: u16 = rdram.read_single(address);
+
+            // The two lower coverage bits live in the 'hidden' 9th bits of
+            // RDRAM, which aren't emulated, so assume they are set
+            MemoryPixel {
+                color: [
+                    ((word >> 8) & 0xf8) as i32,
+                    ((word >> 3) & 0xf8) as i32,
+                    ((word << 2) & 0xf8) as i32,
+                ],
+                cvg: ((word as u32 & 1) << 2) | 3,
+            }
+        }
+        _ => {
+            let byte: u8 = rdram.read_single(address);
+
+            MemoryPixel {
+                color: [byte as i32; 3],
+                cvg: 7,
+            }
+        }
+    }
+}
+
+pub fn write_color(
+    rdram: &mut Rdram,
+    image: &ColorImage,
+    x: u32,
+    y: u32,
+    color: [i32; 3],
+    cvg: u32,
+    dither: u32,
+) {
+    let address = pixel_address(image.dram_addr, image, x, y);
+    let [r, g, b] = color.map(|value| value as u32);
+
+    match image.format.1 {
+        3 => {
+            let word = (r << 24) | (g << 16) | (b << 8) | (cvg << 5);
+            rdram.write_single(address, word);
+        }
+        2 => {
+            let [r, g, b] = [r, g, b].map(|value| {
+                if (value & 7) > dither {
+                    (value + 8).min(0xff)
+                } else {
+                    value
+                }
+            });
+
+            let word = ((r >> 3) << 11) | ((g >> 3) << 6) | ((b >> 3) << 1) | (cvg >> 2);
+            rdram.write_single(address, word as u16);
+        }
+        _ => rdram.write_single(address, r as u8),
+    }
+}
+
+pub fn write_fill(rdram: &mut Rdram, image: &ColorImage, x: u32, y: u32, fill_color: u32) {
+    let address = pixel_address(image.dram_addr, image, x, y);
+
+    // The fill color is a 32-bit pattern that repeats across the whole
+    // scanline, regardless of pixel size
+    match image.format.1 {
+        3 => rdram.write_single(address, fill_color),
+        2 => rdram.write_single(address, (fill_color >> ((!x & 1) << 4)) as u16),
+        _ => rdram.write_single(address, (fill_color >> ((!x & 3) << 3)) as u8),
+    }
+}
+
+pub fn write_raw(rdram: &mut Rdram, image: &ColorImage, x: u32, y: u32, value: u32) {
+    let address = pixel_address(image.dram_addr, image, x, y);
+
+    match image.format.1 {
+        3 => rdram.write_single(address, value),
+        2 => rdram.write_single(address, value as u16),
+        _ => rdram.write_single(address, value as u8),
+    }
+}
+
+pub fn read_z(rdram: &Rdram, z_image: u32, image: &ColorImage, x: u32, y: u32) -> ZValue {
+    let address = z_address(z_image, image, x, y);
+    let word: u16 = rdram.read_single(address);
+
+    // As with colour, the lower two bits of DZ would be in hidden RDRAM bits
+    ZValue {
+        z: decompress_z(word as u32 >> 2),
+        dz: 1 << ((word as u32 & 3) << 2),
+    }
+}
+
+pub fn write_z(rdram: &mut Rdram, z_image: u32, image: &ColorImage, x: u32, y: u32, z: ZValue) {
+    let address = z_address(z_image, image, x, y);
+    let word = (compress_z(z.z) << 2) | (compress_dz(z.dz) >> 2);
+    rdram.write_single(address, word as u16);
+}
+
+pub fn z_compare(mode: ZMode, new: ZValue, stored: ZValue, overflow: bool) -> bool {
+    let max = stored.z == MAX_Z;
+    let dz_max = new.dz.max(stored.dz) as i64;
+    let (z, stored_z) = (new.z as i64, stored.z as i64);
+
+    let in_front = z < stored_z;
+    let nearer = (z - dz_max) <= stored_z;
+    let farther = (z + dz_max) >= stored_z;
+
+    match mode {
+        // TODO: Interpenetrating mode should also adjust coverage
+        ZMode::Opaque | ZMode::Interpenetrating => max || if overflow { in_front } else { nearer },
+        ZMode::Transparent => max || in_front,
+        ZMode::Decal => !max && nearer && farther,
+    }
+}
+
+fn compress_z(z: u32) -> u32 {
+    let z = z.min(MAX_Z);
+
+    let exponent = Z_FORMAT
+        .iter()
+        .rposition(|&(base, _)| z >= base)
+        .unwrap_or(0);
+
+    let (_, shift) = Z_FORMAT[exponent];
+    ((exponent as u32) << 11) | ((z >> shift) & 0x7ff)
+}
+
+fn decompress_z(value: u32) -> u32 {
+    let (base, shift) = Z_FORMAT[(value >> 11) as usize & 7];
+    base + ((value & 0x7ff) << shift)
+}
+
+fn compress_dz(dz: u32) -> u32 {
+    dz.max(1).next_power_of_two().trailing_zeros().min(15)
+}
+
+fn pixel_address(base: u32, image: &ColorImage, x: u32, y: u32) -> usize {
+    let index = y as usize * image.width as usize + x as usize;
+    let address = match image.format.1 {
+        3 => base as usize + index * 4,
+        2 => base as usize + index * 2,
+        _ => base as usize + index,
+    };
+
+    address & 0x00ff_ffff
+}
+
+fn z_address(z_image: u32, image: &ColorImage, x: u32, y: u32) -> usize {
+    // Z buffer always uses 16-bit pixels, with the same width as the colour image
+    (z_image as usize + (y as usize * image.width as usize + x as usize) * 2) & 0x00ff_ffff
+}
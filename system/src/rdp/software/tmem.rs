@@ -0,0 +1,459 @@
+use super::{
+    Format, OtherModes, SampleType, TextureImage, TileAddressMode, TileDescriptor, TileSize,
+    TlutType,
+};
+use crate::rdram::Rdram;
+use tracing::trace;
+
+const TMEM_SIZE: usize = 4096;
+
+// When a TLUT is in use, or for 32-bit textures, only the lower half of TMEM
+// holds texel data
+const LOW_HALF_MASK: usize = 0x07ff;
+const HIGH_HALF: usize = 0x0800;
+
+// Odd lines of texels are stored with each pair of 32-bit words swapped
+const ODD_LINE_SWAP: usize = 4;
+
+#[derive(Clone, Debug, Default)]
+struct Tile {
+    descriptor: TileDescriptor,
+    size: TileSize,
+}
+
+pub struct Tmem {
+    texture_image: TextureImage,
+    tiles: [Tile; 8],
+    data: Vec<u8>,
+}
+
+impl Tmem {
+    pub fn new() -> Self {
+        Self {
+            texture_image: TextureImage::default(),
+            tiles: Default::default(),
+            data: vec![0; TMEM_SIZE],
+        }
+    }
+
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    pub fn data_mut(&mut self) -> &mut [u8] {
+        &mut self.data
+    }
+
+    pub fn set_texture_image(&mut self, texture_image: TextureImage) {
+        self.texture_image = texture_image;
+        trace!("  Texture Image: {:?}", self.texture_image);
+    }
+
+    pub fn set_tile(&mut self, index: usize, descriptor: TileDescriptor) {
+        self.tiles[index].descriptor = descriptor;
+        trace!(
+            "  Tile {} Descriptor: {:?}",
+            index,
+            self.tiles[index].descriptor
+        );
+    }
+
+    pub fn set_tile_size(&mut self, index: usize, size: TileSize) {
+        self.tiles[index].size = size;
+        trace!("  Tile {} Size: {:?}", index, self.tiles[index].size);
+    }
+
+    pub fn load_tile(&mut self, rdram: &Rdram, index: usize) {
+        let tile = self.tiles[index].clone();
+        let image = self.texture_image.clone();
+        let texel_size = image.format.1;
+
+        let s_start = (tile.size.sl >> 2) as usize;
+        let s_end = (tile.size.sh >> 2) as usize;
+        let t_start = (tile.size.tl >> 2) as usize;
+        let t_end = (tile.size.th >> 2) as usize;
+
+        let dram_stride = (image.width as usize) << texel_size >> 1;
+        let tmem_base = tile.descriptor.tmem_addr as usize * 8;
+        let tmem_stride = tile.descriptor.width as usize * 8;
+
+        for t in t_start..=t_end {
+            let line = t - t_start;
+            let tmem_line = tmem_base + line * tmem_stride;
+            let dram_line = image.dram_addr as usize + t * dram_stride;
+            let swap = if (line & 1) != 0 { ODD_LINE_SWAP } else { 0 };
+
+            if texel_size == 3 {
+                // 32-bit texels are split, with red/green in the lower half of
+                // TMEM and blue/alpha in the upper half
+                for s in s_start..=s_end {
+                    let texel: u32 = rdram.read_single((dram_line + s * 4) & 0x00ff_fffc);
+                    let offset = ((tmem_line + (s - s_start) * 2) ^ swap) & LOW_HALF_MASK;
+                    self.write_u16(offset, (texel >> 16) as u16);
+                    self.write_u16(offset | HIGH_HALF, texel as u16);
+                }
+            } else {
+                let byte_start = s_start << texel_size >> 1;
+                let byte_end = ((s_end + 1) << texel_size).div_ceil(2);
+
+                for (offset, byte_index) in (byte_start..byte_end).enumerate() {
+                    let byte: u8 = rdram.read_single((dram_line + byte_index) & 0x00ff_ffff);
+                    self.data[((tmem_line + offset) ^ swap) & (TMEM_SIZE - 1)] = byte;
+                }
+            }
+        }
+
+        trace!(
+            "  Tile {} loaded: {}x{} texels to {:04X}",
+            index,
+            s_end + 1 - s_start,
+            t_end + 1 - t_start,
+            tmem_base,
+        );
+    }
+
+    pub fn load_block(&mut self, rdram: &Rdram, index: usize) {
+        let tile = self.tiles[index].clone();
+        let image = self.texture_image.clone();
+        let texel_size = image.format.1;
+
+        // For LoadBlock, SL, TL and SH are whole texels, and TH holds DXT
+        let s_start = tile.size.sl as usize;
+        let s_end = tile.size.sh as usize;
+        let t_start = tile.size.tl as usize;
+        let dxt = tile.size.th;
+
+        let texel_offset = t_start * image.width as usize + s_start;
+        let dram_base = image.dram_addr as usize + (texel_offset << texel_size >> 1);
+        let tmem_base = tile.descriptor.tmem_addr as usize * 8;
+
+        let byte_len = ((s_end + 1 - s_start.min(s_end + 1)) << texel_size).div_ceil(2);
+        let word_count = byte_len.div_ceil(8);
+        let mut line_counter = 0u32;
+
+        for word in 0..word_count {
+            let swap = if (line_counter & 0x0800) != 0 {
+                ODD_LINE_SWAP
+            } else {
+                0
+            };
+
+            let dram_addr = dram_base + word * 8;
+
+            if texel_size == 3 {
+                for texel_index in 0..2 {
+                    let texel: u32 = rdram.read_single((dram_addr + texel_index * 4) & 0x00ff_fffc);
+                    let offset = ((tmem_base + word * 4 + texel_index * 2) ^ swap) & LOW_HALF_MASK;
+                    self.write_u16(offset, (texel >> 16) as u16);
+                    self.write_u16(offset | HIGH_HALF, texel as u16);
+                }
+            } else {
+                for byte_index in 0..8 {
+                    let byte: u8 = rdram.read_single((dram_addr + byte_index) & 0x00ff_ffff);
+                    self.data[((tmem_base + word * 8 + byte_index) ^ swap) & (TMEM_SIZE - 1)] =
+                        byte;
+                }
+            }
+
+            line_counter = line_counter.wrapping_add(dxt);
+        }
+
+        trace!(
+            "  Block loaded: {} words from {:08X} to {:04X}",
+            word_count,
+            dram_base,
+            tmem_base,
+        );
+    }
+
+    pub fn load_tlut(&mut self, rdram: &Rdram, index: usize) {
+        let tile = self.tiles[index].clone();
+        let image = self.texture_image.clone();
+
+        let s_start = (tile.size.sl >> 2) as usize;
+        let s_end = (tile.size.sh >> 2) as usize;
+        let t = (tile.size.tl >> 2) as usize;
+
+        let dram_base = image.dram_addr as usize + (t * image.width as usize + s_start) * 2;
+        let tmem_base = tile.descriptor.tmem_addr as usize * 8;
+
+        for entry in 0..=(s_end.saturating_sub(s_start)) {
+            let color: u16 = rdram.read_single((dram_base + entry * 2) & 0x00ff_fffe);
+
+            // Each entry is replicated across all four banks
+            for bank in 0..4 {
+                let offset = (tmem_base + entry * 8 + bank * 2) & (TMEM_SIZE - 1);
+                self.write_u16(offset, color);
+            }
+        }
+
+        trace!(
+            "  TLUT loaded: {} entries to {:04X}",
+            s_end.saturating_sub(s_start) + 1,
+            tmem_base
+        );
+    }
+
+    pub fn format(&self, index: usize) -> Format {
+        self.tiles[index].descriptor.format.0
+    }
+
+    // Samples a tile, using bilinear filtering if enabled and 'filter' is set.
+    // YUV texels are returned unconverted, as (U, V, Y, Y), with U and V
+    // signed.
+    pub fn sample(
+        &self,
+        index: usize,
+        s: i32,
+        t: i32,
+        modes: &OtherModes,
+        filter: bool,
+    ) -> [i32; 4] {
+        let tile = &self.tiles[index];
+        let (s, t) = tile.local_coords(s, t);
+
+        if !filter || modes.sample_type != SampleType::Bilinear {
+            let (s, t) = tile.wrap(s >> 5, t >> 5);
+            return self.texel(tile, s, t, modes);
+        }
+
+        let (s_int, s_frac) = (s >> 5, s & 31);
+        let (t_int, t_frac) = (t >> 5, t & 31);
+
+        let fetch = |s_offset: i32, t_offset: i32| {
+            let (s, t) = tile.wrap(s_int + s_offset, t_int + t_offset);
+            self.texel(tile, s, t, modes)
+        };
+
+        let texel00 = fetch(0, 0);
+        let texel10 = fetch(1, 0);
+        let texel01 = fetch(0, 1);
+        let texel11 = fetch(1, 1);
+
+        // The RDP filters between three texels rather than four, picking the
+        // triangle of the texel square that the sample point falls within
+        let mut result = [0; 4];
+
+        for (channel, value) in result.iter_mut().enumerate() {
+            *value = if (s_frac + t_frac) < 32 {
+                texel00[channel]
+                    + (((texel10[channel] - texel00[channel]) * s_frac
+                        + (texel01[channel] - texel00[channel]) * t_frac
+                        + 0x10)
+                        >> 5)
+            } else {
+                texel11[channel]
+                    + (((texel01[channel] - texel11[channel]) * (32 - s_frac)
+                        + (texel10[channel] - texel11[channel]) * (32 - t_frac)
+                        + 0x10)
+                        >> 5)
+            };
+        }
+
+        result
+    }
+
+    // Used by copy mode, which writes texels without any conversion (other
+    // than TLUT lookup). Returns the raw value and its alpha bit.
+    pub fn fetch_raw(&self, index: usize, s: i32, t: i32, en_tlut: bool) -> (u32, bool) {
+        let tile = &self.tiles[index];
+        let (s, t) = tile.local_coords(s, t);
+        let (s, t) = tile.wrap(s >> 5, t >> 5);
+
+        let value = match tile.descriptor.format.1 {
+            0 | 1 if en_tlut => self.tlut_entry(self.palette_index(tile, s, t)) as u32,
+            0 | 1 => self.read_index(tile, s, t),
+            2 => self.read_u16(tile.texel_offset(s, t, 2, LOW_HALF_MASK | HIGH_HALF)) as u32,
+            _ => {
+                let offset = tile.texel_offset(s, t, 2, LOW_HALF_MASK);
+                ((self.read_u16(offset) as u32) << 16) | self.read_u16(offset | HIGH_HALF) as u32
+            }
+        };
+
+        (value, (value & 1) != 0)
+    }
+
+    fn texel(&self, tile: &Tile, s: i32, t: i32, modes: &OtherModes) -> [i32; 4] {
+        let (format, texel_size) = tile.descriptor.format;
+
+        if texel_size < 2 {
+            if modes.en_tlut {
+                let entry = self.tlut_entry(self.palette_index(tile, s, t));
+
+                return match modes.tlut_type {
+                    TlutType::Rgba16 => decode_rgba16(entry),
+                    TlutType::Ia16 => decode_ia16(entry),
+                };
+            }
+
+            let index = self.read_index(tile, s, t);
+
+            return match (format, texel_size) {
+                (Format::IA, 0) => decode_ia4(index),
+                (Format::IA, _) => decode_ia8(index),
+                (_, 0) => [(index * 17) as i32; 4],
+                _ => [index as i32; 4],
+            };
+        }
+
+        if texel_size == 2 {
+            let mask = if modes.en_tlut {
+                LOW_HALF_MASK
+            } else {
+                LOW_HALF_MASK | HIGH_HALF
+            };
+
+            let word = self.read_u16(tile.texel_offset(s, t, 2, mask));
+
+            return match format {
+                Format::IA => decode_ia16(word),
+                Format::Yuv => {
+                    // Each pair of texels shares its chroma, with the texels
+                    // stored as U, Y0, V, Y1
+                    let u = self.data[tile.texel_offset(s & !1, t, 2, mask)] as i32;
+                    let v = self.data[tile.texel_offset(s | 1, t, 2, mask)] as i32;
+                    let y = (word & 0xff) as i32;
+                    [u - 0x80, v - 0x80, y, y]
+                }
+                _ => decode_rgba16(word),
+            };
+        }
+
+        let offset = tile.texel_offset(s, t, 2, LOW_HALF_MASK);
+        let high = self.read_u16(offset);
+        let low = self.read_u16(offset | HIGH_HALF);
+
+        [
+            (high >> 8) as i32,
+            (high & 0xff) as i32,
+            (low >> 8) as i32,
+            (low & 0xff) as i32,
+        ]
+    }
+
+    fn read_index(&self, tile: &Tile, s: i32, t: i32) -> u32 {
+        let texel_size = tile.descriptor.format.1;
+        let offset = tile.texel_offset(s >> (1 - texel_size), t, 1, LOW_HALF_MASK | HIGH_HALF);
+        let byte = self.data[offset] as u32;
+
+        if texel_size == 0 {
+            if (s & 1) == 0 {
+                byte >> 4
+            } else {
+                byte & 15
+            }
+        } else {
+            byte
+        }
+    }
+
+    // 4-bit indices select an entry within the tile's 16-entry palette
+    fn palette_index(&self, tile: &Tile, s: i32, t: i32) -> u32 {
+        let index = self.read_index(tile, s, t);
+
+        if tile.descriptor.format.1 == 0 {
+            ((tile.descriptor.palette << 4) | index) & 0xff
+        } else {
+            index
+        }
+    }
+
+    fn tlut_entry(&self, index: u32) -> u16 {
+        self.read_u16(HIGH_HALF + (index as usize & 0xff) * 8)
+    }
+
+    fn read_u16(&self, offset: usize) -> u16 {
+        ((self.data[offset] as u16) << 8) | self.data[offset + 1] as u16
+    }
+
+    fn write_u16(&mut self, offset: usize, value: u16) {
+        self.data[offset] = (value >> 8) as u8;
+        self.data[offset + 1] = value as u8;
+    }
+}
+
+impl Tile {
+    fn local_coords(&self, s: i32, t: i32) -> (i32, i32) {
+        // Apply shift, then make relative to the tile origin (both in s10.5)
+        let s = shift(s, self.descriptor.address_s.shift) - ((self.size.sl as i32) << 3);
+        let t = shift(t, self.descriptor.address_t.shift) - ((self.size.tl as i32) << 3);
+        (s, t)
+    }
+
+    fn wrap(&self, s: i32, t: i32) -> (i32, i32) {
+        let s_max = ((self.size.sh as i32 - self.size.sl as i32) >> 2).max(0);
+        let t_max = ((self.size.th as i32 - self.size.tl as i32) >> 2).max(0);
+
+        (
+            wrap(s, &self.descriptor.address_s, s_max),
+            wrap(t, &self.descriptor.address_t, t_max),
+        )
+    }
+
+    fn texel_offset(&self, s: i32, t: i32, bytes_per_texel: usize, mask: usize) -> usize {
+        let line = self.descriptor.tmem_addr as usize * 8
+            + t as usize * self.descriptor.width as usize * 8;
+
+        let swap = if (t & 1) != 0 { ODD_LINE_SWAP } else { 0 };
+        ((line + s as usize * bytes_per_texel) ^ swap) & mask
+    }
+}
+
+fn shift(coord: i32, shift: u32) -> i32 {
+    if shift < 11 {
+        coord >> shift
+    } else {
+        coord << (16 - shift)
+    }
+}
+
+fn wrap(coord: i32, mode: &TileAddressMode, max: i32) -> i32 {
+    let mut coord = coord;
+
+    if mode.clamp || mode.mask == 0 {
+        coord = coord.clamp(0, max);
+    }
+
+    if mode.mask != 0 {
+        let mask = mode.mask.min(10);
+
+        if mode.mirror && ((coord >> mask) & 1) != 0 {
+            coord = !coord;
+        }
+
+        coord &= (1 << mask) - 1;
+    }
+
+    coord
+}
+
+fn decode_rgba16(word: u16) -> [i32; 4] {
+    let expand = |value: u16| {
+        let value = (value & 31) as i32;
+        (value << 3) | (value >> 2)
+    };
+
+    [
+        expand(word >> 11),
+        expand(word >> 6),
+        expand(word >> 1),
+        if (word & 1) != 0 { 0xff } else { 0 },
+    ]
+}
+
+fn decode_ia16(word: u16) -> [i32; 4] {
+    let intensity = (word >> 8) as i32;
+    [intensity, intensity, intensity, (word & 0xff) as i32]
+}
+
+fn decode_ia8(value: u32) -> [i32; 4] {
+    let intensity = ((value >> 4) * 17) as i32;
+    [intensity, intensity, intensity, ((value & 15) * 17) as i32]
+}
+
+fn decode_ia4(value: u32) -> [i32; 4] {
+    let level = (value >> 1) & 7;
+    let intensity = ((level << 5) | (level << 2) | (level >> 1)) as i32;
+    let alpha = if (value & 1) != 0 { 0xff } else { 0 };
+    [intensity, intensity, intensity, alpha]
+}
@@ -1,7 +1,10 @@
 use crate::header::CicType;
 use crate::memory::{Memory, Size, WriteMask};
 use crate::mips_interface::MipsInterface;
+use crate::snapshot::{Reader, Writer};
+use crate::watch::Watch;
 use regs::{Delay, Mode, RasInterval, RefRow, RiConfig, RiMode, RiRefresh, RiSelect};
+use std::error::Error;
 use tracing::{debug, warn};
 
 mod regs;
@@ -27,16 +30,19 @@ struct Interface {
 
 pub struct Rdram {
     mem: Memory<u64>,
+    watch: Watch,
     modules: Vec<Module>,
     ri: Interface,
 }
 
 impl Rdram {
-    pub fn new(cic_type: CicType) -> Self {
+    pub fn new(cic_type: CicType, expansion_pak: bool) -> Self {
         let mut mem = Memory::with_byte_len(8 * BANK_SIZE);
 
         // RAM size detection doesn't currently work, so populate the specific
-        // destinations in memory with the RAM size (8MB) based on CIC type
+        // destinations in memory with the RAM size based on CIC type. Without
+        // the Expansion Pak, the upper 4MB is still there but games won't use
+        // it.
         let ram_size_address = match cic_type {
             CicType::Nus6101 | CicType::Nus6102 | CicType::MiniIPL3 => Some(0x0318),
             CicType::Nus6105 => Some(0x03f0),
@@ -44,11 +50,13 @@ impl Rdram {
         };
 
         if let Some(address) = ram_size_address {
-            mem.write(address, 0x0080_0000u32);
+            let size = if expansion_pak { 8 } else { 4 } * BANK_SIZE;
+            mem.write(address, size as u32);
         }
 
         Self {
             mem,
+            watch: Watch::default(),
             modules: (0..4)
                 .map(|_| Module {
                     device_id: 0xffff,
@@ -64,19 +72,97 @@ impl Rdram {
         }
     }
 
+    pub fn save_state(&self, writer: &mut Writer) {
+        writer.tag(b"RDRM");
+        writer.bytes(self.mem.as_bytes());
+
+        for module in &self.modules {
+            writer.u32(module.device_id);
+            writer.reg(module.delay);
+            writer.reg(module.mode);
+            writer.reg(module.ref_row);
+            writer.reg(module.ras_interval);
+        }
+
+        writer.reg(self.ri.mode);
+        writer.reg(self.ri.config);
+        writer.reg(self.ri.select);
+        writer.reg(self.ri.refresh);
+    }
+
+    pub fn load_state(&mut self, reader: &mut Reader) -> Result<(), Box<dyn Error>> {
+        reader.tag(b"RDRM")?;
+        reader.bytes_into(self.mem.as_bytes_mut())?;
+
+        for module in &mut self.modules {
+            module.device_id = reader.u32()?;
+            module.delay = reader.reg()?;
+            module.mode = reader.reg()?;
+            module.ref_row = reader.reg()?;
+            module.ras_interval = reader.reg()?;
+        }
+
+        self.ri.mode = reader.reg()?;
+        self.ri.config = reader.reg()?;
+        self.ri.select = reader.reg()?;
+        self.ri.refresh = reader.reg()?;
+        Ok(())
+    }
+
+    // All accesses to RDRAM data pass through here, whether from the CPU or
+    // from DMA, so this is where watchpoints are checked
+    pub fn watch(&self) -> &Watch {
+        &self.watch
+    }
+
+    pub fn watch_mut(&mut self) -> &mut Watch {
+        &mut self.watch
+    }
+
+    // Raw contents, bypassing watchpoints
+    pub fn as_bytes(&self) -> &[u8] {
+        self.mem.as_bytes()
+    }
+
+    pub fn as_bytes_mut(&mut self) -> &mut [u8] {
+        self.mem.as_bytes_mut()
+    }
+
     pub fn read_single<T: Size>(&self, address: usize) -> T {
-        self.mem.read_or_zero(address)
+        let value = self.mem.read_or_zero(address);
+
+        if self.watch.is_active() {
+            self.watch
+                .check(address as u32, bytemuck::bytes_of(&T::to_be(value)), false);
+        }
+
+        value
     }
 
     pub fn write_single<T: Size>(&mut self, address: usize, value: T) {
+        if self.watch.is_active() {
+            self.watch
+                .check(address as u32, bytemuck::bytes_of(&T::to_be(value)), true);
+        }
+
         self.mem.write_or_ignore(address, value);
     }
 
     pub fn read_block<T: Size>(&self, address: usize, data: &mut [T]) {
         self.mem.read_or_zero_block(address, data);
+
+        if self.watch.is_active() {
+            self.watch
+                .check(address as u32, bytemuck::cast_slice(data), false);
+        }
     }
 
     pub fn write_block<T: Size>(&mut self, address: usize, data: &[T]) {
+        if self.watch.is_active() {
+            self.watch
+                .check(address as u32, bytemuck::cast_slice(data), true);
+        }
+
         self.mem.write_or_ignore_block(address, data);
     }
 
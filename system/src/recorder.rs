@@ -0,0 +1,154 @@
+use crate::audio::AudioReceiver;
+use crate::video::{DisplayMode, Frame};
+use crate::wav::WavWriter;
+use std::io::{self, Seek, Write};
+
+// Writes video frames to a Y4M stream and audio samples to a WAV file. Both
+// are timed by emulated time rather than by how many frames or samples are
+// received, so the two stay in sync.
+pub struct Recorder<V: Write, A: Write + Seek> {
+    video: V,
+    audio: WavWriter<A>,
+    frame_rate: f64,
+    // Set once the first visible frame has been seen
+    size: Option<(u32, u32)>,
+    frames_written: u64,
+}
+
+impl<V: Write, A: Write + Seek> Recorder<V, A> {
+    pub fn new(video: V, audio: A, frame_rate: f64, sample_rate: u32) -> io::Result<Self> {
+        Ok(Self {
+            video,
+            audio: WavWriter::new(audio, sample_rate)?,
+            frame_rate,
+            size: None,
+            frames_written: 0,
+        })
+    }
+
+    pub fn write_samples(&mut self, samples: &[(i16, i16)], sample_rate: u32) -> io::Result<()> {
+        self.audio.set_sample_rate(sample_rate);
+        self.audio.write_samples(samples)
+    }
+
+    // Writes 'frame' as many times as needed to bring the video up to
+    // 'elapsed' seconds. Frames are scaled to the size of the first visible
+    // frame, as every frame in the stream must be the same size.
+    pub fn write_frame(&mut self, frame: &Frame, elapsed: f64) -> io::Result<()> {
+        let visible = frame.display_mode != DisplayMode::Blank;
+
+        let (width, height) = match self.size {
+            Some(size) => size,
+            None if visible => {
+                writeln!(
+                    self.video,
+                    "YUV4MPEG2 W{} H{} F{}:1000 Ip A1:1 C444",
+                    frame.width,
+                    frame.height,
+                    (self.frame_rate * 1000.0).round() as u64
+                )?;
+
+                self.size = Some((frame.width, frame.height));
+                (frame.width, frame.height)
+            }
+            // Nothing to size the stream by yet
+            None => return Ok(()),
+        };
+
+        let target = (elapsed * self.frame_rate).round() as u64;
+
+        if self.frames_written >= target {
+            return Ok(());
+        }
+
+        let planes = if visible {
+            convert_frame(frame, width, height)
+        } else {
+            black_frame(width, height)
+        };
+
+        while self.frames_written < target {
+            self.video.write_all(b"FRAME\n")?;
+            self.video.write_all(&planes)?;
+            self.frames_written += 1;
+        }
+
+        Ok(())
+    }
+
+    pub fn finish(mut self) -> io::Result<()> {
+        self.video.flush()?;
+        self.audio.finish()
+    }
+}
+
+// Converts RGBA8 pixels to Y, Cb and Cr planes (BT.601, limited range),
+// scaling with nearest neighbour sampling if the size has changed
+fn convert_frame(frame: &Frame, width: u32, height: u32) -> Vec<u8> {
+    let plane_len = width as usize * height as usize;
+    let mut planes = vec![0; plane_len * 3];
+
+    for y in 0..height {
+        let src_y = y * frame.height / height;
+
+        for x in 0..width {
+            let src_x = x * frame.width / width;
+            let src = (src_y as usize * frame.width as usize + src_x as usize) * 4;
+            let [r, g, b] = [0, 1, 2].map(|index| frame.pixels[src + index] as f64);
+
+            let dst = y as usize * width as usize + x as usize;
+            planes[dst] = (16.0 + (65.738 * r + 129.057 * g + 25.064 * b) / 256.0) as u8;
+
+            planes[plane_len + dst] =
+                (128.0 + (-37.945 * r - 74.494 * g + 112.439 * b) / 256.0) as u8;
+
+            planes[plane_len * 2 + dst] =
+                (128.0 + (112.439 * r - 94.154 * g - 18.285 * b) / 256.0) as u8;
+        }
+    }
+
+    planes
+}
+
+fn black_frame(width: u32, height: u32) -> Vec<u8> {
+    let plane_len = width as usize * height as usize;
+    let mut planes = vec![128; plane_len * 3];
+    planes[0..plane_len].fill(16);
+    planes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::video::AntiAliasMode;
+    use std::io::Cursor;
+
+    #[test]
+    fn repeats_frames_to_keep_time() {
+        let mut video = Vec::new();
+        let mut audio = Cursor::new(Vec::new());
+        let mut recorder = Recorder::new(&mut video, &mut audio, 50.0, 24000).unwrap();
+
+        let pixels = [0xff; 2 * 2 * 4];
+
+        let frame = Frame {
+            width: 2,
+            height: 2,
+            display_mode: DisplayMode::Color16,
+            aa_mode: AntiAliasMode::Off,
+            pixels: &pixels,
+        };
+
+        recorder.write_samples(&[(0, 0); 24000], 24000).unwrap();
+
+        // A frame that took long enough to be shown twice
+        recorder.write_frame(&frame, 0.04).unwrap();
+        recorder.write_frame(&frame, 0.06).unwrap();
+        recorder.finish().unwrap();
+
+        assert!(video.starts_with(b"YUV4MPEG2 W2 H2 F50000:1000 Ip A1:1 C444\n"));
+        assert_eq!(video.windows(6).filter(|w| w == b"FRAME\n").count(), 3);
+
+        assert!(audio.into_inner().starts_with(b"RIFF"));
+    }
+}
@@ -0,0 +1,219 @@
+use std::collections::VecDeque;
+use std::error::Error;
+use std::mem;
+
+// Granularity of delta comparisons. RDRAM makes up the bulk of each snapshot,
+// and most of it is unchanged from one frame to the next.
+const CHUNK_SIZE: usize = 64;
+
+// Frames between snapshots. Taking a snapshot means syncing the renderer and
+// serializing all of RDRAM, which is too slow to do every frame.
+pub const INTERVAL: usize = 4;
+
+// Keeps a history of snapshots taken every few frames. Only the most recent
+// snapshot is stored in full. Each older one is stored as a delta against the
+// snapshot that followed it, so stepping backwards is just a matter of applying
+// deltas in reverse order, and the oldest can be discarded at any time.
+pub struct Rewind {
+    budget: usize,
+    size: usize,
+    latest: Option<Vec<u8>>,
+    history: VecDeque<Vec<u8>>,
+    // Frames run since the latest snapshot
+    frames: usize,
+}
+
+impl Rewind {
+    pub fn new(budget: usize) -> Self {
+        Self {
+            budget,
+            size: 0,
+            latest: None,
+            history: VecDeque::new(),
+            frames: 0,
+        }
+    }
+
+    // Called at the end of each frame. Returns whether a snapshot is due.
+    pub fn end_frame(&mut self) -> bool {
+        self.frames += 1;
+
+        if self.frames < INTERVAL {
+            return false;
+        }
+
+        self.frames = 0;
+        true
+    }
+
+    pub fn push(&mut self, state: Vec<u8>) {
+        if let Some(latest) = self.latest.take() {
+            let delta = encode(&state, &latest);
+            self.size += delta.len();
+            self.size -= latest.len();
+            self.history.push_back(delta);
+        }
+
+        self.size += state.len();
+        self.latest = Some(state);
+
+        while self.size > self.budget {
+            let Some(delta) = self.history.pop_front() else {
+                break;
+            };
+
+            self.size -= delta.len();
+        }
+    }
+
+    // Goes back by the given number of frames, rounded up to the next
+    // snapshot, returning it along with the number of frames actually
+    // rewound. This may be fewer than requested if the history is not long
+    // enough.
+    pub fn rewind(&mut self, frames: usize) -> Option<(&[u8], usize)> {
+        let mut state = self.latest.take()?;
+        let mut rewound = mem::take(&mut self.frames);
+
+        while rewound < frames {
+            let Some(delta) = self.history.pop_back() else {
+                break;
+            };
+
+            self.size -= state.len() + delta.len();
+            // Deltas in the history are only ever produced by 'encode'
+            state = decode(&state, &delta).expect("Rewind history is corrupt");
+            self.size += state.len();
+            rewound += INTERVAL;
+        }
+
+        Some((self.latest.insert(state), rewound))
+    }
+}
+
+// Encodes 'target' as a series of runs against 'base'. Each run is a count of
+// bytes to copy from 'base' at the same offset, followed by a count of literal
+// bytes and the bytes themselves.
+pub fn encode(base: &[u8], target: &[u8]) -> Vec<u8> {
+    let mut delta = Vec::new();
+    delta.extend_from_slice(&(target.len() as u32).to_le_bytes());
+
+    let mut pos = 0;
+
+    while pos < target.len() {
+        let start = pos;
+
+        while pos < target.len() && chunk_matches(base, target, pos) {
+            pos += CHUNK_SIZE;
+        }
+
+        let copy_len = pos - start;
+        let literal_start = pos;
+
+        while pos < target.len() && !chunk_matches(base, target, pos) {
+            pos = (pos + CHUNK_SIZE).min(target.len());
+        }
+
+        delta.extend_from_slice(&(copy_len as u32).to_le_bytes());
+        delta.extend_from_slice(&((pos - literal_start) as u32).to_le_bytes());
+        delta.extend_from_slice(&target[literal_start..pos]);
+    }
+
+    delta
+}
+
+// Deltas may come from a file, so they're checked rather than trusted
+pub fn decode(base: &[u8], delta: &[u8]) -> Result<Vec<u8>, Box<dyn Error>> {
+    let read_u32 = |offset: usize| -> Result<usize, Box<dyn Error>> {
+        let bytes = delta
+            .get(offset..(offset + 4))
+            .ok_or("Delta is truncated")?;
+        Ok(u32::from_le_bytes(bytes.try_into().unwrap()) as usize)
+    };
+
+    let len = read_u32(0)?;
+    let mut target = Vec::with_capacity(len.min(base.len() + delta.len()));
+    let mut offset = 4;
+
+    while offset < delta.len() {
+        let copy_len = read_u32(offset)?;
+        let literal_len = read_u32(offset + 4)?;
+        offset += 8;
+
+        let pos = target.len();
+
+        let copy = base
+            .get(pos..(pos + copy_len))
+            .ok_or("Delta copies past the end of its base")?;
+
+        let literal = delta
+            .get(offset..(offset + literal_len))
+            .ok_or("Delta is truncated")?;
+
+        target.extend_from_slice(copy);
+        target.extend_from_slice(literal);
+        offset += literal_len;
+    }
+
+    if target.len() != len {
+        return Err("Delta length does not match its contents".into());
+    }
+
+    Ok(target)
+}
+
+fn chunk_matches(base: &[u8], target: &[u8], pos: usize) -> bool {
+    let end = pos + CHUNK_SIZE;
+    end <= target.len() && end <= base.len() && base[pos..end] == target[pos..end]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn delta_round_trip() {
+        let base: Vec<u8> = (0..1000).map(|value| value as u8).collect();
+        let mut target = base.clone();
+        target[10] = 0xff;
+        target[500..600].fill(0);
+        target.extend_from_slice(&[1, 2, 3]);
+
+        let delta = encode(&base, &target);
+        assert!(delta.len() < target.len());
+        assert_eq!(decode(&base, &delta).unwrap(), target);
+        assert_eq!(decode(&target, &encode(&target, &base)).unwrap(), base);
+
+        // Corrupt deltas are rejected
+        assert!(decode(&base, &delta[..(delta.len() - 1)]).is_err());
+        assert!(decode(&base[..10], &delta).is_err());
+        assert!(decode(&base, &[0xff; 4]).is_err());
+    }
+
+    #[test]
+    fn rewind_respects_budget() {
+        let mut rewind = Rewind::new(4096);
+
+        for frame in 0..16u8 {
+            let mut state = vec![0; 1024];
+            state[0] = frame;
+            rewind.push(state);
+        }
+
+        assert!(rewind.size <= rewind.budget);
+
+        let (state, frames) = rewind.rewind(3 * INTERVAL).unwrap();
+        assert_eq!(state[0], 12);
+        assert_eq!(frames, 3 * INTERVAL);
+
+        // Frames run since the latest snapshot count towards the total, and
+        // anything in between snapshots is rounded up
+        rewind.end_frame();
+        let (state, frames) = rewind.rewind(2).unwrap();
+        assert_eq!(state[0], 11);
+        assert_eq!(frames, 1 + INTERVAL);
+
+        let (state, frames) = rewind.rewind(usize::MAX).unwrap();
+        assert!(state[0] < 11);
+        assert!(frames > 0);
+    }
+}
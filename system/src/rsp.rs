@@ -2,8 +2,11 @@ use crate::interrupt::{RcpIntType, RcpInterrupt};
 use crate::memory::{Memory, Size, WriteMask};
 use crate::rdp::RdpShared;
 use crate::rdram::Rdram;
-use core::Core;
+use crate::snapshot::{Reader, Writer};
+pub use core::{disassemble, disassemble_imem, Core};
+
 use regs::{DmaLength, DmaRamAddr, DmaSpAddr, Regs, Status};
+use std::error::Error;
 use tracing::{debug, debug_span, trace};
 
 mod core;
@@ -20,6 +23,26 @@ struct Dma {
     write: bool,
 }
 
+impl Dma {
+    fn save_state(&self, writer: &mut Writer) {
+        writer.reg(self.sp_addr);
+        writer.reg(self.ram_addr);
+        writer.reg(self.len);
+        writer.u32(self.reload_len);
+        writer.bool(self.write);
+    }
+
+    fn load_state(reader: &mut Reader) -> Result<Self, Box<dyn Error>> {
+        Ok(Self {
+            sp_addr: reader.reg()?,
+            ram_addr: reader.reg()?,
+            len: reader.reg()?,
+            reload_len: reader.u32()?,
+            write: reader.bool()?,
+        })
+    }
+}
+
 struct RspShared {
     mem: Memory<u128>,
     regs: Regs,
@@ -73,10 +96,71 @@ impl Rsp {
         }
     }
 
+    pub fn save_state(&self, writer: &mut Writer) {
+        let shared = &self.shared;
+
+        writer.tag(b"RSP ");
+        writer.bytes(shared.mem.as_bytes());
+        writer.reg(shared.regs.dma_sp_addr);
+        writer.reg(shared.regs.dma_ram_addr);
+        writer.reg(shared.regs.status);
+        writer.bool(shared.regs.semaphore.get());
+        writer.bool(shared.dma_in_progress);
+        shared.dma_active.save_state(writer);
+        writer.bool(shared.dma_pending.is_some());
+
+        if let Some(dma_pending) = &shared.dma_pending {
+            dma_pending.save_state(writer);
+        }
+
+        self.core.save_state(writer);
+    }
+
+    pub fn load_state(&mut self, reader: &mut Reader) -> Result<(), Box<dyn Error>> {
+        let shared = &mut self.shared;
+
+        reader.tag(b"RSP ")?;
+        reader.bytes_into(shared.mem.as_bytes_mut())?;
+        shared.regs.dma_sp_addr = reader.reg()?;
+        shared.regs.dma_ram_addr = reader.reg()?;
+        shared.regs.status = reader.reg()?;
+        shared.regs.semaphore.set(reader.bool()?);
+        shared.dma_in_progress = reader.bool()?;
+        shared.dma_active = Dma::load_state(reader)?;
+
+        shared.dma_pending = if reader.bool()? {
+            Some(Dma::load_state(reader)?)
+        } else {
+            None
+        };
+
+        self.core.load_state(reader)
+    }
+
     pub fn mem(&self) -> &Memory<u128> {
         &self.shared.mem
     }
 
+    pub fn mem_mut(&mut self) -> &mut Memory<u128> {
+        &mut self.shared.mem
+    }
+
+    pub fn is_running(&self) -> bool {
+        !self.shared.regs.status.halted()
+    }
+
+    pub fn status(&self) -> u32 {
+        self.shared.read_register(4)
+    }
+
+    pub fn core(&self) -> &Core {
+        &self.core
+    }
+
+    pub fn core_mut(&mut self) -> &mut Core {
+        &mut self.core
+    }
+
     #[cfg(feature = "profiling")]
     pub fn stats(&self) -> &Stats {
         &self.stats
@@ -1,9 +1,14 @@
+pub use disassembler::{disassemble, disassemble_imem};
+
 use crate::memory::Size;
+use crate::snapshot::{Reader, Writer};
 use cp2::Cp2;
+use std::error::Error;
 use tracing::trace;
 
 mod cp0;
 mod cp2;
+mod disassembler;
 mod instruction;
 
 pub trait Bus {
@@ -25,12 +30,14 @@ pub struct Core {
 }
 
 impl Core {
-    const REG_NAMES: [&'static str; 32] = [
+    pub const REG_NAMES: [&'static str; 32] = [
         "ZERO", "AT", "V0", "V1", "A0", "A1", "A2", "A3", "T0", "T1", "T2", "T3", "T4", "T5", "T6",
         "T7", "S0", "S1", "S2", "S3", "S4", "S5", "S6", "S7", "T8", "T9", "K0", "K1", "GP", "SP",
         "FP", "RA",
     ];
 
+    pub const CP2_CONTROL_REG_NAMES: [&'static str; 32] = Cp2::CONTROL_REG_NAMES;
+
     pub fn new() -> Self {
         Self {
             opcode: [0; 2],
@@ -42,10 +49,76 @@ impl Core {
         }
     }
 
+    pub fn save_state(&self, writer: &mut Writer) {
+        writer.tag(b"RSPC");
+
+        for opcode in self.opcode {
+            writer.u32(opcode);
+        }
+
+        for delay in self.delay {
+            writer.bool(delay);
+        }
+
+        for pc in self.pc {
+            writer.u32(pc);
+        }
+
+        writer.bool(self.broke);
+
+        for reg in self.regs {
+            writer.i32(reg);
+        }
+
+        self.cp2.save_state(writer);
+    }
+
+    pub fn load_state(&mut self, reader: &mut Reader) -> Result<(), Box<dyn Error>> {
+        reader.tag(b"RSPC")?;
+
+        for opcode in &mut self.opcode {
+            *opcode = reader.u32()?;
+        }
+
+        for delay in &mut self.delay {
+            *delay = reader.bool()?;
+        }
+
+        for pc in &mut self.pc {
+            *pc = reader.u32()?;
+        }
+
+        self.broke = reader.bool()?;
+
+        for reg in &mut self.regs {
+            *reg = reader.i32()?;
+        }
+
+        self.cp2.load_state(reader)
+    }
+
     pub fn pc(&self) -> u32 {
         self.pc[2]
     }
 
+    // Address of the next instruction to be executed. This will repeat while
+    // the pipeline refills after a write to SP_PC.
+    pub fn current_pc(&self) -> u32 {
+        self.pc[0]
+    }
+
+    pub fn reg(&self, reg: usize) -> i32 {
+        self.regs[reg]
+    }
+
+    pub fn cp2(&self) -> &Cp2 {
+        &self.cp2
+    }
+
+    pub fn cp2_mut(&mut self) -> &mut Cp2 {
+        &mut self.cp2
+    }
+
     pub fn set_pc(&mut self, value: u32) {
         self.opcode = [0; 2];
         self.delay = [false; 2];
@@ -53,6 +126,12 @@ impl Core {
     }
 
     pub fn step(&mut self, bus: &mut impl Bus) {
+        trace!(
+            "{:08X}: {}",
+            self.pc[0],
+            disassemble(self.pc[0], self.opcode[0])
+        );
+
         instruction::execute(self, bus);
 
         if self.broke {
@@ -72,7 +151,7 @@ impl Core {
         self.pc[2] = self.pc[2].wrapping_add(4) & 0x0ffc;
     }
 
-    fn set_reg(&mut self, reg: usize, value: i32) {
+    pub fn set_reg(&mut self, reg: usize, value: i32) {
         self.regs[reg] = value;
         self.regs[0] = 0;
         trace!("  {}: {:08X}", Self::REG_NAMES[reg], value);
@@ -4,7 +4,7 @@ use super::{Bus, Core};
 
 mod instruction;
 
-const REG_NAMES: [&str; 16] = [
+pub const REG_NAMES: [&str; 16] = [
     "SP_DMA_SPADDR",
     "SP_DMA_RAMADDR",
     "SP_DMA_RDLEN",
@@ -1,5 +1,4 @@
-use super::{Bus, Core, REG_NAMES};
-use tracing::trace;
+use super::{Bus, Core};
 
 pub fn cop0(core: &mut Core, bus: &mut impl Bus) {
     match (core.opcode[0] >> 21) & 31 {
@@ -13,13 +12,6 @@ pub fn mfc0(core: &mut Core, bus: &mut impl Bus) {
     let rt = ((core.opcode[0] >> 16) & 31) as usize;
     let rd = ((core.opcode[0] >> 11) & 31) as usize;
 
-    trace!(
-        "{:08X}: MFC0 {}, {}",
-        core.pc[0],
-        Core::REG_NAMES[rt],
-        REG_NAMES[rd]
-    );
-
     let value = bus.read_register(rd) as i32;
     core.set_reg(rt, value);
 }
@@ -28,12 +20,5 @@ pub fn mtc0(core: &mut Core, bus: &mut impl Bus) {
     let rt = ((core.opcode[0] >> 16) & 31) as usize;
     let rd = ((core.opcode[0] >> 11) & 31) as usize;
 
-    trace!(
-        "{:08X}: MTC0 {}, {}",
-        core.pc[0],
-        Core::REG_NAMES[rt],
-        REG_NAMES[rd]
-    );
-
     bus.write_register(rd, core.regs[rt] as u32);
 }
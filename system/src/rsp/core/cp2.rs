@@ -2,8 +2,10 @@ pub use instruction::{cop2, lwc2, swc2};
 pub use regs::Vector;
 
 use super::{Bus, Core};
+use crate::snapshot::{Reader, Writer};
 use regs::{Accumulator, FlagVector, Flags};
 use std::array;
+use std::error::Error;
 use tracing::{trace, warn};
 
 mod instruction;
@@ -23,7 +25,7 @@ pub struct Cp2 {
 }
 
 impl Cp2 {
-    const CONTROL_REG_NAMES: [&'static str; 32] = [
+    pub const CONTROL_REG_NAMES: [&'static str; 32] = [
         "VCO", "VCC", "VCE", "VC3", "VC4", "VC5", "VC6", "VC7", "VC8", "VC9", "VC10", "VC11",
         "VC12", "VC13", "VC14", "VC15", "VC16", "VC17", "VC18", "VC19", "VC20", "VC21", "VC22",
         "VC23", "VC24", "VC25", "VC26", "VC27", "VC28", "VC29", "VC30", "VC31",
@@ -42,6 +44,55 @@ impl Cp2 {
         }
     }
 
+    pub fn save_state(&self, writer: &mut Writer) {
+        writer.tag(b"CP2 ");
+
+        for reg in self.regs {
+            for lane in reg.to_le_array() {
+                writer.u16(lane);
+            }
+        }
+
+        for value in self.acc.as_le_array() {
+            writer.u64(*value);
+        }
+
+        for flags in self.flags.as_le_array() {
+            writer.u8(flags.bits());
+        }
+
+        writer.u32(self.rcp_in);
+        writer.u32(self.rcp_out);
+        writer.bool(self.rcp_high);
+    }
+
+    pub fn load_state(&mut self, reader: &mut Reader) -> Result<(), Box<dyn Error>> {
+        reader.tag(b"CP2 ")?;
+
+        for reg in &mut self.regs {
+            let mut lanes = [0; 8];
+
+            for lane in &mut lanes {
+                *lane = reader.u16()?;
+            }
+
+            *reg = Vector::from_le_array(lanes);
+        }
+
+        for value in self.acc.as_le_array_mut() {
+            *value = reader.u64()?;
+        }
+
+        for flags in self.flags.as_le_array_mut() {
+            *flags = Flags::from_bits_retain(reader.u8()?);
+        }
+
+        self.rcp_in = reader.u32()?;
+        self.rcp_out = reader.u32()?;
+        self.rcp_high = reader.bool()?;
+        Ok(())
+    }
+
     pub fn reg(&self, index: usize) -> Vector {
         self.regs[index]
     }
@@ -51,6 +102,15 @@ impl Cp2 {
         trace!("  V{:02}: {}", index, self.regs[index]);
     }
 
+    // Accumulator lanes, each holding a 48-bit value
+    pub fn acc(&self) -> &[u64; 8] {
+        self.acc.as_le_array()
+    }
+
+    pub fn acc_mut(&mut self) -> &mut [u64; 8] {
+        self.acc.as_le_array_mut()
+    }
+
     pub fn control_reg(&self, index: usize) -> i32 {
         if index > 2 {
             warn!(
@@ -1,9 +1,7 @@
 use super::{Core, Flags, Vector};
 use std::cmp::Ordering;
-use tracing::trace;
 
 pub trait ComputeOperator {
-    const NAME: &'static str;
     fn apply(flags: &mut Flags, acc: &mut u64, lhs: u16, rhs: u16) -> u16;
 }
 
@@ -32,8 +30,6 @@ pub struct VXor;
 pub struct VNxor;
 
 impl ComputeOperator for VMulf {
-    const NAME: &'static str = "VMULF";
-
     fn apply(_flags: &mut Flags, acc: &mut u64, lhs: u16, rhs: u16) -> u16 {
         let result = (lhs as i16 as i64 * rhs as i16 as i64) << 1;
         *acc = (0x8000 + result) as u64 & 0xffff_ffff_ffff;
@@ -42,8 +38,6 @@ impl ComputeOperator for VMulf {
 }
 
 impl ComputeOperator for VMulu {
-    const NAME: &'static str = "VMULU";
-
     fn apply(_flags: &mut Flags, acc: &mut u64, lhs: u16, rhs: u16) -> u16 {
         let result = (lhs as i16 as i64 * rhs as i16 as i64) << 1;
         *acc = (0x8000 + result) as u64 & 0xffff_ffff_ffff;
@@ -61,8 +55,6 @@ impl ComputeOperator for VMulu {
 }
 
 impl ComputeOperator for VMudl {
-    const NAME: &'static str = "VMUDL";
-
     fn apply(_flags: &mut Flags, acc: &mut u64, lhs: u16, rhs: u16) -> u16 {
         let result = ((lhs as u32).wrapping_mul(rhs as u32) >> 16) as i32 as i64;
         *acc = result as u64;
@@ -71,8 +63,6 @@ impl ComputeOperator for VMudl {
 }
 
 impl ComputeOperator for VMudm {
-    const NAME: &'static str = "VMUDM";
-
     fn apply(_flags: &mut Flags, acc: &mut u64, lhs: u16, rhs: u16) -> u16 {
         let result = (lhs as i16 as u32).wrapping_mul(rhs as u32) as i32 as i64;
         *acc = result as u64;
@@ -81,8 +71,6 @@ impl ComputeOperator for VMudm {
 }
 
 impl ComputeOperator for VMudn {
-    const NAME: &'static str = "VMUDN";
-
     fn apply(_flags: &mut Flags, acc: &mut u64, lhs: u16, rhs: u16) -> u16 {
         let result = (lhs as u32).wrapping_mul(rhs as i16 as u32) as i32 as i64;
         *acc = result as u64;
@@ -91,8 +79,6 @@ impl ComputeOperator for VMudn {
 }
 
 impl ComputeOperator for VMudh {
-    const NAME: &'static str = "VMUDH";
-
     fn apply(_flags: &mut Flags, acc: &mut u64, lhs: u16, rhs: u16) -> u16 {
         let result = ((lhs as i16 as i32).wrapping_mul(rhs as i16 as i32) as i64) << 16;
         *acc = result as u64;
@@ -101,8 +87,6 @@ impl ComputeOperator for VMudh {
 }
 
 impl ComputeOperator for VMacf {
-    const NAME: &'static str = "VMACF";
-
     fn apply(_flags: &mut Flags, acc: &mut u64, lhs: u16, rhs: u16) -> u16 {
         let result = (lhs as i16 as i64 * rhs as i16 as i64) << 1;
         *acc = (*acc as i64 + result) as u64 & 0xffff_ffff_ffff;
@@ -111,8 +95,6 @@ impl ComputeOperator for VMacf {
 }
 
 impl ComputeOperator for VMacu {
-    const NAME: &'static str = "VMACU";
-
     fn apply(_flags: &mut Flags, acc: &mut u64, lhs: u16, rhs: u16) -> u16 {
         let result = (lhs as i16 as i64 * rhs as i16 as i64) << 1;
         *acc = (*acc as i64 + result) as u64 & 0xffff_ffff_ffff;
@@ -130,8 +112,6 @@ impl ComputeOperator for VMacu {
 }
 
 impl ComputeOperator for VMadl {
-    const NAME: &'static str = "VMADL";
-
     fn apply(_flags: &mut Flags, acc: &mut u64, lhs: u16, rhs: u16) -> u16 {
         let result = ((lhs as u32).wrapping_mul(rhs as u32) >> 16) as i64;
         *acc = (*acc as i64 + result) as u64 & 0xffff_ffff_ffff;
@@ -140,8 +120,6 @@ impl ComputeOperator for VMadl {
 }
 
 impl ComputeOperator for VMadm {
-    const NAME: &'static str = "VMADM";
-
     fn apply(_flags: &mut Flags, acc: &mut u64, lhs: u16, rhs: u16) -> u16 {
         let result = (lhs as i16 as u32).wrapping_mul(rhs as u32) as i32 as i64;
         *acc = (*acc as i64 + result) as u64 & 0xffff_ffff_ffff;
@@ -150,8 +128,6 @@ impl ComputeOperator for VMadm {
 }
 
 impl ComputeOperator for VMadn {
-    const NAME: &'static str = "VMADN";
-
     fn apply(_flags: &mut Flags, acc: &mut u64, lhs: u16, rhs: u16) -> u16 {
         let result = lhs as u64 as i64 * rhs as i16 as i64;
         *acc = (*acc as i64 + result) as u64 & 0xffff_ffff_ffff;
@@ -160,8 +136,6 @@ impl ComputeOperator for VMadn {
 }
 
 impl ComputeOperator for VMadh {
-    const NAME: &'static str = "VMADH";
-
     fn apply(_flags: &mut Flags, acc: &mut u64, lhs: u16, rhs: u16) -> u16 {
         let result = ((lhs as i16 as i32).wrapping_mul(rhs as i16 as i32) as i64) << 16;
         *acc = (*acc as i64 + result) as u64 & 0xffff_ffff_ffff;
@@ -170,8 +144,6 @@ impl ComputeOperator for VMadh {
 }
 
 impl ComputeOperator for VAdd {
-    const NAME: &'static str = "VADD";
-
     fn apply(flags: &mut Flags, acc: &mut u64, lhs: u16, rhs: u16) -> u16 {
         let carry = flags.contains(Flags::CARRY);
         let result = lhs as i16 as i32 + rhs as i16 as i32 + carry as i32;
@@ -182,8 +154,6 @@ impl ComputeOperator for VAdd {
 }
 
 impl ComputeOperator for VAddc {
-    const NAME: &'static str = "VADDC";
-
     fn apply(flags: &mut Flags, acc: &mut u64, lhs: u16, rhs: u16) -> u16 {
         let result = lhs as u32 + rhs as u32;
         *acc = (*acc & !0xffff) | (result as u16 as u64);
@@ -194,8 +164,6 @@ impl ComputeOperator for VAddc {
 }
 
 impl ComputeOperator for VSub {
-    const NAME: &'static str = "VSUB";
-
     fn apply(flags: &mut Flags, acc: &mut u64, lhs: u16, rhs: u16) -> u16 {
         let carry = flags.contains(Flags::CARRY);
         let result = lhs as i16 as i32 - rhs as i16 as i32 - carry as i32;
@@ -206,8 +174,6 @@ impl ComputeOperator for VSub {
 }
 
 impl ComputeOperator for VSubc {
-    const NAME: &'static str = "VSUBC";
-
     fn apply(flags: &mut Flags, acc: &mut u64, lhs: u16, rhs: u16) -> u16 {
         let result = lhs as i32 - rhs as i32;
         *acc = (*acc & !0xffff) | (result as u16 as u64);
@@ -218,8 +184,6 @@ impl ComputeOperator for VSubc {
 }
 
 impl ComputeOperator for VAbs {
-    const NAME: &'static str = "VABS";
-
     fn apply(_flags: &mut Flags, acc: &mut u64, lhs: u16, rhs: u16) -> u16 {
         let (result, acc_result) = match (lhs as i16).cmp(&0) {
             Ordering::Less => {
@@ -239,8 +203,6 @@ impl ComputeOperator for VAbs {
 }
 
 impl ComputeOperator for VAnd {
-    const NAME: &'static str = "VAND";
-
     fn apply(_flags: &mut Flags, acc: &mut u64, lhs: u16, rhs: u16) -> u16 {
         let result = lhs & rhs;
         *acc = (*acc & !0xffff) | (result as u64);
@@ -249,8 +211,6 @@ impl ComputeOperator for VAnd {
 }
 
 impl ComputeOperator for VNand {
-    const NAME: &'static str = "VNAND";
-
     fn apply(_flags: &mut Flags, acc: &mut u64, lhs: u16, rhs: u16) -> u16 {
         let result = !(lhs & rhs);
         *acc = (*acc & !0xffff) | (result as u64);
@@ -259,8 +219,6 @@ impl ComputeOperator for VNand {
 }
 
 impl ComputeOperator for VOr {
-    const NAME: &'static str = "VOR";
-
     fn apply(_flags: &mut Flags, acc: &mut u64, lhs: u16, rhs: u16) -> u16 {
         let result = lhs | rhs;
         *acc = (*acc & !0xffff) | (result as u64);
@@ -269,8 +227,6 @@ impl ComputeOperator for VOr {
 }
 
 impl ComputeOperator for VNor {
-    const NAME: &'static str = "VNOR";
-
     fn apply(_flags: &mut Flags, acc: &mut u64, lhs: u16, rhs: u16) -> u16 {
         let result = !(lhs | rhs);
         *acc = (*acc & !0xffff) | (result as u64);
@@ -279,8 +235,6 @@ impl ComputeOperator for VNor {
 }
 
 impl ComputeOperator for VXor {
-    const NAME: &'static str = "VXOR";
-
     fn apply(_flags: &mut Flags, acc: &mut u64, lhs: u16, rhs: u16) -> u16 {
         let result = lhs ^ rhs;
         *acc = (*acc & !0xffff) | (result as u64);
@@ -289,8 +243,6 @@ impl ComputeOperator for VXor {
 }
 
 impl ComputeOperator for VNxor {
-    const NAME: &'static str = "VNXOR";
-
     fn apply(_flags: &mut Flags, acc: &mut u64, lhs: u16, rhs: u16) -> u16 {
         let result = !(lhs ^ rhs);
         *acc = (*acc & !0xffff) | (result as u64);
@@ -304,16 +256,6 @@ pub fn compute<Op: ComputeOperator>(core: &mut Core) {
     let vs = ((core.opcode[0] >> 11) & 31) as usize;
     let vd = ((core.opcode[0] >> 6) & 31) as usize;
 
-    trace!(
-        "{:08X}: {} V{:02}, V{:02}, V{:02}[E{}]",
-        core.pc[0],
-        Op::NAME,
-        vd,
-        vs,
-        vt,
-        el
-    );
-
     let rhs = core.cp2.reg(vt).broadcast_le(el);
     let lhs = core.cp2.reg(vs).to_le_array();
     let flags = &mut core.cp2.flags.as_le_array_mut();
@@ -330,8 +272,6 @@ pub fn vsar(core: &mut Core) {
     let el = ((core.opcode[0] >> 21) & 15) as usize;
     let vd = ((core.opcode[0] >> 6) & 31) as usize;
 
-    trace!("{:08X}: VSAR V{:02}, V{:02}[E{}]", core.pc[0], vd, vd, el);
-
     if (8..=10).contains(&el) {
         let shift = 32 - ((el - 8) << 4);
         let acc = core.cp2.acc.as_le_array();
@@ -2,7 +2,6 @@ use super::{Bus, Core, Cp2, Vector};
 use tracing::trace;
 
 pub trait LoadOperator {
-    const NAME: &'static str;
     const SHIFT: usize;
     fn apply(cp2: &mut Cp2, bus: &impl Bus, reg: usize, el: usize, addr: u32);
 }
@@ -18,7 +17,6 @@ pub struct Luv;
 pub struct Ltv;
 
 impl LoadOperator for Lbv {
-    const NAME: &'static str = "LBV";
     const SHIFT: usize = 0;
 
     fn apply(cp2: &mut Cp2, bus: &impl Bus, reg: usize, el: usize, addr: u32) {
@@ -31,7 +29,6 @@ impl LoadOperator for Lbv {
 }
 
 impl LoadOperator for Lsv {
-    const NAME: &'static str = "LSV";
     const SHIFT: usize = 1;
 
     fn apply(cp2: &mut Cp2, bus: &impl Bus, reg: usize, el: usize, addr: u32) {
@@ -44,7 +41,6 @@ impl LoadOperator for Lsv {
 }
 
 impl LoadOperator for Llv {
-    const NAME: &'static str = "LLV";
     const SHIFT: usize = 2;
 
     fn apply(cp2: &mut Cp2, bus: &impl Bus, reg: usize, el: usize, addr: u32) {
@@ -57,7 +53,6 @@ impl LoadOperator for Llv {
 }
 
 impl LoadOperator for Ldv {
-    const NAME: &'static str = "LDV";
     const SHIFT: usize = 3;
 
     fn apply(cp2: &mut Cp2, bus: &impl Bus, reg: usize, el: usize, addr: u32) {
@@ -70,7 +65,6 @@ impl LoadOperator for Ldv {
 }
 
 impl LoadOperator for Lqv {
-    const NAME: &'static str = "LQV";
     const SHIFT: usize = 4;
 
     fn apply(cp2: &mut Cp2, bus: &impl Bus, reg: usize, el: usize, addr: u32) {
@@ -98,7 +92,6 @@ impl LoadOperator for Lqv {
 }
 
 impl LoadOperator for Lrv {
-    const NAME: &'static str = "LRV";
     const SHIFT: usize = 4;
 
     fn apply(cp2: &mut Cp2, bus: &impl Bus, reg: usize, el: usize, end: u32) {
@@ -118,7 +111,6 @@ impl LoadOperator for Lrv {
 }
 
 impl LoadOperator for Lpv {
-    const NAME: &'static str = "LPV";
     const SHIFT: usize = 3;
 
     fn apply(cp2: &mut Cp2, bus: &impl Bus, reg: usize, el: usize, addr: u32) {
@@ -138,7 +130,6 @@ impl LoadOperator for Lpv {
 }
 
 impl LoadOperator for Luv {
-    const NAME: &'static str = "LUV";
     const SHIFT: usize = 3;
 
     fn apply(cp2: &mut Cp2, bus: &impl Bus, reg: usize, el: usize, addr: u32) {
@@ -158,7 +149,6 @@ impl LoadOperator for Luv {
 }
 
 impl LoadOperator for Ltv {
-    const NAME: &'static str = "LTV";
     const SHIFT: usize = 4;
 
     fn apply(cp2: &mut Cp2, bus: &impl Bus, reg: usize, el: usize, addr: u32) {
@@ -198,16 +188,6 @@ pub fn load<Op: LoadOperator>(core: &mut Core, bus: &impl Bus) {
     let offset =
         ((core.opcode[0] & 0x7f).wrapping_sub((core.opcode[0] & 0x40) << 1) as i32) << Op::SHIFT;
 
-    trace!(
-        "{:08X}: {} V{:02}[E{}], {}({})",
-        core.pc[0],
-        Op::NAME,
-        vt,
-        el,
-        offset,
-        Core::REG_NAMES[base],
-    );
-
     Op::apply(
         &mut core.cp2,
         bus,
@@ -222,14 +202,6 @@ pub fn mtc2(core: &mut Core) {
     let rd = ((core.opcode[0] >> 11) & 31) as usize;
     let el = ((core.opcode[0] >> 7) & 15) as usize;
 
-    trace!(
-        "{:08X}: MTC2 {}, V{:02}[E{}]",
-        core.pc[0],
-        Core::REG_NAMES[rt],
-        rd,
-        el
-    );
-
     let mut vector = core.cp2.reg(rd);
     vector.write(el, core.regs[rt] as u16);
     core.cp2.set_reg(rd, vector);
@@ -239,12 +211,5 @@ pub fn ctc2(core: &mut Core) {
     let rt = ((core.opcode[0] >> 16) & 31) as usize;
     let rd = ((core.opcode[0] >> 11) & 31) as usize;
 
-    trace!(
-        "{:08X}: CFC2 {}, {}",
-        core.pc[0],
-        Core::REG_NAMES[rt],
-        Cp2::CONTROL_REG_NAMES[rd]
-    );
-
     core.cp2.set_control_reg(rd, core.regs[rt]);
 }
@@ -3,7 +3,6 @@ use super::Flags;
 use std::marker::PhantomData;
 
 pub trait SelectOperator {
-    const NAME: &'static str;
     fn apply(flags: &mut Flags, lhs: u16, rhs: u16) -> bool;
 }
 
@@ -26,8 +25,6 @@ pub struct Ge;
 pub struct Lt;
 
 impl<Op: SelectOperator> ComputeOperator for Select<Op> {
-    const NAME: &'static str = Op::NAME;
-
     fn apply(flags: &mut Flags, acc: &mut u64, lhs: u16, rhs: u16) -> u16 {
         let condition = Op::apply(flags, lhs, rhs);
         let result = if condition { lhs } else { rhs };
@@ -39,24 +36,18 @@ impl<Op: SelectOperator> ComputeOperator for Select<Op> {
 }
 
 impl SelectOperator for Eq {
-    const NAME: &'static str = "VEQ";
-
     fn apply(flags: &mut Flags, lhs: u16, rhs: u16) -> bool {
         lhs == rhs && !flags.contains(Flags::NOT_EQUAL)
     }
 }
 
 impl SelectOperator for Ne {
-    const NAME: &'static str = "VNE";
-
     fn apply(flags: &mut Flags, lhs: u16, rhs: u16) -> bool {
         lhs != rhs || flags.contains(Flags::NOT_EQUAL)
     }
 }
 
 impl SelectOperator for Ge {
-    const NAME: &'static str = "VGE";
-
     fn apply(flags: &mut Flags, lhs: u16, rhs: u16) -> bool {
         (lhs as i16) > (rhs as i16)
             || (lhs == rhs && !flags.contains(Flags::CARRY | Flags::NOT_EQUAL))
@@ -64,8 +55,6 @@ impl SelectOperator for Ge {
 }
 
 impl SelectOperator for Lt {
-    const NAME: &'static str = "VLT";
-
     fn apply(flags: &mut Flags, lhs: u16, rhs: u16) -> bool {
         (lhs as i16) < (rhs as i16)
             || (lhs == rhs && flags.contains(Flags::CARRY | Flags::NOT_EQUAL))
@@ -73,8 +62,6 @@ impl SelectOperator for Lt {
 }
 
 impl ComputeOperator for VCl {
-    const NAME: &'static str = "VCL";
-
     fn apply(flags: &mut Flags, acc: &mut u64, lhs: u16, rhs: u16) -> u16 {
         let result = if flags.contains(Flags::CARRY) {
             let lt = if flags.contains(Flags::NOT_EQUAL) {
@@ -120,8 +107,6 @@ impl ComputeOperator for VCl {
 }
 
 impl ComputeOperator for VCh {
-    const NAME: &'static str = "VCH";
-
     fn apply(flags: &mut Flags, acc: &mut u64, lhs: u16, rhs: u16) -> u16 {
         let carry = (lhs as i16 ^ rhs as i16) < 0;
         flags.set(Flags::CARRY, carry);
@@ -162,8 +147,6 @@ impl ComputeOperator for VCh {
 }
 
 impl ComputeOperator for VCr {
-    const NAME: &'static str = "VCR";
-
     fn apply(flags: &mut Flags, acc: &mut u64, lhs: u16, rhs: u16) -> u16 {
         let result = if (lhs as i16 ^ rhs as i16) < 0 {
             flags.set(Flags::CLIP_COMPARE, (rhs as i16) < 0);
@@ -195,8 +178,6 @@ impl ComputeOperator for VCr {
 }
 
 impl ComputeOperator for VMrg {
-    const NAME: &'static str = "VMRG";
-
     fn apply(flags: &mut Flags, acc: &mut u64, lhs: u16, rhs: u16) -> u16 {
         let result = if flags.contains(Flags::COMPARE) {
             lhs
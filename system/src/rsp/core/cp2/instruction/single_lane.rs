@@ -1,8 +1,6 @@
 use super::{Core, Cp2};
-use tracing::trace;
 
 pub trait SingleLaneOperator {
-    const NAME: &'static str;
     const VT_EL_MAGIC: bool;
     fn apply(cp2: &mut Cp2, input: u16) -> u16;
 }
@@ -24,7 +22,6 @@ struct Rcp;
 struct Rsq;
 
 impl SingleLaneOperator for VMov {
-    const NAME: &'static str = "VMOV";
     const VT_EL_MAGIC: bool = true;
 
     fn apply(_cp2: &mut Cp2, input: u16) -> u16 {
@@ -41,7 +38,6 @@ impl ReciprocalOperator for Rcp {
 }
 
 impl SingleLaneOperator for VRcp {
-    const NAME: &'static str = "VRCP";
     const VT_EL_MAGIC: bool = false;
 
     fn apply(cp2: &mut Cp2, input: u16) -> u16 {
@@ -51,7 +47,6 @@ impl SingleLaneOperator for VRcp {
 }
 
 impl SingleLaneOperator for VRcpl {
-    const NAME: &'static str = "VRCPL";
     const VT_EL_MAGIC: bool = false;
 
     fn apply(cp2: &mut Cp2, input: u16) -> u16 {
@@ -66,7 +61,6 @@ impl SingleLaneOperator for VRcpl {
 }
 
 impl SingleLaneOperator for VRcph {
-    const NAME: &'static str = "VRCPH";
     const VT_EL_MAGIC: bool = false;
 
     fn apply(cp2: &mut Cp2, input: u16) -> u16 {
@@ -85,7 +79,6 @@ impl ReciprocalOperator for Rsq {
 }
 
 impl SingleLaneOperator for VRsq {
-    const NAME: &'static str = "VRSQ";
     const VT_EL_MAGIC: bool = false;
 
     fn apply(cp2: &mut Cp2, input: u16) -> u16 {
@@ -95,7 +88,6 @@ impl SingleLaneOperator for VRsq {
 }
 
 impl SingleLaneOperator for VRsql {
-    const NAME: &'static str = "VRSQL";
     const VT_EL_MAGIC: bool = false;
 
     fn apply(cp2: &mut Cp2, input: u16) -> u16 {
@@ -110,7 +102,6 @@ impl SingleLaneOperator for VRsql {
 }
 
 impl SingleLaneOperator for VRsqh {
-    const NAME: &'static str = "VRSQH";
     const VT_EL_MAGIC: bool = false;
 
     fn apply(cp2: &mut Cp2, input: u16) -> u16 {
@@ -126,16 +117,6 @@ pub fn single_lane<Op: SingleLaneOperator>(core: &mut Core) {
     let vd_el_raw = ((core.opcode[0] >> 11) & 15) as usize;
     let vd = ((core.opcode[0] >> 6) & 31) as usize;
 
-    trace!(
-        "{:08X} {} V{:02}[E({}], V{:02}[E({}]",
-        core.pc[0],
-        Op::NAME,
-        vd,
-        vd_el_raw,
-        vt,
-        vt_el_raw,
-    );
-
     let vt_el = if Op::VT_EL_MAGIC {
         match vt_el_raw {
             0..=1 => vd_el_raw & 0b111,
@@ -161,13 +142,9 @@ pub fn single_lane<Op: SingleLaneOperator>(core: &mut Core) {
     core.cp2.set_reg(vd, dst);
 }
 
-pub fn vnop(core: &mut Core) {
-    trace!("{:08x}: VNOP", core.pc[0]);
-}
+pub fn vnop(_core: &mut Core) {}
 
-pub fn vnull(core: &mut Core) {
-    trace!("{:08x}: VNULL", core.pc[0]);
-}
+pub fn vnull(_core: &mut Core) {}
 
 fn calc_reciprocal<Op: ReciprocalOperator>(cp2: &mut Cp2, input: i32) -> u16 {
     let mask = input >> 31;
@@ -2,7 +2,6 @@ use super::{Bus, Core, Cp2};
 use tracing::trace;
 
 pub trait StoreOperator {
-    const NAME: &'static str;
     const SHIFT: usize;
     fn apply(cp2: &Cp2, bus: &mut impl Bus, reg: usize, el: usize, addr: u32);
 }
@@ -18,7 +17,6 @@ pub struct Suv;
 pub struct Stv;
 
 impl StoreOperator for Sbv {
-    const NAME: &'static str = "SBV";
     const SHIFT: usize = 0;
 
     fn apply(cp2: &Cp2, bus: &mut impl Bus, reg: usize, el: usize, addr: u32) {
@@ -29,7 +27,6 @@ impl StoreOperator for Sbv {
 }
 
 impl StoreOperator for Ssv {
-    const NAME: &'static str = "SSV";
     const SHIFT: usize = 1;
 
     fn apply(cp2: &Cp2, bus: &mut impl Bus, reg: usize, el: usize, addr: u32) {
@@ -40,7 +37,6 @@ impl StoreOperator for Ssv {
 }
 
 impl StoreOperator for Slv {
-    const NAME: &'static str = "SLV";
     const SHIFT: usize = 2;
 
     fn apply(cp2: &Cp2, bus: &mut impl Bus, reg: usize, el: usize, addr: u32) {
@@ -51,7 +47,6 @@ impl StoreOperator for Slv {
 }
 
 impl StoreOperator for Sdv {
-    const NAME: &'static str = "SDV";
     const SHIFT: usize = 3;
 
     fn apply(cp2: &Cp2, bus: &mut impl Bus, reg: usize, el: usize, addr: u32) {
@@ -62,7 +57,6 @@ impl StoreOperator for Sdv {
 }
 
 impl StoreOperator for Sqv {
-    const NAME: &'static str = "SQV";
     const SHIFT: usize = 4;
 
     fn apply(cp2: &Cp2, bus: &mut impl Bus, reg: usize, el: usize, addr: u32) {
@@ -88,7 +82,6 @@ impl StoreOperator for Sqv {
 }
 
 impl StoreOperator for Srv {
-    const NAME: &'static str = "SRV";
     const SHIFT: usize = 4;
 
     fn apply(cp2: &Cp2, bus: &mut impl Bus, reg: usize, el: usize, end: u32) {
@@ -106,7 +99,6 @@ impl StoreOperator for Srv {
 }
 
 impl StoreOperator for Spv {
-    const NAME: &'static str = "SPV";
     const SHIFT: usize = 3;
 
     fn apply(cp2: &Cp2, bus: &mut impl Bus, reg: usize, el: usize, addr: u32) {
@@ -129,7 +121,6 @@ impl StoreOperator for Spv {
 }
 
 impl StoreOperator for Suv {
-    const NAME: &'static str = "SUV";
     const SHIFT: usize = 3;
 
     fn apply(cp2: &Cp2, bus: &mut impl Bus, reg: usize, el: usize, addr: u32) {
@@ -152,7 +143,6 @@ impl StoreOperator for Suv {
 }
 
 impl StoreOperator for Stv {
-    const NAME: &'static str = "STV";
     const SHIFT: usize = 4;
 
     fn apply(cp2: &Cp2, bus: &mut impl Bus, reg: usize, el: usize, addr: u32) {
@@ -186,16 +176,6 @@ pub fn store<Op: StoreOperator>(core: &Core, bus: &mut impl Bus) {
     let offset =
         ((core.opcode[0] & 0x7f).wrapping_sub((core.opcode[0] & 0x40) << 1) as i32) << Op::SHIFT;
 
-    trace!(
-        "{:08X}: {} V{:02}[E{}], {}({})",
-        core.pc[0],
-        Op::NAME,
-        vt,
-        el,
-        offset,
-        Core::REG_NAMES[base],
-    );
-
     Op::apply(
         &core.cp2,
         bus,
@@ -210,14 +190,6 @@ pub fn mfc2(core: &mut Core) {
     let rd = ((core.opcode[0] >> 11) & 31) as usize;
     let el = ((core.opcode[0] >> 7) & 15) as usize;
 
-    trace!(
-        "{:08X}: MFC2 {}, V{:02}[E{}]",
-        core.pc[0],
-        Core::REG_NAMES[rt],
-        rd,
-        el
-    );
-
     core.set_reg(rt, core.cp2.reg(rd).read::<u16>(el) as i16 as i32);
 }
 
@@ -225,12 +197,5 @@ pub fn cfc2(core: &mut Core) {
     let rt = ((core.opcode[0] >> 16) & 31) as usize;
     let rd = ((core.opcode[0] >> 11) & 31) as usize;
 
-    trace!(
-        "{:08X}: CFC2 {}, {}",
-        core.pc[0],
-        Core::REG_NAMES[rt],
-        Cp2::CONTROL_REG_NAMES[rd]
-    );
-
     core.set_reg(rt, core.cp2.control_reg(rd));
 }
@@ -59,6 +59,10 @@ impl Display for FlagVector {
 }
 
 impl FlagVector {
+    pub fn as_le_array(&self) -> &[Flags; 8] {
+        &self.0
+    }
+
     pub fn as_le_array_mut(&mut self) -> &mut [Flags; 8] {
         &mut self.0
     }
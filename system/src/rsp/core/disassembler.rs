@@ -0,0 +1,364 @@
+use super::cp0;
+use super::{Core, Cp2};
+use std::fmt::Write;
+
+const COMPUTE_NAMES: [Option<&str>; 64] = {
+    let mut names = [None; 64];
+    names[0x00] = Some("VMULF");
+    names[0x01] = Some("VMULU");
+    names[0x02] = Some("VRNDP");
+    names[0x03] = Some("VMULQ");
+    names[0x04] = Some("VMUDL");
+    names[0x05] = Some("VMUDM");
+    names[0x06] = Some("VMUDN");
+    names[0x07] = Some("VMUDH");
+    names[0x08] = Some("VMACF");
+    names[0x09] = Some("VMACU");
+    names[0x0a] = Some("VRNDN");
+    names[0x0b] = Some("VMACQ");
+    names[0x0c] = Some("VMADL");
+    names[0x0d] = Some("VMADM");
+    names[0x0e] = Some("VMADN");
+    names[0x0f] = Some("VMADH");
+    names[0x10] = Some("VADD");
+    names[0x11] = Some("VSUB");
+    names[0x13] = Some("VABS");
+    names[0x14] = Some("VADDC");
+    names[0x15] = Some("VSUBC");
+    names[0x20] = Some("VLT");
+    names[0x21] = Some("VEQ");
+    names[0x22] = Some("VNE");
+    names[0x23] = Some("VGE");
+    names[0x24] = Some("VCL");
+    names[0x25] = Some("VCH");
+    names[0x26] = Some("VCR");
+    names[0x27] = Some("VMRG");
+    names[0x28] = Some("VAND");
+    names[0x29] = Some("VNAND");
+    names[0x2a] = Some("VOR");
+    names[0x2b] = Some("VNOR");
+    names[0x2c] = Some("VXOR");
+    names[0x2d] = Some("VNXOR");
+    names
+};
+
+const SINGLE_LANE_NAMES: [&str; 7] = ["VRCP", "VRCPL", "VRCPH", "VMOV", "VRSQ", "VRSQL", "VRSQH"];
+
+// Name and offset shift for each LWC2/SWC2 function
+const LOAD_NAMES: [Option<(&str, u32)>; 12] = [
+    Some(("LBV", 0)),
+    Some(("LSV", 1)),
+    Some(("LLV", 2)),
+    Some(("LDV", 3)),
+    Some(("LQV", 4)),
+    Some(("LRV", 4)),
+    Some(("LPV", 3)),
+    Some(("LUV", 3)),
+    Some(("LHV", 4)),
+    Some(("LFV", 4)),
+    None,
+    Some(("LTV", 4)),
+];
+
+const STORE_NAMES: [Option<(&str, u32)>; 12] = [
+    Some(("SBV", 0)),
+    Some(("SSV", 1)),
+    Some(("SLV", 2)),
+    Some(("SDV", 3)),
+    Some(("SQV", 4)),
+    Some(("SRV", 4)),
+    Some(("SPV", 3)),
+    Some(("SUV", 3)),
+    Some(("SHV", 4)),
+    Some(("SFV", 4)),
+    Some(("SWV", 4)),
+    Some(("STV", 4)),
+];
+
+// Decodes a single RSP instruction. 'pc' is the IMEM offset of the
+// instruction, and is used to resolve branch and jump targets.
+pub fn disassemble(pc: u32, word: u32) -> String {
+    let ins = Instruction { pc, word };
+
+    match word >> 26 {
+        0o00 => special(ins),
+        0o01 => regimm(ins),
+        0o02 => ins.jump("J"),
+        0o03 => ins.jump("JAL"),
+        0o04 | 0o24 => ins.branch_rs_rt("BEQ"),
+        0o05 | 0o25 => ins.branch_rs_rt("BNE"),
+        0o06 | 0o26 => ins.branch_rs("BLEZ"),
+        0o07 | 0o27 => ins.branch_rs("BGTZ"),
+        0o10 => ins.i_type("ADDI"),
+        0o11 => ins.i_type("ADDIU"),
+        0o12 => ins.i_type("SLTI"),
+        0o13 => ins.i_type("SLTIU"),
+        0o14 => ins.i_type_hex("ANDI"),
+        0o15 => ins.i_type_hex("ORI"),
+        0o16 => ins.i_type_hex("XORI"),
+        0o17 => format!("LUI {}, 0x{:04X}", ins.rt(), ins.word as u16),
+        0o20 => cop0(ins),
+        0o22 => cop2(ins),
+        0o40 => ins.load_store("LB"),
+        0o41 => ins.load_store("LH"),
+        0o43 => ins.load_store("LW"),
+        0o44 => ins.load_store("LBU"),
+        0o45 => ins.load_store("LHU"),
+        0o47 => ins.load_store("LWU"),
+        0o50 => ins.load_store("SB"),
+        0o51 => ins.load_store("SH"),
+        0o53 => ins.load_store("SW"),
+        0o62 => ins.vector_load_store(&LOAD_NAMES),
+        0o72 => ins.vector_load_store(&STORE_NAMES),
+        _ => ins.unknown(),
+    }
+}
+
+// Produces a listing of an IMEM image, one instruction per line
+pub fn disassemble_imem(imem: &[u8]) -> String {
+    let mut output = String::new();
+
+    for (index, chunk) in imem.chunks_exact(4).enumerate() {
+        let pc = (index as u32 * 4) & 0x0ffc;
+        let word = u32::from_be_bytes(chunk.try_into().unwrap());
+        writeln!(
+            output,
+            "{:03X}: {:08X}  {}",
+            pc,
+            word,
+            disassemble(pc, word)
+        )
+        .unwrap();
+    }
+
+    output
+}
+
+fn special(ins: Instruction) -> String {
+    match ins.word & 63 {
+        0o00 if ins.word == 0 => "NOP".into(),
+        0o00 => ins.shift_fixed("SLL"),
+        0o02 => ins.shift_fixed("SRL"),
+        0o03 => ins.shift_fixed("SRA"),
+        0o04 => ins.shift_variable("SLLV"),
+        0o06 => ins.shift_variable("SRLV"),
+        0o07 => ins.shift_variable("SRAV"),
+        0o10 => format!("JR {}", ins.rs()),
+        0o11 => format!("JALR {}, {}", ins.rd(), ins.rs()),
+        0o15 => "BREAK".into(),
+        0o40 => ins.r_type("ADD"),
+        0o41 => ins.r_type("ADDU"),
+        0o42 => ins.r_type("SUB"),
+        0o43 => ins.r_type("SUBU"),
+        0o44 => ins.r_type("AND"),
+        0o45 => ins.r_type("OR"),
+        0o46 => ins.r_type("XOR"),
+        0o47 => ins.r_type("NOR"),
+        0o52 => ins.r_type("SLT"),
+        0o53 => ins.r_type("SLTU"),
+        _ => ins.unknown(),
+    }
+}
+
+fn regimm(ins: Instruction) -> String {
+    match (ins.word >> 16) & 31 {
+        0o00 | 0o02 => ins.branch_rs("BLTZ"),
+        0o01 | 0o03 => ins.branch_rs("BGEZ"),
+        0o20 | 0o22 => ins.branch_rs("BLTZAL"),
+        0o21 | 0o23 => ins.branch_rs("BGEZAL"),
+        _ => ins.unknown(),
+    }
+}
+
+fn cop0(ins: Instruction) -> String {
+    let reg = cp0::REG_NAMES[ins.rd_index() & 15];
+
+    match ins.rs_index() {
+        0o00 => format!("MFC0 {}, {}", ins.rt(), reg),
+        0o04 => format!("MTC0 {}, {}", ins.rt(), reg),
+        _ => ins.unknown(),
+    }
+}
+
+fn cop2(ins: Instruction) -> String {
+    let vt = ins.rt_index();
+    let vs = ins.rd_index();
+    let vd = (ins.word >> 6) & 31;
+    let el = (ins.word >> 21) & 15;
+
+    match ins.rs_index() {
+        0o00 => format!("MFC2 {}, V{:02}[E{}]", ins.rt(), vs, (ins.word >> 7) & 15),
+        0o02 => format!("CFC2 {}, {}", ins.rt(), Cp2::CONTROL_REG_NAMES[vs]),
+        0o04 => format!("MTC2 {}, V{:02}[E{}]", ins.rt(), vs, (ins.word >> 7) & 15),
+        0o06 => format!("CTC2 {}, {}", ins.rt(), Cp2::CONTROL_REG_NAMES[vs]),
+        0o20..=0o37 => match ins.word & 63 {
+            0x1d => match el {
+                8 => format!("VSAR V{:02}, ACC_HI", vd),
+                9 => format!("VSAR V{:02}, ACC_MD", vd),
+                10 => format!("VSAR V{:02}, ACC_LO", vd),
+                _ => format!("VSAR V{:02}, E{}", vd, el),
+            },
+            func @ 0x30..=0x36 => format!(
+                "{} V{:02}[E{}], V{:02}{}",
+                SINGLE_LANE_NAMES[func as usize - 0x30],
+                vd,
+                vs & 7,
+                vt,
+                element(el)
+            ),
+            0x37 => "VNOP".into(),
+            0x3f => "VNULL".into(),
+            func => match COMPUTE_NAMES[func as usize] {
+                Some(name) => format!("{} V{:02}, V{:02}, V{:02}{}", name, vd, vs, vt, element(el)),
+                None => ins.unknown(),
+            },
+        },
+        _ => ins.unknown(),
+    }
+}
+
+// Element selector of a computational instruction. 0 and 1 select the whole
+// vector.
+fn element(el: u32) -> String {
+    match el {
+        0..=1 => String::new(),
+        2..=3 => format!("[{}Q]", el - 2),
+        4..=7 => format!("[{}H]", el - 4),
+        _ => format!("[{}]", el - 8),
+    }
+}
+
+#[derive(Copy, Clone)]
+struct Instruction {
+    pc: u32,
+    word: u32,
+}
+
+impl Instruction {
+    fn rs_index(self) -> usize {
+        ((self.word >> 21) & 31) as usize
+    }
+
+    fn rt_index(self) -> usize {
+        ((self.word >> 16) & 31) as usize
+    }
+
+    fn rd_index(self) -> usize {
+        ((self.word >> 11) & 31) as usize
+    }
+
+    fn rs(self) -> &'static str {
+        Core::REG_NAMES[self.rs_index()]
+    }
+
+    fn rt(self) -> &'static str {
+        Core::REG_NAMES[self.rt_index()]
+    }
+
+    fn rd(self) -> &'static str {
+        Core::REG_NAMES[self.rd_index()]
+    }
+
+    fn imm(self) -> i16 {
+        self.word as i16
+    }
+
+    fn branch_target(self) -> u32 {
+        self.pc
+            .wrapping_add(4)
+            .wrapping_add(((self.imm() as i32) << 2) as u32)
+            & 0x0ffc
+    }
+
+    fn r_type(self, name: &str) -> String {
+        format!("{} {}, {}, {}", name, self.rd(), self.rs(), self.rt())
+    }
+
+    fn i_type(self, name: &str) -> String {
+        format!("{} {}, {}, {}", name, self.rt(), self.rs(), self.imm())
+    }
+
+    fn i_type_hex(self, name: &str) -> String {
+        format!(
+            "{} {}, {}, 0x{:04X}",
+            name,
+            self.rt(),
+            self.rs(),
+            self.word as u16
+        )
+    }
+
+    fn shift_fixed(self, name: &str) -> String {
+        format!(
+            "{} {}, {}, {}",
+            name,
+            self.rd(),
+            self.rt(),
+            (self.word >> 6) & 31
+        )
+    }
+
+    fn shift_variable(self, name: &str) -> String {
+        format!("{} {}, {}, {}", name, self.rd(), self.rt(), self.rs())
+    }
+
+    fn load_store(self, name: &str) -> String {
+        format!("{} {}, {}({})", name, self.rt(), self.imm(), self.rs())
+    }
+
+    fn vector_load_store(self, names: &[Option<(&str, u32)>]) -> String {
+        let Some(&Some((name, shift))) = names.get(self.rd_index()) else {
+            return self.unknown();
+        };
+
+        // Offsets are a signed 7-bit value scaled by the access size
+        let offset = (((self.word & 0x7f) as i32) << 25 >> 25) << shift;
+
+        format!(
+            "{} V{:02}[E{}], {}({})",
+            name,
+            self.rt_index(),
+            (self.word >> 7) & 15,
+            offset,
+            self.rs()
+        )
+    }
+
+    fn jump(self, name: &str) -> String {
+        format!("{} 0x{:03X}", name, (self.word << 2) & 0x0ffc)
+    }
+
+    fn branch_rs(self, name: &str) -> String {
+        format!("{} {}, 0x{:03X}", name, self.rs(), self.branch_target())
+    }
+
+    fn branch_rs_rt(self, name: &str) -> String {
+        format!(
+            "{} {}, {}, 0x{:03X}",
+            name,
+            self.rs(),
+            self.rt(),
+            self.branch_target()
+        )
+    }
+
+    fn unknown(self) -> String {
+        format!(".word 0x{:08X}", self.word)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_vector_instructions() {
+        assert_eq!(disassemble(0x000, 0x4a01_0850), "VADD V01, V01, V01");
+        assert_eq!(disassemble(0x000, 0x4b02_0847), "VMUDH V01, V01, V02[0]");
+        assert_eq!(disassemble(0x000, 0xc801_2001), "LQV V01[E0], 16(ZERO)");
+        assert_eq!(disassemble(0x000, 0xe9e2_207f), "SQV V02[E0], -16(T7)");
+        assert_eq!(disassemble(0x000, 0x4081_2000), "MTC0 AT, SP_STATUS");
+        assert_eq!(disassemble(0x010, 0x1420_fffe), "BNE AT, ZERO, 0x00C");
+        assert_eq!(disassemble(0x000, 0x4a00_002c), "VXOR V00, V00, V00");
+    }
+}
@@ -1,8 +1,6 @@
 use super::Core;
-use tracing::trace;
 
 pub fn break_(core: &mut Core) {
-    trace!("{:08X}: BREAK", core.pc[0]);
     core.broke = true;
     core.opcode[1] = 0;
     core.delay[1] = false;
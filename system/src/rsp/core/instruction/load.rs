@@ -2,7 +2,6 @@ use super::{Bus, Core};
 use tracing::trace;
 
 pub trait LoadOperator {
-    const NAME: &'static str;
     fn apply(bus: &mut impl Bus, addr: u32) -> i32;
 }
 
@@ -14,8 +13,6 @@ pub struct Lw;
 pub struct Lwu;
 
 impl LoadOperator for Lb {
-    const NAME: &'static str = "LB";
-
     fn apply(bus: &mut impl Bus, addr: u32) -> i32 {
         let value = bus.read_data::<u8>(addr);
         trace!("  [{:08X} => {:02X}]", addr, value);
@@ -24,8 +21,6 @@ impl LoadOperator for Lb {
 }
 
 impl LoadOperator for Lbu {
-    const NAME: &'static str = "LBU";
-
     fn apply(bus: &mut impl Bus, addr: u32) -> i32 {
         let value = bus.read_data::<u8>(addr);
         trace!("  [{:08X} => {:02X}]", addr, value);
@@ -34,8 +29,6 @@ impl LoadOperator for Lbu {
 }
 
 impl LoadOperator for Lh {
-    const NAME: &'static str = "LH";
-
     fn apply(bus: &mut impl Bus, addr: u32) -> i32 {
         let value = bus.read_data::<u16>(addr);
         trace!("  [{:08X} => {:04X}]", addr, value);
@@ -44,8 +37,6 @@ impl LoadOperator for Lh {
 }
 
 impl LoadOperator for Lhu {
-    const NAME: &'static str = "LHU";
-
     fn apply(bus: &mut impl Bus, addr: u32) -> i32 {
         let value = bus.read_data::<u16>(addr);
         trace!("  [{:08X} => {:04X}]", addr, value);
@@ -54,8 +45,6 @@ impl LoadOperator for Lhu {
 }
 
 impl LoadOperator for Lw {
-    const NAME: &'static str = "LW";
-
     fn apply(bus: &mut impl Bus, addr: u32) -> i32 {
         let value = bus.read_data::<u32>(addr);
         trace!("  [{:08X} => {:08X}]", addr, value);
@@ -64,8 +53,6 @@ impl LoadOperator for Lw {
 }
 
 impl LoadOperator for Lwu {
-    const NAME: &'static str = "LWU";
-
     fn apply(bus: &mut impl Bus, addr: u32) -> i32 {
         let value = bus.read_data::<u32>(addr);
         trace!("  [{:08X} => {:08X}]", addr, value);
@@ -77,13 +64,6 @@ pub fn lui(core: &mut Core) {
     let rt = ((core.opcode[0] >> 16) & 31) as usize;
     let imm = (core.opcode[0] & 0xffff) as i16;
 
-    trace!(
-        "{:08X}: LUI {}, 0x{:04X}",
-        core.pc[0],
-        Core::REG_NAMES[rt],
-        imm
-    );
-
     core.set_reg(rt, (imm as i32) << 16);
 }
 
@@ -92,15 +72,6 @@ pub fn load<Op: LoadOperator>(core: &mut Core, bus: &mut impl Bus) {
     let rt = ((core.opcode[0] >> 16) & 31) as usize;
     let offset = (core.opcode[0] & 0xffff) as i16 as i32;
 
-    trace!(
-        "{:08X}: {} {}, {}({})",
-        core.pc[0],
-        Op::NAME,
-        Core::REG_NAMES[rt],
-        offset,
-        Core::REG_NAMES[base],
-    );
-
     let address = core.regs[base].wrapping_add(offset) as u32;
 
     core.set_reg(rt, Op::apply(bus, address));
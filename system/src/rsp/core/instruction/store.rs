@@ -2,7 +2,6 @@ use super::{Bus, Core};
 use tracing::trace;
 
 pub trait StoreOperator {
-    const NAME: &'static str;
     fn apply(bus: &mut impl Bus, value: i32, addr: u32);
 }
 
@@ -11,8 +10,6 @@ pub struct Sh;
 pub struct Sw;
 
 impl StoreOperator for Sb {
-    const NAME: &'static str = "SB";
-
     fn apply(bus: &mut impl Bus, value: i32, addr: u32) {
         let value = value as u8;
         trace!("  [{:08X} <= {:02X}]", addr, value);
@@ -21,8 +18,6 @@ impl StoreOperator for Sb {
 }
 
 impl StoreOperator for Sh {
-    const NAME: &'static str = "SH";
-
     fn apply(bus: &mut impl Bus, value: i32, addr: u32) {
         let value = value as u16;
         trace!("  [{:08X} <= {:04X}]", addr, value);
@@ -31,8 +26,6 @@ impl StoreOperator for Sh {
 }
 
 impl StoreOperator for Sw {
-    const NAME: &'static str = "SW";
-
     fn apply(bus: &mut impl Bus, value: i32, addr: u32) {
         let value = value as u32;
         trace!("  [{:08X} <= {:08X}]", addr, value);
@@ -45,15 +38,6 @@ pub fn store<Op: StoreOperator>(core: &Core, bus: &mut impl Bus) {
     let rt = ((core.opcode[0] >> 16) & 31) as usize;
     let offset = (core.opcode[0] & 0xffff) as i16 as i32;
 
-    trace!(
-        "{:08X}: {} {}, {}({})",
-        core.pc[0],
-        Op::NAME,
-        Core::REG_NAMES[rt],
-        offset,
-        Core::REG_NAMES[base],
-    );
-
     let address = core.regs[base].wrapping_add(offset) as u32;
     let value = core.regs[rt];
 
@@ -0,0 +1,141 @@
+use crate::audio::AudioReceiver;
+use crate::{Device, Processor};
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Target {
+    Cpu,
+    Rsp,
+}
+
+impl Target {
+    // Address of the next instruction the processor will execute
+    pub fn pc(self, device: &Device) -> u32 {
+        match self {
+            Self::Cpu => device.cpu.pc(),
+            Self::Rsp => device.bus.rsp.core().current_pc(),
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum RunResult {
+    Running,
+    Stopped,
+    Breakpoint(Target),
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+enum State {
+    Stopped,
+    // Breakpoints only trigger on arriving at a new PC, so that a breakpoint
+    // at the current PC can be stepped over
+    Continuing { cpu_pc: u32, rsp_pc: u32 },
+    Stepping { target: Target, pc: u32, count: u64 },
+}
+
+// Shared by the debugger front ends. The device only runs while the debugger
+// allows it to, one frame at a time, so the host application stays responsive.
+pub struct RunControl {
+    state: State,
+}
+
+impl RunControl {
+    // The device starts off stopped
+    pub fn new() -> Self {
+        Self {
+            state: State::Stopped,
+        }
+    }
+
+    pub fn is_stopped(&self) -> bool {
+        self.state == State::Stopped
+    }
+
+    pub fn stop(&mut self) {
+        self.state = State::Stopped;
+    }
+
+    pub fn resume(&mut self, device: &Device) {
+        self.state = State::Continuing {
+            cpu_pc: Target::Cpu.pc(device),
+            rsp_pc: Target::Rsp.pc(device),
+        };
+    }
+
+    // Runs until the given processor has executed 'count' more instructions
+    pub fn step(&mut self, device: &Device, target: Target, count: u64) {
+        self.state = State::Stepping {
+            target,
+            pc: target.pc(device),
+            count,
+        };
+    }
+
+    // Runs the device for up to one frame if it is not stopped, returning
+    // whether (and why) it stopped during the frame
+    pub fn run_frame(
+        &mut self,
+        device: &mut Device,
+        receiver: &mut impl AudioReceiver,
+        is_breakpoint: impl Fn(Processor) -> bool,
+    ) -> RunResult {
+        let mut hit = None;
+
+        let stopped = match self.state {
+            State::Stopped => return RunResult::Running,
+            State::Continuing {
+                mut cpu_pc,
+                mut rsp_pc,
+            } => {
+                let stopped = device.run_frame_until(receiver, |processor| {
+                    let (target, pc, last_pc) = match processor {
+                        Processor::Cpu(cpu) => (Target::Cpu, cpu.pc(), &mut cpu_pc),
+                        Processor::Rsp(rsp) => (Target::Rsp, rsp.core().current_pc(), &mut rsp_pc),
+                    };
+
+                    let stop = pc != *last_pc && is_breakpoint(processor);
+                    *last_pc = pc;
+
+                    if stop {
+                        hit = Some(target);
+                    }
+
+                    stop
+                });
+
+                self.state = State::Continuing { cpu_pc, rsp_pc };
+                stopped
+            }
+            State::Stepping {
+                target,
+                mut pc,
+                mut count,
+            } => {
+                let stopped = device.run_frame_until(receiver, |processor| {
+                    let current_pc = match (target, processor) {
+                        (Target::Cpu, Processor::Cpu(cpu)) => cpu.pc(),
+                        (Target::Rsp, Processor::Rsp(rsp)) => rsp.core().current_pc(),
+                        _ => return false,
+                    };
+
+                    if current_pc != pc {
+                        pc = current_pc;
+                        count -= 1;
+                    }
+
+                    count == 0
+                });
+
+                self.state = State::Stepping { target, pc, count };
+                stopped
+            }
+        };
+
+        if !stopped {
+            return RunResult::Running;
+        }
+
+        self.state = State::Stopped;
+        hit.map_or(RunResult::Stopped, RunResult::Breakpoint)
+    }
+}
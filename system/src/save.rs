@@ -0,0 +1,111 @@
+use crate::snapshot::{Reader, Writer};
+use std::error::Error;
+use std::ffi::OsString;
+use std::fs;
+use std::io::{self, ErrorKind};
+use std::path::{Path, PathBuf};
+use tracing::{error, info, warn};
+
+// Frames without a write before changes are flushed to disk, so that a game
+// writing a save a piece at a time results in a single file write
+const FLUSH_DELAY: u32 = 30;
+
+// Save data backed by a file. Without a path, the data only lasts as long
+// as the emulator is running.
+pub struct SaveFile {
+    data: Vec<u8>,
+    path: Option<PathBuf>,
+    // Frames remaining until unflushed changes are written
+    flush_delay: Option<u32>,
+}
+
+impl SaveFile {
+    pub fn open(path: Option<PathBuf>, len: usize, fill: u8) -> io::Result<Self> {
+        let mut data = match &path {
+            Some(path) => match fs::read(path) {
+                Ok(data) => {
+                    info!("Loaded save data from {}", path.display());
+                    data
+                }
+                Err(err) if err.kind() == ErrorKind::NotFound => Vec::new(),
+                Err(err) => return Err(err),
+            },
+            None => Vec::new(),
+        };
+
+        if !data.is_empty() && data.len() != len {
+            warn!(
+                "Save file is {} bytes, but expected {} bytes",
+                data.len(),
+                len
+            );
+        }
+
+        data.resize(len, fill);
+
+        Ok(Self {
+            data,
+            path,
+            flush_delay: None,
+        })
+    }
+
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    // Marks the data as changed, so any access through this will eventually
+    // be flushed
+    pub fn data_mut(&mut self) -> &mut [u8] {
+        self.flush_delay = Some(FLUSH_DELAY);
+        &mut self.data
+    }
+
+    pub fn save_state(&self, writer: &mut Writer) {
+        writer.bytes(&self.data);
+    }
+
+    // Loading a save state also rewrites the save file, so that the game's
+    // saves match the state it was left in
+    pub fn load_state(&mut self, reader: &mut Reader) -> Result<(), Box<dyn Error>> {
+        reader.bytes_into(self.data_mut())
+    }
+
+    // Called once per frame
+    pub fn tick(&mut self) {
+        match self.flush_delay {
+            Some(0) => self.flush(),
+            Some(ref mut delay) => *delay -= 1,
+            None => (),
+        }
+    }
+
+    pub fn flush(&mut self) {
+        if self.flush_delay.take().is_none() {
+            return;
+        }
+
+        let Some(path) = &self.path else {
+            return;
+        };
+
+        if let Err(err) = fs::write(path, &self.data) {
+            error!("Failed to write save data to {}: {}", path.display(), err);
+        }
+    }
+}
+
+impl Drop for SaveFile {
+    fn drop(&mut self) {
+        self.flush();
+    }
+}
+
+// Returns the path of a save file, given the base path for the ROM's saves
+pub fn save_path(base: Option<&Path>, suffix: &str) -> Option<PathBuf> {
+    base.map(|base| {
+        let mut path = OsString::from(base);
+        path.push(suffix);
+        path.into()
+    })
+}
@@ -1,17 +1,25 @@
-pub use joybus::JoypadState;
+pub use joybus::{Accessory, JoypadState};
 
 use crate::header::{CicType, SaveType};
 use crate::interrupt::{RcpIntType, RcpInterrupt};
 use crate::memory::{Size, WriteMask};
+use crate::movie::Movie;
 use crate::rdram::Rdram;
+use crate::snapshot::{Reader, Writer};
 use joybus::Joybus;
 use pif::Pif;
 use regs::Regs;
+use std::error::Error;
+use std::io;
+use std::path::Path;
 use tracing::debug;
 
+mod controller_pak;
+mod eeprom;
 mod joybus;
 mod pif;
 mod regs;
+mod rtc;
 
 struct Dma {
     pif_addr: u32,
@@ -32,7 +40,10 @@ impl SerialInterface {
         pif_data: Option<Vec<u8>>,
         cic_type: CicType,
         save_type: SaveType,
-    ) -> Self {
+        accessories: [Accessory; 4],
+        rtc_start: Option<i64>,
+        save_path: Option<&Path>,
+    ) -> io::Result<Self> {
         let mut pif = Pif::new(pif_data);
 
         let cic_seed: Option<u32> = match cic_type {
@@ -48,13 +59,66 @@ impl SerialInterface {
             pif.write(0x07e4, seed);
         }
 
-        Self {
+        Ok(Self {
             regs: Regs::default(),
-            joybus: Joybus::new(save_type),
+            joybus: Joybus::new(save_type, accessories, rtc_start, save_path)?,
             pif,
             dma: None,
             rcp_int,
+        })
+    }
+
+    pub fn save_state(&self, writer: &mut Writer) {
+        writer.tag(b"SI  ");
+        writer.reg(self.regs.dram_addr);
+        writer.reg(self.regs.status);
+        writer.bool(self.dma.is_some());
+
+        if let Some(dma) = &self.dma {
+            writer.u32(dma.pif_addr);
+            writer.bool(dma.write);
         }
+
+        self.pif.save_state(writer);
+        self.joybus.save_state(writer);
+    }
+
+    pub fn load_state(&mut self, reader: &mut Reader) -> Result<(), Box<dyn Error>> {
+        reader.tag(b"SI  ")?;
+        self.regs.dram_addr = reader.reg()?;
+        self.regs.status = reader.reg()?;
+
+        self.dma = if reader.bool()? {
+            Some(Dma {
+                pif_addr: reader.u32()?,
+                write: reader.bool()?,
+            })
+        } else {
+            None
+        };
+
+        self.pif.load_state(reader)?;
+        self.joybus.load_state(reader)
+    }
+
+    pub fn movie(&self) -> Option<&Movie> {
+        self.joybus.movie()
+    }
+
+    pub fn movie_mut(&mut self) -> Option<&mut Movie> {
+        self.joybus.movie_mut()
+    }
+
+    pub fn set_movie(&mut self, movie: Option<Movie>) {
+        self.joybus.set_movie(movie);
+    }
+
+    pub fn tick_saves(&mut self) {
+        self.joybus.tick_saves();
+    }
+
+    pub fn flush_saves(&mut self) {
+        self.joybus.flush_saves();
     }
 
     pub fn update_joypads(&mut self, joypads: &[JoypadState; 4]) {
@@ -62,15 +126,15 @@ impl SerialInterface {
     }
 
     #[inline(always)]
-    pub fn step(&mut self, rdram: &mut Rdram) {
+    pub fn step(&mut self, rdram: &mut Rdram, cycles: u64) {
         if self.dma.is_none() {
             return;
         }
 
-        self.step_inner(rdram);
+        self.step_inner(rdram, cycles);
     }
 
-    fn step_inner(&mut self, rdram: &mut Rdram) {
+    fn step_inner(&mut self, rdram: &mut Rdram, cycles: u64) {
         let dma = self.dma.as_ref().unwrap();
 
         let dram_addr = self.regs.dram_addr.dram_addr();
@@ -100,7 +164,7 @@ impl SerialInterface {
                 self.joybus.configure(self.pif.ram());
             }
         } else {
-            self.joybus.execute(self.pif.ram_mut());
+            self.joybus.execute(self.pif.ram_mut(), cycles);
 
             let mut pif_addr = dma.pif_addr;
 
@@ -0,0 +1,83 @@
+use crate::save::SaveFile;
+use crate::snapshot::{Reader, Writer};
+use std::error::Error;
+use std::io;
+use std::path::PathBuf;
+use tracing::warn;
+
+const SIZE: usize = 32768;
+
+pub const BLOCK_SIZE: usize = 32;
+
+pub struct ControllerPak {
+    save: SaveFile,
+}
+
+impl ControllerPak {
+    pub fn new(path: Option<PathBuf>) -> io::Result<Self> {
+        Ok(Self {
+            save: SaveFile::open(path, SIZE, 0)?,
+        })
+    }
+
+    pub fn save_state(&self, writer: &mut Writer) {
+        self.save.save_state(writer);
+    }
+
+    pub fn load_state(&mut self, reader: &mut Reader) -> Result<(), Box<dyn Error>> {
+        self.save.load_state(reader)
+    }
+
+    pub fn tick(&mut self) {
+        self.save.tick();
+    }
+
+    pub fn flush(&mut self) {
+        self.save.flush();
+    }
+
+    pub fn read(&self, address: usize, data: &mut [u8]) {
+        if address < SIZE {
+            data.copy_from_slice(&self.save.data()[address..(address + data.len())]);
+        } else {
+            // Accessory probe area: reads as zero, as no Rumble Pak is present
+            data.fill(0);
+        }
+    }
+
+    pub fn write(&mut self, address: usize, data: &[u8]) {
+        if address < SIZE {
+            self.save.data_mut()[address..(address + data.len())].copy_from_slice(data);
+        } else {
+            warn!("Controller Pak write to {:04X} ignored", address);
+        }
+    }
+}
+
+// Checks the 5-bit CRC in the low bits of a Controller Pak address, returning
+// the block address if it is valid
+pub fn check_address(address: u16) -> Option<usize> {
+    const XOR_TABLE: [u8; 16] = [
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x15, 0x1f, 0x0b, 0x16, 0x19, 0x07, 0x0e, 0x1c, 0x0d, 0x1a,
+        0x01,
+    ];
+
+    let crc = (5..16)
+        .filter(|bit| (address & (1 << bit)) != 0)
+        .fold(0, |crc, bit| crc ^ XOR_TABLE[bit]);
+
+    (crc == (address & 0x1f) as u8).then_some((address & !0x1f) as usize)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checks_address_crc() {
+        assert_eq!(check_address(0x0000), Some(0x0000));
+        assert_eq!(check_address(0x8001), Some(0x8000));
+        assert_eq!(check_address(0xc01b), Some(0xc000));
+        assert_eq!(check_address(0xc01a), None);
+    }
+}
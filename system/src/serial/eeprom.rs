@@ -0,0 +1,57 @@
+use crate::save::SaveFile;
+use crate::snapshot::{Reader, Writer};
+use std::error::Error;
+use std::io;
+use std::path::PathBuf;
+
+pub const BLOCK_SIZE: usize = 8;
+
+pub struct Eeprom {
+    save: SaveFile,
+    id: u8,
+}
+
+impl Eeprom {
+    pub fn new(len: usize, path: Option<PathBuf>) -> io::Result<Self> {
+        Ok(Self {
+            save: SaveFile::open(path, len, 0xff)?,
+            id: if len > 512 { 0xc0 } else { 0x80 },
+        })
+    }
+
+    pub fn save_state(&self, writer: &mut Writer) {
+        self.save.save_state(writer);
+    }
+
+    pub fn load_state(&mut self, reader: &mut Reader) -> Result<(), Box<dyn Error>> {
+        self.save.load_state(reader)
+    }
+
+    pub fn tick(&mut self) {
+        self.save.tick();
+    }
+
+    pub fn flush(&mut self) {
+        self.save.flush();
+    }
+
+    pub fn status(&self) -> [u8; 3] {
+        // TODO: 'Write in progress' flag
+        [0x00, self.id, 0x00]
+    }
+
+    pub fn read(&self, block: u8, data: &mut [u8]) {
+        let address = self.block_address(block);
+        data.copy_from_slice(&self.save.data()[address..(address + BLOCK_SIZE)]);
+    }
+
+    pub fn write(&mut self, block: u8, data: &[u8]) {
+        let address = self.block_address(block);
+        self.save.data_mut()[address..(address + BLOCK_SIZE)].copy_from_slice(data);
+    }
+
+    // Block numbers wrap around on the smaller EEPROM
+    fn block_address(&self, block: u8) -> usize {
+        (block as usize * BLOCK_SIZE) % self.save.data().len()
+    }
+}
@@ -1,5 +1,14 @@
+use super::controller_pak::{self, ControllerPak, BLOCK_SIZE};
+use super::eeprom::{self, Eeprom};
+use super::rtc::{self, Rtc};
 use crate::header::SaveType;
+use crate::movie::Movie;
+use crate::save;
+use crate::snapshot::{Reader, Writer};
 use arrayvec::ArrayVec;
+use std::error::Error;
+use std::io;
+use std::path::Path;
 use tracing::{debug, trace, warn};
 
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
@@ -22,18 +31,164 @@ pub struct JoypadState {
     pub axis_y: i8,
 }
 
+// What is plugged into the accessory slot of each controller
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Accessory {
+    None,
+    ControllerPak,
+}
+
+#[derive(Default)]
+struct Port {
+    pak: Option<ControllerPak>,
+    // Set when a Controller Pak address fails its CRC check, until the next
+    // status query
+    address_crc_error: bool,
+}
+
 pub struct Joybus {
     program: [u8; 64],
     joypads: [[u8; 4]; 4],
-    save_type: SaveType,
+    ports: [Port; 4],
+    eeprom: Option<Eeprom>,
+    rtc: Option<Rtc>,
+    movie: Option<Movie>,
 }
 
 impl Joybus {
-    pub fn new(save_type: SaveType) -> Self {
-        Self {
+    pub fn new(
+        save_type: SaveType,
+        accessories: [Accessory; 4],
+        rtc_start: Option<i64>,
+        save_path: Option<&Path>,
+    ) -> io::Result<Self> {
+        let mut ports: [Port; 4] = Default::default();
+
+        for (index, accessory) in accessories.into_iter().enumerate() {
+            if accessory == Accessory::ControllerPak {
+                let path = save::save_path(save_path, &format!("-{}.mpk", index + 1));
+                ports[index].pak = Some(ControllerPak::new(path)?);
+            }
+        }
+
+        let eeprom_len = match save_type {
+            SaveType::Eeprom4K => Some(512),
+            SaveType::Eeprom16K => Some(2048),
+            _ => None,
+        };
+
+        let eeprom = eeprom_len
+            .map(|len| Eeprom::new(len, save::save_path(save_path, ".eep")))
+            .transpose()?;
+
+        Ok(Self {
             program: [0; 64],
             joypads: [[0; 4]; 4],
-            save_type,
+            ports,
+            eeprom,
+            rtc: rtc_start.map(Rtc::new),
+            movie: None,
+        })
+    }
+
+    pub fn save_state(&self, writer: &mut Writer) {
+        writer.tag(b"JBUS");
+        writer.bytes(&self.program);
+
+        for joypad in &self.joypads {
+            writer.bytes(joypad);
+        }
+
+        for port in &self.ports {
+            writer.bool(port.address_crc_error);
+            writer.bool(port.pak.is_some());
+
+            if let Some(pak) = &port.pak {
+                pak.save_state(writer);
+            }
+        }
+
+        writer.bool(self.eeprom.is_some());
+
+        if let Some(eeprom) = &self.eeprom {
+            eeprom.save_state(writer);
+        }
+
+        writer.bool(self.rtc.is_some());
+
+        if let Some(rtc) = &self.rtc {
+            rtc.save_state(writer);
+        }
+    }
+
+    pub fn load_state(&mut self, reader: &mut Reader) -> Result<(), Box<dyn Error>> {
+        reader.tag(b"JBUS")?;
+        reader.bytes_into(&mut self.program)?;
+
+        for joypad in &mut self.joypads {
+            reader.bytes_into(joypad)?;
+        }
+
+        for port in &mut self.ports {
+            port.address_crc_error = reader.bool()?;
+
+            if reader.bool()? != port.pak.is_some() {
+                return Err("Save state has different Controller Paks inserted".into());
+            }
+
+            if let Some(pak) = &mut port.pak {
+                pak.load_state(reader)?;
+            }
+        }
+
+        if reader.bool()? != self.eeprom.is_some() {
+            return Err("Save state has a different save type".into());
+        }
+
+        if let Some(eeprom) = &mut self.eeprom {
+            eeprom.load_state(reader)?;
+        }
+
+        if reader.bool()? != self.rtc.is_some() {
+            return Err("Save state has a different RTC setting".into());
+        }
+
+        if let Some(rtc) = &mut self.rtc {
+            rtc.load_state(reader)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn movie(&self) -> Option<&Movie> {
+        self.movie.as_ref()
+    }
+
+    pub fn movie_mut(&mut self) -> Option<&mut Movie> {
+        self.movie.as_mut()
+    }
+
+    pub fn set_movie(&mut self, movie: Option<Movie>) {
+        self.movie = movie;
+    }
+
+    pub fn tick_saves(&mut self) {
+        for pak in self.ports.iter_mut().filter_map(|port| port.pak.as_mut()) {
+            pak.tick();
+        }
+
+        if let Some(eeprom) = &mut self.eeprom {
+            eeprom.tick();
+        }
+    }
+
+    pub fn flush_saves(&mut self) {
+        for pak in self.ports.iter_mut().filter_map(|port| port.pak.as_mut()) {
+            pak.flush();
+        }
+
+        if let Some(eeprom) = &mut self.eeprom {
+            eeprom.flush();
         }
     }
 
@@ -68,9 +223,13 @@ impl Joybus {
         trace!("Joybus Configured");
     }
 
-    pub fn execute(&mut self, pif_ram: &mut [u8]) {
+    pub fn execute(&mut self, pif_ram: &mut [u8], cycles: u64) {
         debug!("PIF Joybus Input: {:X?}", self.program);
 
+        if let Some(movie) = &mut self.movie {
+            movie.poll(&mut self.joypads);
+        }
+
         let mut channel = 0;
         let mut index = 0;
 
@@ -113,7 +272,7 @@ impl Joybus {
                 break;
             }
 
-            if let Some(recv_data) = self.perform_query(channel, &send_data) {
+            if let Some(recv_data) = self.perform_query(channel, &send_data, cycles) {
                 let len = recv_data.len();
 
                 if len != recv_bytes {
@@ -132,33 +291,52 @@ impl Joybus {
         debug!("PIF Joybus Output: {:X?}", pif_ram);
     }
 
-    fn perform_query(&self, channel: usize, input: &[u8]) -> Option<ArrayVec<u8, 64>> {
+    fn perform_query(
+        &mut self,
+        channel: usize,
+        input: &[u8],
+        cycles: u64,
+    ) -> Option<ArrayVec<u8, 64>> {
         let mut output = ArrayVec::new();
 
+        // Commands sent without all of their arguments get no response
+        if input.len() < command_len(input[0]) {
+            warn!(
+                "JoyBus command {:02X} too short: {} bytes",
+                input[0],
+                input.len()
+            );
+            return None;
+        }
+
         match input[0] {
-            0x00 | 0xff => {
-                match channel {
-                    0 => {
-                        output.push(0x05);
-                        output.push(0x00);
-                        output.push(0x02); // TODO: Controller Pak
+            0x00 | 0xff => match channel {
+                0..=3 => {
+                    let port = &mut self.ports[channel];
+
+                    // The first controller is always plugged in. The others
+                    // only are if they have an accessory.
+                    if channel > 0 && port.pak.is_none() {
+                        return None;
                     }
-                    1..=3 => return None,
-                    4 => {
-                        // TODO: This shouldn't return anything for non-EEPROM saves
-                        output.push(0x00);
-
-                        output.push(if self.save_type == SaveType::Eeprom16K {
-                            0xc0
-                        } else {
-                            0x80
-                        });
-
-                        output.push(0x00); // TODO: 'Write in progress' flag
+
+                    let mut status = if port.pak.is_some() { 0x01 } else { 0x02 };
+
+                    if port.address_crc_error {
+                        status |= 0x04;
+                        port.address_crc_error = false;
                     }
-                    _ => panic!("Invalid JoyBus channel: {}", channel),
+
+                    output.push(0x05);
+                    output.push(0x00);
+                    output.push(status);
                 }
-            }
+                4 => {
+                    let eeprom = self.eeprom.as_ref()?;
+                    output.try_extend_from_slice(&eeprom.status()).unwrap();
+                }
+                _ => panic!("Invalid JoyBus channel: {}", channel),
+            },
             0x01 => {
                 if channel > 3 {
                     panic!("Invalid JoyBus channel: {}", channel);
@@ -173,33 +351,72 @@ impl Joybus {
                     panic!("Invalid JoyBus channel: {}", channel);
                 }
 
-                warn!("Controller Pak reads not yet implemented");
+                let mut data = [0; BLOCK_SIZE];
+                let port = &mut self.ports[channel];
 
-                for _ in 0..32 {
-                    output.push(0);
+                match controller_pak::check_address(u16::from_be_bytes([input[1], input[2]])) {
+                    Some(address) => {
+                        if let Some(pak) = &port.pak {
+                            pak.read(address, &mut data);
+                        }
+                    }
+                    None => {
+                        warn!("Controller Pak address CRC error");
+                        port.address_crc_error = true;
+                    }
                 }
 
-                output.push(calc_crc(&output[0..32]));
+                output.try_extend_from_slice(&data).unwrap();
+                output.push(data_crc(port, &data));
             }
             0x03 => {
                 if channel > 3 {
                     panic!("Invalid JoyBus channel: {}", channel);
                 }
 
-                warn!("Controller Pak writes not yet implemented");
-                output.push(calc_crc(&input[3..35]));
+                let data = &input[3..(3 + BLOCK_SIZE)];
+                let port = &mut self.ports[channel];
+
+                match controller_pak::check_address(u16::from_be_bytes([input[1], input[2]])) {
+                    Some(address) => {
+                        if let Some(pak) = &mut port.pak {
+                            pak.write(address, data);
+                        }
+                    }
+                    None => {
+                        warn!("Controller Pak address CRC error");
+                        port.address_crc_error = true;
+                    }
+                }
+
+                output.push(data_crc(port, data));
             }
             0x04 => {
-                // TODO: EEPROM reads
-                for _ in 0..8 {
-                    output.push(0x00);
-                }
+                let eeprom = self.eeprom.as_ref().filter(|_| channel == 4)?;
+                let mut data = [0; eeprom::BLOCK_SIZE];
+                eeprom.read(input[1], &mut data);
+                output.try_extend_from_slice(&data).unwrap();
             }
             0x05 => {
-                // TODO: EEPROM writes
+                let eeprom = self.eeprom.as_mut().filter(|_| channel == 4)?;
+                eeprom.write(input[1], &input[2..(2 + eeprom::BLOCK_SIZE)]);
                 // TODO: 'Write in progress' flag
                 output.push(0x00);
             }
+            0x06 => {
+                let rtc = self.rtc.as_ref().filter(|_| channel == 4)?;
+                output.try_extend_from_slice(&rtc.status()).unwrap();
+            }
+            0x07 => {
+                let rtc = self.rtc.as_ref().filter(|_| channel == 4)?;
+                output
+                    .try_extend_from_slice(&rtc.read(input[1], cycles))
+                    .unwrap();
+            }
+            0x08 => {
+                let rtc = self.rtc.as_mut().filter(|_| channel == 4)?;
+                output.push(rtc.write(input[1], &input[2..(2 + rtc::BLOCK_SIZE)], cycles));
+            }
             _ => panic!("Unknown JoyBus command: {:02X}", input[0]),
         }
 
@@ -207,6 +424,30 @@ impl Joybus {
     }
 }
 
+// Bytes sent with each command, including the command byte itself
+fn command_len(command: u8) -> usize {
+    match command {
+        0x02 => 3,
+        0x03 => 3 + BLOCK_SIZE,
+        0x04 | 0x07 => 2,
+        0x05 => 2 + eeprom::BLOCK_SIZE,
+        0x08 => 2 + rtc::BLOCK_SIZE,
+        _ => 1,
+    }
+}
+
+// The CRC is inverted when nothing is there to receive the data, which is
+// how games tell that no accessory is present
+fn data_crc(port: &Port, data: &[u8]) -> u8 {
+    let crc = calc_crc(data);
+
+    if port.pak.is_some() && !port.address_crc_error {
+        crc
+    } else {
+        !crc
+    }
+}
+
 fn calc_crc(data: &[u8]) -> u8 {
     debug_assert!(data.len() == 32);
 
@@ -1,4 +1,6 @@
 use crate::memory::{Memory, Size};
+use crate::snapshot::{Reader, Writer};
+use std::error::Error;
 use std::mem;
 use tracing::{trace, warn};
 
@@ -25,6 +27,20 @@ impl Pif {
         Self { mem, rom_locked }
     }
 
+    // Only PIF RAM is saved, as the boot ROM never changes
+    pub fn save_state(&self, writer: &mut Writer) {
+        writer.tag(b"PIF ");
+        writer.bytes(self.ram());
+        writer.bool(self.rom_locked);
+    }
+
+    pub fn load_state(&mut self, reader: &mut Reader) -> Result<(), Box<dyn Error>> {
+        reader.tag(b"PIF ")?;
+        reader.bytes_into(self.ram_mut())?;
+        self.rom_locked = reader.bool()?;
+        Ok(())
+    }
+
     pub fn ram(&self) -> &[u8] {
         &self.mem[PIF_RAM_START as usize..]
     }
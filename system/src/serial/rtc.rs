@@ -0,0 +1,153 @@
+use crate::snapshot::{Reader, Writer};
+use crate::RCP_CLOCK_RATE;
+use std::error::Error;
+use tracing::warn;
+
+pub const BLOCK_SIZE: usize = 8;
+
+// Real-time clock (as used by Animal Forest). Time is kept by counting
+// emulated cycles from a start time, so that the clock runs the same way when
+// a movie is played back or a save state is loaded.
+pub struct Rtc {
+    control: [u8; 2],
+    // Seconds since the Unix epoch as of cycle zero, moved by however far the
+    // game has set the clock
+    start: i64,
+}
+
+impl Rtc {
+    pub fn new(start: i64) -> Self {
+        Self {
+            control: [0x03, 0x00],
+            start,
+        }
+    }
+
+    pub fn save_state(&self, writer: &mut Writer) {
+        writer.bytes(&self.control);
+        writer.u64(self.start as u64);
+    }
+
+    pub fn load_state(&mut self, reader: &mut Reader) -> Result<(), Box<dyn Error>> {
+        reader.bytes_into(&mut self.control)?;
+        self.start = reader.u64()? as i64;
+        Ok(())
+    }
+
+    pub fn status(&self) -> [u8; 3] {
+        [0x00, 0x10, 0x00]
+    }
+
+    pub fn read(&self, block: u8, cycles: u64) -> [u8; BLOCK_SIZE + 1] {
+        let mut output = [0; BLOCK_SIZE + 1];
+
+        match block {
+            0 => output[0..2].copy_from_slice(&self.control),
+            1 => (),
+            2 => output[0..BLOCK_SIZE].copy_from_slice(&encode_time(self.start + elapsed(cycles))),
+            _ => warn!("RTC read from unknown block {}", block),
+        }
+
+        output
+    }
+
+    pub fn write(&mut self, block: u8, data: &[u8], cycles: u64) -> u8 {
+        match block {
+            0 => self.control.copy_from_slice(&data[0..2]),
+            1 => (),
+            2 => self.start = decode_time(data) - elapsed(cycles),
+            _ => warn!("RTC write to unknown block {}", block),
+        }
+
+        0x00
+    }
+}
+
+// Whole seconds of emulated time
+fn elapsed(cycles: u64) -> i64 {
+    (cycles as f64 / RCP_CLOCK_RATE) as i64
+}
+
+// Converts seconds since the Unix epoch to the RTC's BCD format: second,
+// minute, hour, day, weekday, month, year and century
+fn encode_time(time: i64) -> [u8; BLOCK_SIZE] {
+    let days = time.div_euclid(86400);
+    let seconds = time.rem_euclid(86400);
+    let (year, month, day) = civil_from_days(days);
+
+    [
+        bcd(seconds % 60),
+        bcd(seconds / 60 % 60),
+        bcd(seconds / 3600) | 0x80, // 24-hour clock
+        bcd(day),
+        bcd((days + 4).rem_euclid(7)), // 1970-01-01 was a Thursday
+        bcd(month),
+        bcd(year % 100),
+        if year >= 2000 { 0x01 } else { 0x00 },
+    ]
+}
+
+fn decode_time(data: &[u8]) -> i64 {
+    let value = |index: usize, mask: u8| {
+        let byte = data[index] & mask;
+        (byte >> 4) as i64 * 10 + (byte & 0x0f) as i64
+    };
+
+    let year = 1900 + data[7] as i64 * 100 + value(6, 0xff);
+    let days = days_from_civil(year, value(5, 0x1f), value(3, 0x3f));
+
+    days * 86400 + value(2, 0x3f) * 3600 + value(1, 0x7f) * 60 + value(0, 0x7f)
+}
+
+fn bcd(value: i64) -> u8 {
+    (((value / 10) << 4) | (value % 10)) as u8
+}
+
+// Date conversions from Howard Hinnant's 'chrono-Compatible Low-Level Date
+// Algorithms'
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z.rem_euclid(146097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let yoe = year.rem_euclid(400);
+    let mp = if month > 2 { month - 3 } else { month + 9 };
+    let doy = (153 * mp + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146097 + doe - 719468
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encodes_time_as_bcd() {
+        // 2001-09-09 01:46:40 (a Sunday)
+        let time = encode_time(1_000_000_000);
+        assert_eq!(time, [0x40, 0x46, 0x81, 0x09, 0x00, 0x09, 0x01, 0x01]);
+        assert_eq!(decode_time(&time), 1_000_000_000);
+    }
+
+    #[test]
+    fn keeps_time_by_emulated_cycles() {
+        let mut rtc = Rtc::new(1_000_000_000);
+        let minute = 60 * RCP_CLOCK_RATE as u64;
+        assert_eq!(rtc.read(2, minute)[0..2], [0x40, 0x47]);
+
+        // Setting the clock carries on from the new time
+        rtc.write(2, &encode_time(0), minute);
+        assert_eq!(rtc.read(2, minute * 2)[0..2], [0x00, 0x01]);
+    }
+}
@@ -0,0 +1,212 @@
+use crate::header::{CicType, Header, SaveType};
+use crate::serial::Accessory;
+use std::collections::HashMap;
+use std::error::Error;
+use tracing::debug;
+
+// Settings for known games, which any user-supplied settings are layered over
+const BUNDLED: &str = include_str!("games.ini");
+
+// Per-game settings. Anything left unset falls back to what is detected from
+// the ROM header (or to the emulator default).
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct GameSettings {
+    pub save_type: Option<SaveType>,
+    pub expansion_pak: Option<bool>,
+    pub accessories: [Option<Accessory>; 4],
+    pub rtc: Option<bool>,
+    pub cic_type: Option<CicType>,
+    pub granularity: Option<u64>,
+}
+
+impl GameSettings {
+    // Fills in anything not set here from 'other'
+    fn merge(&mut self, other: &GameSettings) {
+        self.save_type = self.save_type.or(other.save_type);
+        self.expansion_pak = self.expansion_pak.or(other.expansion_pak);
+
+        for (accessory, other) in self.accessories.iter_mut().zip(other.accessories) {
+            *accessory = accessory.or(other);
+        }
+
+        self.rtc = self.rtc.or(other.rtc);
+        self.cic_type = self.cic_type.or(other.cic_type);
+        self.granularity = self.granularity.or(other.granularity);
+    }
+}
+
+// Looks up the settings for a ROM in the bundled database and in 'extra'
+// (which takes priority). Sections are matched by header CRCs, then by game
+// code and version, then by game code alone, then by game code without the
+// region, with more specific sections taking priority.
+pub fn lookup(header: &Header, extra: Option<&str>) -> Result<GameSettings, Box<dyn Error>> {
+    let keys = [
+        format!("{:08X} {:08X}", header.crcs[0], header.crcs[1]),
+        format!("{} v{}", header.code, header.version),
+        header.code.clone(),
+        header.code.chars().take(3).collect(),
+    ];
+
+    let mut settings = GameSettings::default();
+
+    for text in extra.into_iter().chain([BUNDLED]) {
+        let database = parse(text)?;
+
+        for key in &keys {
+            if let Some(section) = database.get(key) {
+                debug!("Using settings from [{}]", key);
+                settings.merge(section);
+            }
+        }
+    }
+
+    Ok(settings)
+}
+
+fn parse(text: &str) -> Result<HashMap<String, GameSettings>, Box<dyn Error>> {
+    let mut database: HashMap<String, GameSettings> = HashMap::new();
+    let mut section = None;
+
+    for (index, line) in text.lines().enumerate() {
+        let line = line.split('#').next().unwrap().trim();
+
+        if line.is_empty() {
+            continue;
+        }
+
+        let error = |message: String| format!("Settings line {}: {}", index + 1, message);
+
+        if let Some(key) = line
+            .strip_prefix('[')
+            .and_then(|line| line.strip_suffix(']'))
+        {
+            section = Some(database.entry(key.trim().to_owned()).or_default());
+            continue;
+        }
+
+        let Some(settings) = section.as_deref_mut() else {
+            return Err(error("setting outside of a section".to_owned()).into());
+        };
+
+        let Some((name, value)) = line.split_once('=') else {
+            return Err(error(format!("expected 'name = value', found '{}'", line)).into());
+        };
+
+        let value = value.trim();
+
+        match name.trim() {
+            "save_type" => settings.save_type = Some(value.parse().map_err(error)?),
+            "expansion_pak" => settings.expansion_pak = Some(parse_bool(value).map_err(error)?),
+            "accessory1" => settings.accessories[0] = Some(parse_accessory(value).map_err(error)?),
+            "accessory2" => settings.accessories[1] = Some(parse_accessory(value).map_err(error)?),
+            "accessory3" => settings.accessories[2] = Some(parse_accessory(value).map_err(error)?),
+            "accessory4" => settings.accessories[3] = Some(parse_accessory(value).map_err(error)?),
+            "rtc" => settings.rtc = Some(parse_bool(value).map_err(error)?),
+            "cic" => settings.cic_type = Some(parse_cic_type(value).map_err(error)?),
+            "granularity" => {
+                settings.granularity = Some(
+                    value
+                        .parse()
+                        .map_err(|_| error(format!("invalid granularity '{}'", value)))?,
+                )
+            }
+            name => return Err(error(format!("unknown setting '{}'", name)).into()),
+        }
+    }
+
+    Ok(database)
+}
+
+fn parse_bool(value: &str) -> Result<bool, String> {
+    match value {
+        "true" => Ok(true),
+        "false" => Ok(false),
+        _ => Err(format!("expected 'true' or 'false', found '{}'", value)),
+    }
+}
+
+fn parse_accessory(value: &str) -> Result<Accessory, String> {
+    match value {
+        "none" => Ok(Accessory::None),
+        "controller-pak" => Ok(Accessory::ControllerPak),
+        _ => Err(format!("unknown accessory '{}'", value)),
+    }
+}
+
+fn parse_cic_type(value: &str) -> Result<CicType, String> {
+    match value {
+        "6101" => Ok(CicType::Nus6101),
+        "6102" => Ok(CicType::Nus6102),
+        "6103" => Ok(CicType::Nus6103),
+        "6105" => Ok(CicType::Nus6105),
+        "6106" => Ok(CicType::Nus6106),
+        "mini-ipl3" => Ok(CicType::MiniIPL3),
+        _ => Err(format!("unknown CIC type '{}'", value)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prefers_more_specific_sections() {
+        let header = Header {
+            code: "NXXE".to_owned(),
+            version: 1,
+            crcs: [0x1234_5678, 0x9abc_def0],
+            cic_type: CicType::Nus6102,
+            save_type: None,
+            accessories: [None; 4],
+        };
+
+        let extra = "
+            # Comment
+            [NXXE]
+            save_type = sram256k
+            granularity = 100
+
+            [NXXE v1]
+            granularity = 200
+            accessory2 = controller-pak
+
+            [NXXE v2]
+            rtc = true
+
+            [12345678 9ABCDEF0]
+            cic = 6105
+        ";
+
+        let settings = lookup(&header, Some(extra)).unwrap();
+        assert_eq!(settings.save_type, Some(SaveType::Sram256K));
+        assert_eq!(settings.granularity, Some(200));
+        assert_eq!(settings.accessories[1], Some(Accessory::ControllerPak));
+        assert_eq!(settings.rtc, None);
+        assert_eq!(settings.cic_type, Some(CicType::Nus6105));
+
+        let error = lookup(&header, Some("[NXXE]\nrtc = yes")).unwrap_err();
+        assert!(error.to_string().starts_with("Settings line 2:"));
+    }
+
+    #[test]
+    fn bundles_save_types_by_game_code() {
+        let header = |code: &str| Header {
+            code: code.to_owned(),
+            version: 0,
+            crcs: [0; 2],
+            cic_type: CicType::Nus6102,
+            save_type: None,
+            accessories: [None; 4],
+        };
+
+        let save_type = |code| lookup(&header(code), None).unwrap().save_type;
+        assert_eq!(save_type("NZLE"), Some(SaveType::Sram256K));
+        assert_eq!(save_type("NK4J"), Some(SaveType::Sram256K));
+        assert_eq!(save_type("NJFP"), Some(SaveType::Eeprom16K));
+        assert_eq!(save_type("NSME"), None);
+
+        let settings = lookup(&header("NAFJ"), None).unwrap();
+        assert_eq!(settings.save_type, Some(SaveType::FlashRam));
+        assert_eq!(settings.rtc, Some(true));
+    }
+}
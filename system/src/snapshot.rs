@@ -0,0 +1,260 @@
+use std::error::Error;
+
+// Save state format: an 8-byte magic, a version number, then the state of
+// each component in a fixed order. Each component begins with a 4-byte tag so
+// that a mismatch is caught close to where it happened. All values are
+// little-endian.
+const MAGIC: &[u8; 8] = b"REALITY\x1a";
+
+// Increment this whenever the layout of any component changes
+pub const VERSION: u32 = 1;
+
+pub struct Writer {
+    data: Vec<u8>,
+}
+
+impl Writer {
+    pub fn new() -> Self {
+        Self::with_header(MAGIC, VERSION)
+    }
+
+    // Other file formats can reuse the same encoding with their own header
+    pub fn with_header(magic: &[u8; 8], version: u32) -> Self {
+        let mut writer = Self { data: Vec::new() };
+        writer.data.extend_from_slice(magic);
+        writer.u32(version);
+        writer
+    }
+
+    pub fn finish(self) -> Vec<u8> {
+        self.data
+    }
+
+    pub fn tag(&mut self, tag: &[u8; 4]) {
+        self.data.extend_from_slice(tag);
+    }
+
+    pub fn bool(&mut self, value: bool) {
+        self.u8(value as u8);
+    }
+
+    pub fn u8(&mut self, value: u8) {
+        self.data.push(value);
+    }
+
+    pub fn u16(&mut self, value: u16) {
+        self.data.extend_from_slice(&value.to_le_bytes());
+    }
+
+    pub fn u32(&mut self, value: u32) {
+        self.data.extend_from_slice(&value.to_le_bytes());
+    }
+
+    pub fn u64(&mut self, value: u64) {
+        self.data.extend_from_slice(&value.to_le_bytes());
+    }
+
+    pub fn i32(&mut self, value: i32) {
+        self.u32(value as u32);
+    }
+
+    pub fn i64(&mut self, value: i64) {
+        self.u64(value as u64);
+    }
+
+    // For 32-bit register bitfields
+    pub fn reg<T: Into<u32>>(&mut self, value: T) {
+        self.u32(value.into());
+    }
+
+    // For 64-bit register bitfields
+    pub fn reg64<T: Into<u64>>(&mut self, value: T) {
+        self.u64(value.into());
+    }
+
+    // Writes a length-prefixed block of bytes
+    pub fn bytes(&mut self, data: &[u8]) {
+        self.u32(data.len() as u32);
+        self.data.extend_from_slice(data);
+    }
+}
+
+pub struct Reader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    pub fn new(data: &'a [u8]) -> Result<Self, Box<dyn Error>> {
+        Self::with_header(data, MAGIC, VERSION, "save state")
+    }
+
+    pub fn with_header(
+        data: &'a [u8],
+        magic: &[u8; 8],
+        version: u32,
+        description: &str,
+    ) -> Result<Self, Box<dyn Error>> {
+        let mut reader = Self { data, pos: 0 };
+
+        if reader.take(magic.len())? != magic {
+            return Err(format!("Not a {}", description).into());
+        }
+
+        let found = reader.u32()?;
+
+        if found != version {
+            return Err(format!(
+                "Unsupported {} version: {} (expected {})",
+                description, found, version
+            )
+            .into());
+        }
+
+        Ok(reader)
+    }
+
+    pub fn finish(self) -> Result<(), Box<dyn Error>> {
+        if self.pos != self.data.len() {
+            return Err(format!(
+                "Save state has {} bytes of unexpected trailing data",
+                self.data.len() - self.pos
+            )
+            .into());
+        }
+
+        Ok(())
+    }
+
+    pub fn tag(&mut self, tag: &[u8; 4]) -> Result<(), Box<dyn Error>> {
+        let offset = self.pos;
+
+        if self.take(tag.len())? != tag {
+            return Err(format!(
+                "Save state is corrupt: expected '{}' section at offset {:X}",
+                String::from_utf8_lossy(tag),
+                offset
+            )
+            .into());
+        }
+
+        Ok(())
+    }
+
+    pub fn bool(&mut self) -> Result<bool, Box<dyn Error>> {
+        Ok(self.u8()? != 0)
+    }
+
+    pub fn u8(&mut self) -> Result<u8, Box<dyn Error>> {
+        Ok(self.take(1)?[0])
+    }
+
+    pub fn u16(&mut self) -> Result<u16, Box<dyn Error>> {
+        Ok(u16::from_le_bytes(self.array()?))
+    }
+
+    pub fn u32(&mut self) -> Result<u32, Box<dyn Error>> {
+        Ok(u32::from_le_bytes(self.array()?))
+    }
+
+    pub fn u64(&mut self) -> Result<u64, Box<dyn Error>> {
+        Ok(u64::from_le_bytes(self.array()?))
+    }
+
+    pub fn i32(&mut self) -> Result<i32, Box<dyn Error>> {
+        Ok(self.u32()? as i32)
+    }
+
+    pub fn i64(&mut self) -> Result<i64, Box<dyn Error>> {
+        Ok(self.u64()? as i64)
+    }
+
+    pub fn reg<T: From<u32>>(&mut self) -> Result<T, Box<dyn Error>> {
+        Ok(self.u32()?.into())
+    }
+
+    pub fn reg64<T: From<u64>>(&mut self) -> Result<T, Box<dyn Error>> {
+        Ok(self.u64()?.into())
+    }
+
+    pub fn bytes(&mut self) -> Result<&'a [u8], Box<dyn Error>> {
+        let len = self.u32()? as usize;
+        self.take(len)
+    }
+
+    // Reads a length-prefixed block of bytes, which must exactly fill 'dst'
+    pub fn bytes_into(&mut self, dst: &mut [u8]) -> Result<(), Box<dyn Error>> {
+        let src = self.bytes()?;
+
+        if src.len() != dst.len() {
+            return Err(format!(
+                "Save state is corrupt: expected block of {} bytes, found {}",
+                dst.len(),
+                src.len()
+            )
+            .into());
+        }
+
+        dst.copy_from_slice(src);
+        Ok(())
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], Box<dyn Error>> {
+        Ok(self.take(N)?.try_into().unwrap())
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8], Box<dyn Error>> {
+        let end = self
+            .pos
+            .checked_add(len)
+            .filter(|&end| end <= self.data.len())
+            .ok_or("Save state is truncated")?;
+
+        let slice = &self.data[self.pos..end];
+        self.pos = end;
+        Ok(slice)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let mut writer = Writer::new();
+        writer.tag(b"TEST");
+        writer.bool(true);
+        writer.u16(0x1234);
+        writer.i64(-2);
+        writer.bytes(&[1, 2, 3]);
+        let data = writer.finish();
+
+        let mut reader = Reader::new(&data).unwrap();
+        reader.tag(b"TEST").unwrap();
+        assert!(reader.bool().unwrap());
+        assert_eq!(reader.u16().unwrap(), 0x1234);
+        assert_eq!(reader.i64().unwrap(), -2);
+        let mut block = [0; 3];
+        reader.bytes_into(&mut block).unwrap();
+        assert_eq!(block, [1, 2, 3]);
+        reader.finish().unwrap();
+    }
+
+    #[test]
+    fn rejects_truncated_data() {
+        let mut writer = Writer::new();
+        writer.u64(0);
+        let data = writer.finish();
+
+        let mut reader = Reader::new(&data[..data.len() - 1]).unwrap();
+        assert!(reader.u64().is_err());
+    }
+
+    #[test]
+    fn rejects_wrong_version() {
+        let mut data = Writer::new().finish();
+        data[8] = data[8].wrapping_add(1);
+        assert!(Reader::new(&data).is_err());
+    }
+}
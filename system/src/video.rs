@@ -1,10 +1,13 @@
-use crate::gfx::GfxContext;
+use crate::gfx::{self, GfxContext};
 use crate::interrupt::{RcpIntType, RcpInterrupt};
 use crate::memory::{Size, WriteMask};
 use crate::rdram::Rdram;
+use crate::snapshot::{Reader, Writer};
 use crate::{RCP_CLOCK_RATE, VIDEO_DAC_RATE};
-use framebuffer::Framebuffer;
-use regs::{DisplayMode, Regs};
+pub use regs::{AntiAliasMode, DisplayMode};
+
+use framebuffer::{Framebuffer, FramebufferTexture};
+use regs::Regs;
 use std::error::Error;
 use tracing::{debug, trace};
 use upscaler::Upscaler;
@@ -13,25 +16,51 @@ mod framebuffer;
 mod regs;
 mod upscaler;
 
+pub struct Frame<'a> {
+    pub width: u32,
+    pub height: u32,
+    pub display_mode: DisplayMode,
+    pub aa_mode: AntiAliasMode,
+    pub pixels: &'a [u8],
+}
+
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum ScreenshotMode {
+    // The frame at the resolution the VI outputs it
+    #[default]
+    Native,
+    // The frame as scaled to fit the window
+    Upscaled,
+}
+
 pub struct VideoInterface {
     regs: Regs,
     cycles_remaining: u32,
     cycles_per_line: u32,
     frame_counter: u64,
     rcp_int: RcpInterrupt,
-    upscaler: Upscaler,
     frame_buffer: Framebuffer,
+    // Only present when there is a GPU to draw the output with
+    output: Option<Output>,
+}
+
+struct Output {
+    upscaler: Upscaler,
+    texture: FramebufferTexture,
 }
 
 impl VideoInterface {
     pub fn new(
         rcp_int: RcpInterrupt,
-        gfx: &GfxContext,
+        gfx: Option<&GfxContext>,
         skip_pif_rom: bool,
     ) -> Result<Self, Box<dyn Error>> {
-        let upscaler = Upscaler::new(gfx.device(), gfx.output_format());
-
-        let frame_buffer = Framebuffer::new(gfx.device(), upscaler.texture_bind_group_layout());
+        let output = gfx.map(|gfx| {
+            let upscaler = Upscaler::new(gfx.device(), gfx.output_format());
+            let texture =
+                FramebufferTexture::new(gfx.device(), upscaler.texture_bind_group_layout());
+            Output { upscaler, texture }
+        });
 
         let mut regs = Regs::default();
 
@@ -47,24 +76,87 @@ impl VideoInterface {
             cycles_per_line,
             frame_counter: 0,
             rcp_int,
-            upscaler,
-            frame_buffer,
+            frame_buffer: Framebuffer::new(),
+            output,
         })
     }
 
+    pub fn save_state(&self, writer: &mut Writer) {
+        let regs = &self.regs;
+
+        writer.tag(b"VI  ");
+        writer.reg(regs.ctrl);
+        writer.reg(regs.origin);
+        writer.reg(regs.width);
+        writer.reg(regs.v_intr);
+        writer.reg(regs.v_current);
+        writer.reg(regs.burst);
+        writer.reg(regs.v_sync);
+        writer.reg(regs.h_sync);
+        writer.reg(regs.h_sync_leap);
+        writer.reg(regs.h_video);
+        writer.reg(regs.v_video);
+        writer.reg(regs.v_burst);
+        writer.reg(regs.x_scale);
+        writer.reg(regs.y_scale);
+        writer.reg(regs.test_addr);
+        writer.u32(regs.staged_data);
+        writer.u32(self.cycles_remaining);
+        writer.u32(self.cycles_per_line);
+        writer.u64(self.frame_counter);
+    }
+
+    // RDRAM must already have been restored, as the frame buffer texture is
+    // rebuilt from it
+    pub fn load_state(
+        &mut self,
+        reader: &mut Reader,
+        rdram: &Rdram,
+        gfx: Option<&GfxContext>,
+    ) -> Result<(), Box<dyn Error>> {
+        let regs = &mut self.regs;
+
+        reader.tag(b"VI  ")?;
+        regs.ctrl = reader.reg()?;
+        regs.origin = reader.reg()?;
+        regs.width = reader.reg()?;
+        regs.v_intr = reader.reg()?;
+        regs.v_current = reader.reg()?;
+        regs.burst = reader.reg()?;
+        regs.v_sync = reader.reg()?;
+        regs.h_sync = reader.reg()?;
+        regs.h_sync_leap = reader.reg()?;
+        regs.h_video = reader.reg()?;
+        regs.v_video = reader.reg()?;
+        regs.v_burst = reader.reg()?;
+        regs.x_scale = reader.reg()?;
+        regs.y_scale = reader.reg()?;
+        regs.test_addr = reader.reg()?;
+        regs.staged_data = reader.u32()?;
+        self.cycles_remaining = reader.u32()?;
+        self.cycles_per_line = reader.u32()?;
+        self.frame_counter = reader.u64()?;
+
+        self.render(rdram, gfx);
+        Ok(())
+    }
+
     pub fn present(&mut self, gfx: &GfxContext) -> Result<(), wgpu::SurfaceError> {
-        let output = gfx.surface_texture()?;
+        let Some(Output { upscaler, texture }) = &mut self.output else {
+            return Ok(());
+        };
+
+        let output = gfx.output_texture()?;
 
         let view = output
-            .texture
+            .texture()
             .create_view(&wgpu::TextureViewDescriptor::default());
 
         let mut encoder = gfx
             .device()
             .create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
 
-        self.upscaler
-            .render(&mut encoder, &view, self.frame_buffer.bind_group());
+        upscaler.render(&mut encoder, &view, texture.bind_group());
 
         gfx.queue().submit(std::iter::once(encoder.finish()));
         output.present();
@@ -72,8 +164,96 @@ impl VideoInterface {
         Ok(())
     }
 
+    // Returns the most recently completed frame as a PNG image
+    pub fn screenshot(
+        &mut self,
+        gfx: Option<&GfxContext>,
+        mode: ScreenshotMode,
+    ) -> Result<Vec<u8>, Box<dyn Error>> {
+        let (width, height, mut pixels) = match mode {
+            ScreenshotMode::Native => (
+                self.frame_buffer.width(),
+                self.frame_buffer.height(),
+                self.frame_buffer.pixels().to_vec(),
+            ),
+            ScreenshotMode::Upscaled => {
+                let gfx = gfx.ok_or("Upscaled screenshots need a GPU")?;
+                let (width, height) = gfx.output_size();
+                (width, height, self.render_upscaled(gfx, width, height)?)
+            }
+        };
+
+        if width == 0 || height == 0 {
+            return Err("No video output to take a screenshot of".into());
+        }
+
+        // Alpha is meaningless on the TV, so make sure every pixel is opaque
+        for pixel in pixels.chunks_exact_mut(4) {
+            pixel[3] = 0xff;
+        }
+
+        gfx::encode_png(width, height, &pixels)
+    }
+
+    fn render_upscaled(
+        &mut self,
+        gfx: &GfxContext,
+        width: u32,
+        height: u32,
+    ) -> Result<Vec<u8>, Box<dyn Error>> {
+        let Output { upscaler, texture } = self.output.as_mut().ok_or("No GPU output")?;
+
+        // Window surfaces can't be read back, so draw to a texture of our own
+        let target = gfx.device().create_texture(&wgpu::TextureDescriptor {
+            label: Some("Screenshot Texture"),
+            size: wgpu::Extent3d {
+                width: width.max(1),
+                height: height.max(1),
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: gfx.output_format(),
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+            view_formats: &[],
+        });
+
+        let view = target.create_view(&wgpu::TextureViewDescriptor::default());
+
+        let mut encoder = gfx
+            .device()
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+
+        upscaler.render(&mut encoder, &view, texture.bind_group());
+
+        gfx.queue().submit(std::iter::once(encoder.finish()));
+
+        gfx.read_texture(&target)
+    }
+
+    // Fields per second, given the current line length and line count
+    pub fn frame_rate(&self) -> f64 {
+        let lines = self.regs.v_sync.v_sync() / 2 + 1;
+        RCP_CLOCK_RATE / (self.cycles_per_line as f64 * lines as f64)
+    }
+
+    pub fn frame_counter(&self) -> u64 {
+        self.frame_counter
+    }
+
+    pub fn frame(&self) -> Frame<'_> {
+        Frame {
+            width: self.frame_buffer.width(),
+            height: self.frame_buffer.height(),
+            display_mode: self.frame_buffer.display_mode(),
+            aa_mode: self.frame_buffer.aa_mode(),
+            pixels: self.frame_buffer.pixels(),
+        }
+    }
+
     #[inline(always)]
-    pub fn step(&mut self, rdram: &Rdram, gfx: &GfxContext) -> bool {
+    pub fn step(&mut self, rdram: &Rdram, gfx: Option<&GfxContext>) -> bool {
         self.cycles_remaining -= 1;
 
         if self.cycles_remaining > 0 {
@@ -83,7 +263,7 @@ impl VideoInterface {
         self.step_inner(rdram, gfx)
     }
 
-    fn step_inner(&mut self, rdram: &Rdram, gfx: &GfxContext) -> bool {
+    fn step_inner(&mut self, rdram: &Rdram, gfx: Option<&GfxContext>) -> bool {
         self.cycles_remaining = self.cycles_per_line;
 
         let mut half_line = self.regs.v_current.half_line() + 2;
@@ -108,18 +288,13 @@ impl VideoInterface {
         frame_done
     }
 
-    fn render(&mut self, rdram: &Rdram, gfx: &GfxContext) {
+    fn render(&mut self, rdram: &Rdram, gfx: Option<&GfxContext>) {
         let video_width = self.regs.h_video.width() * self.regs.x_scale.scale() / 1024;
 
         let video_height = (self.regs.v_video.width() >> 1) * self.regs.y_scale.scale() / 1024;
 
-        self.frame_buffer.resize(
-            gfx.device(),
-            self.upscaler.texture_bind_group_layout(),
-            self.regs.ctrl.aa_mode(),
-            video_width,
-            video_height,
-        );
+        self.frame_buffer
+            .resize(self.regs.ctrl.aa_mode(), video_width, video_height);
 
         let display_mode = if video_width > 0 && video_height > 0 {
             self.regs.ctrl.display_mode()
@@ -130,12 +305,20 @@ impl VideoInterface {
         // TODO: We should technically upload each display pixel as it occurs
         // rather than doing things all at once at the end of the frame.
         self.frame_buffer.upload(
-            gfx.queue(),
             rdram,
             display_mode,
             self.regs.origin.origin(),
             self.regs.width.width(),
         );
+
+        if let (Some(output), Some(gfx)) = (&mut self.output, gfx) {
+            output.texture.update(
+                gfx.device(),
+                gfx.queue(),
+                output.upscaler.texture_bind_group_layout(),
+                &self.frame_buffer,
+            );
+        }
     }
 
     pub fn read<T: Size>(&self, address: u32) -> T {
@@ -2,16 +2,97 @@ use super::regs::{AntiAliasMode, DisplayMode};
 use crate::gfx;
 use crate::rdram::Rdram;
 
+// The decoded output of the VI, as RGBA8 pixels
 pub struct Framebuffer {
+    width: u32,
+    height: u32,
+    aa_mode: AntiAliasMode,
+    display_mode: DisplayMode,
+    pixel_buf: Vec<u8>,
+}
+
+impl Framebuffer {
+    pub fn new() -> Self {
+        Self {
+            width: 1,
+            height: 1,
+            aa_mode: AntiAliasMode::default(),
+            display_mode: DisplayMode::default(),
+            pixel_buf: vec![0; 4],
+        }
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    pub fn aa_mode(&self) -> AntiAliasMode {
+        self.aa_mode
+    }
+
+    pub fn display_mode(&self) -> DisplayMode {
+        self.display_mode
+    }
+
+    pub fn pixels(&self) -> &[u8] {
+        &self.pixel_buf
+    }
+
+    pub fn resize(&mut self, aa_mode: AntiAliasMode, width: u32, height: u32) {
+        self.width = width.max(1);
+        self.height = height.max(1);
+        self.aa_mode = aa_mode;
+
+        self.pixel_buf
+            .resize(self.width as usize * self.height as usize * 4, 0);
+    }
+
+    pub fn upload(
+        &mut self,
+        rdram: &Rdram,
+        display_mode: DisplayMode,
+        origin: u32,
+        buffer_width: u32,
+    ) {
+        self.display_mode = display_mode;
+
+        match display_mode {
+            DisplayMode::Blank => self.pixel_buf.fill(0),
+            DisplayMode::Reserved => panic!("Use of reserved display mode"),
+            DisplayMode::Color16 => gfx::copy_image_rgba16(
+                rdram,
+                &mut self.pixel_buf,
+                origin,
+                buffer_width,
+                self.width,
+                self.height,
+            ),
+            DisplayMode::Color32 => gfx::copy_image_rgba32(
+                rdram,
+                &mut self.pixel_buf,
+                origin,
+                buffer_width,
+                self.width,
+                self.height,
+            ),
+        }
+    }
+}
+
+// A copy of the frame buffer on the GPU, for the upscaler to draw from
+pub struct FramebufferTexture {
     sampler_linear: wgpu::Sampler,
     sampler_nearest: wgpu::Sampler,
     texture: wgpu::Texture,
     bind_group: wgpu::BindGroup,
     aa_mode: AntiAliasMode,
-    pixel_buf: Vec<u8>,
 }
 
-impl Framebuffer {
+impl FramebufferTexture {
     pub fn new(device: &wgpu::Device, bind_group_layout: &wgpu::BindGroupLayout) -> Self {
         let sampler_linear = device.create_sampler(&wgpu::SamplerDescriptor {
             label: Some("Framebuffer Sampler (Linear)"),
@@ -34,7 +115,6 @@ impl Framebuffer {
             texture,
             bind_group,
             aa_mode: AntiAliasMode::default(),
-            pixel_buf: vec![0; 4],
         }
     }
 
@@ -42,25 +122,26 @@ impl Framebuffer {
         &self.bind_group
     }
 
-    pub fn resize(
+    pub fn update(
         &mut self,
         device: &wgpu::Device,
+        queue: &wgpu::Queue,
         bind_group_layout: &wgpu::BindGroupLayout,
-        aa_mode: AntiAliasMode,
-        width: u32,
-        height: u32,
+        frame_buffer: &Framebuffer,
     ) {
-        let width = width.max(1);
-        let height = height.max(1);
+        let width = frame_buffer.width();
+        let height = frame_buffer.height();
+        let aa_mode = frame_buffer.aa_mode();
 
-        if width != self.texture.width() && height != self.texture.height() {
+        if width != self.texture.width() || height != self.texture.height() {
             self.texture = create_texture(device, width, height);
-            self.pixel_buf
-                .resize(width as usize * height as usize * 4, 0);
         } else if aa_mode == self.aa_mode {
+            self.write(queue, frame_buffer);
             return;
         }
 
+        self.aa_mode = aa_mode;
+
         let sampler = if aa_mode != AntiAliasMode::Off {
             &self.sampler_linear
         } else {
@@ -68,40 +149,10 @@ impl Framebuffer {
         };
 
         self.bind_group = create_bind_group(device, bind_group_layout, &self.texture, sampler);
+        self.write(queue, frame_buffer);
     }
 
-    pub fn upload(
-        &mut self,
-        queue: &wgpu::Queue,
-        rdram: &Rdram,
-        display_mode: DisplayMode,
-        origin: u32,
-        buffer_width: u32,
-    ) {
-        let video_width = self.texture.width();
-        let video_height = self.texture.height();
-
-        match display_mode {
-            DisplayMode::Blank => self.pixel_buf.fill(0),
-            DisplayMode::Reserved => panic!("Use of reserved display mode"),
-            DisplayMode::Color16 => gfx::copy_image_rgba16(
-                rdram,
-                &mut self.pixel_buf,
-                origin,
-                buffer_width,
-                video_width,
-                video_height,
-            ),
-            DisplayMode::Color32 => gfx::copy_image_rgba32(
-                rdram,
-                &mut self.pixel_buf,
-                origin,
-                buffer_width,
-                video_width,
-                video_height,
-            ),
-        }
-
+    fn write(&self, queue: &wgpu::Queue, frame_buffer: &Framebuffer) {
         queue.write_texture(
             wgpu::ImageCopyTexture {
                 texture: &self.texture,
@@ -109,11 +160,11 @@ impl Framebuffer {
                 origin: wgpu::Origin3d::ZERO,
                 aspect: wgpu::TextureAspect::All,
             },
-            &self.pixel_buf,
+            frame_buffer.pixels(),
             wgpu::ImageDataLayout {
                 offset: 0,
-                bytes_per_row: Some(video_width * 4),
-                rows_per_image: Some(video_height),
+                bytes_per_row: Some(self.texture.width() * 4),
+                rows_per_image: Some(self.texture.height()),
             },
             self.texture.size(),
         )
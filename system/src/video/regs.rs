@@ -20,7 +20,7 @@ pub struct Regs {
     pub staged_data: u32,
 }
 
-#[derive(Debug, Default, PartialEq, Eq)]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
 pub enum AntiAliasMode {
     #[default]
     FetchAlways = 0,
@@ -29,7 +29,7 @@ pub enum AntiAliasMode {
     Off,
 }
 
-#[derive(Debug, Default, PartialEq, Eq)]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
 pub enum DisplayMode {
     #[default]
     Blank = 0,
@@ -0,0 +1,180 @@
+use std::cell::Cell;
+use std::fmt::{self, Display};
+
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum AccessSource {
+    #[default]
+    Cpu,
+    RspDma,
+    Rdp,
+    Vi,
+    AiDma,
+    PiDma,
+    SiDma,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum WatchKind {
+    Read,
+    Write,
+    Access,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Watchpoint {
+    pub start: u32,
+    pub len: u32,
+    pub kind: WatchKind,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct WatchHit {
+    pub index: usize,
+    pub source: AccessSource,
+    // PC of the CPU, or of the RSP for RSP DMA
+    pub pc: u32,
+    pub write: bool,
+    // First watched byte of the access, along with up to 8 bytes of data
+    pub address: u32,
+    pub value: u64,
+    pub size: usize,
+}
+
+// Physical address ranges that stop the debugger when accessed. Accesses are
+// checked through a shared reference, so that read paths don't need to change.
+#[derive(Default)]
+pub struct Watch {
+    watchpoints: Vec<Watchpoint>,
+    source: Cell<(AccessSource, u32)>,
+    hit: Cell<Option<WatchHit>>,
+}
+
+impl Watch {
+    pub fn is_active(&self) -> bool {
+        !self.watchpoints.is_empty()
+    }
+
+    pub fn watchpoints(&self) -> &[Watchpoint] {
+        &self.watchpoints
+    }
+
+    pub fn add(&mut self, watchpoint: Watchpoint) -> usize {
+        self.watchpoints.push(watchpoint);
+        self.watchpoints.len() - 1
+    }
+
+    pub fn remove(&mut self, index: usize) -> Option<Watchpoint> {
+        (index < self.watchpoints.len()).then(|| self.watchpoints.remove(index))
+    }
+
+    // Sets where subsequent accesses are coming from
+    pub fn set_source(&self, source: AccessSource, pc: u32) {
+        self.source.set((source, pc));
+    }
+
+    pub fn has_hit(&self) -> bool {
+        self.hit.get().is_some()
+    }
+
+    pub fn take_hit(&self) -> Option<WatchHit> {
+        self.hit.take()
+    }
+
+    // Replaces any hit recorded since 'take_hit' was called
+    pub fn restore_hit(&self, hit: Option<WatchHit>) {
+        self.hit.set(hit);
+    }
+
+    // 'data' is in memory (big-endian) order. Only the first hit is kept
+    // until it is taken.
+    pub fn check(&self, address: u32, data: &[u8], write: bool) {
+        if self.has_hit() {
+            return;
+        }
+
+        let end = address as u64 + data.len() as u64;
+
+        for (index, watchpoint) in self.watchpoints.iter().enumerate() {
+            let matches_kind = match watchpoint.kind {
+                WatchKind::Read => !write,
+                WatchKind::Write => write,
+                WatchKind::Access => true,
+            };
+
+            let start = watchpoint.start.max(address);
+            let watch_end = watchpoint.start as u64 + watchpoint.len as u64;
+
+            if !matches_kind || start as u64 >= end.min(watch_end) {
+                continue;
+            }
+
+            let offset = (start - address) as usize;
+            let size = (end.min(watch_end) - start as u64).min(8) as usize;
+
+            let value = data[offset..(offset + size)]
+                .iter()
+                .fold(0u64, |value, &byte| (value << 8) | byte as u64);
+
+            let (source, pc) = self.source.get();
+
+            self.hit.set(Some(WatchHit {
+                index,
+                source,
+                pc,
+                write,
+                address: start,
+                value,
+                size,
+            }));
+
+            return;
+        }
+    }
+}
+
+impl Display for AccessSource {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Self::Cpu => "CPU",
+            Self::RspDma => "RSP DMA",
+            Self::Rdp => "RDP",
+            Self::Vi => "VI",
+            Self::AiDma => "AI DMA",
+            Self::PiDma => "PI DMA",
+            Self::SiDma => "SI DMA",
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_overlapping_bytes() {
+        let mut watch = Watch::default();
+
+        watch.add(Watchpoint {
+            start: 0x1004,
+            len: 4,
+            kind: WatchKind::Write,
+        });
+
+        watch.check(0x1000, &[0, 1, 2, 3, 4, 5, 6, 7], false);
+        assert!(!watch.has_hit());
+
+        watch.check(0x1008, &[0, 1, 2, 3], true);
+        assert!(!watch.has_hit());
+
+        watch.set_source(AccessSource::PiDma, 0x8000_0400);
+        watch.check(0x1000, &[0, 1, 2, 3, 4, 5, 6, 7], true);
+
+        let hit = watch.take_hit().unwrap();
+        assert_eq!(hit.source, AccessSource::PiDma);
+        assert_eq!(hit.pc, 0x8000_0400);
+        assert_eq!(hit.address, 0x1004);
+        assert_eq!(hit.value, 0x0405_0607);
+        assert_eq!(hit.size, 4);
+        assert!(!watch.has_hit());
+    }
+}
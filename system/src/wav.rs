@@ -0,0 +1,137 @@
+use crate::audio::AudioReceiver;
+use std::io::{self, Seek, SeekFrom, Write};
+
+// Audio is resampled to a fixed rate, as the AI sample rate can change at
+// any time but a WAV file can only have one
+const OUTPUT_SAMPLE_RATE: u32 = 48000;
+
+const HEADER_LEN: u32 = 44;
+
+// Writes stereo 16-bit samples to a WAV file
+pub struct WavWriter<W: Write + Seek> {
+    writer: W,
+    sample_rate: u32,
+    samples_written: u32,
+    // Position of the next output sample between the previous input sample
+    // (0.0) and the one after it (1.0)
+    position: f64,
+    previous: (i16, i16),
+    // The first error hit while receiving samples, reported by 'finish'
+    error: Option<io::Error>,
+}
+
+impl<W: Write + Seek> WavWriter<W> {
+    pub fn new(mut writer: W, sample_rate: u32) -> io::Result<Self> {
+        // Sizes are filled in later by 'finish'
+        write_header(&mut writer, 0)?;
+
+        Ok(Self {
+            writer,
+            sample_rate,
+            samples_written: 0,
+            position: 0.0,
+            previous: (0, 0),
+            error: None,
+        })
+    }
+
+    pub fn write_samples(&mut self, samples: &[(i16, i16)]) -> io::Result<()> {
+        let step = self.sample_rate as f64 / OUTPUT_SAMPLE_RATE as f64;
+        let mut data = Vec::new();
+
+        for &sample in samples {
+            while self.position <= 1.0 {
+                let left = lerp(self.previous.0, sample.0, self.position);
+                let right = lerp(self.previous.1, sample.1, self.position);
+                data.extend_from_slice(&left.to_le_bytes());
+                data.extend_from_slice(&right.to_le_bytes());
+                self.samples_written += 1;
+                self.position += step;
+            }
+
+            self.position -= 1.0;
+            self.previous = sample;
+        }
+
+        self.writer.write_all(&data)
+    }
+
+    pub fn finish(mut self) -> io::Result<()> {
+        if let Some(err) = self.error {
+            return Err(err);
+        }
+
+        self.writer.seek(SeekFrom::Start(0))?;
+        write_header(&mut self.writer, self.samples_written * 4)?;
+        self.writer.flush()
+    }
+}
+
+impl<W: Write + Seek> AudioReceiver for WavWriter<W> {
+    fn queue_sample(&mut self, sample: (i16, i16)) {
+        if self.error.is_some() {
+            return;
+        }
+
+        if let Err(err) = self.write_samples(&[sample]) {
+            self.error = Some(err);
+        }
+    }
+
+    // Sets the rate of the samples that follow
+    fn set_sample_rate(&mut self, sample_rate: u32) {
+        self.sample_rate = sample_rate;
+    }
+}
+
+fn write_header(writer: &mut impl Write, data_len: u32) -> io::Result<()> {
+    let mut header = Vec::with_capacity(HEADER_LEN as usize);
+    header.extend_from_slice(b"RIFF");
+    header.extend_from_slice(&(HEADER_LEN - 8 + data_len).to_le_bytes());
+    header.extend_from_slice(b"WAVEfmt ");
+    header.extend_from_slice(&16u32.to_le_bytes());
+    // PCM, two channels of 16-bit samples
+    header.extend_from_slice(&1u16.to_le_bytes());
+    header.extend_from_slice(&2u16.to_le_bytes());
+    header.extend_from_slice(&OUTPUT_SAMPLE_RATE.to_le_bytes());
+    header.extend_from_slice(&(OUTPUT_SAMPLE_RATE * 4).to_le_bytes());
+    header.extend_from_slice(&4u16.to_le_bytes());
+    header.extend_from_slice(&16u16.to_le_bytes());
+    header.extend_from_slice(b"data");
+    header.extend_from_slice(&data_len.to_le_bytes());
+    writer.write_all(&header)
+}
+
+fn lerp(from: i16, to: i16, position: f64) -> i16 {
+    (from as f64 + (to as f64 - from as f64) * position).round() as i16
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn resamples_across_rate_changes() {
+        let mut output = Cursor::new(Vec::new());
+        let mut wav = WavWriter::new(&mut output, 24000).unwrap();
+
+        // Half a second at each rate
+        wav.write_samples(&[(100, -100); 12000]).unwrap();
+        wav.set_sample_rate(32000);
+
+        for _ in 0..16000 {
+            wav.queue_sample((100, -100));
+        }
+
+        wav.finish().unwrap();
+
+        let data = output.into_inner();
+        let data_len = u32::from_le_bytes(data[40..44].try_into().unwrap());
+        assert_eq!(data_len as usize, data.len() - HEADER_LEN as usize);
+        assert!((data_len / 4).abs_diff(OUTPUT_SAMPLE_RATE) <= 2);
+
+        let last = data.len() - 4;
+        assert_eq!(&data[last..], &[100, 0, 0x9c, 0xff]);
+    }
+}